@@ -262,6 +262,13 @@ pub fn query(deps: Deps<CustomQueryType>, env: Env, msg: QueryMsg) -> StdResult<
             limit,
         } => to_json_binary(&queries::query_exchange_rates(deps, env, start_after, limit)?),
         QueryMsg::Pair {} => to_json_binary(&queries::query_pair(deps, env)?),
+        QueryMsg::Vote {
+            proposal_id,
+        } => to_json_binary(&queries::vote(deps, proposal_id)?),
+        QueryMsg::Votes {
+            start_after,
+            limit,
+        } => to_json_binary(&queries::votes(deps, start_after, limit)?),
     }
 }
 
@@ -1,4 +1,5 @@
 use cosmwasm_std::{DepsMut, Env, Event, MessageInfo, Response};
+use eris::hub::{HubVote, HubVoteOption};
 use eris_chain_adapter::types::CustomQueryType;
 
 use crate::{error::ContractResult, state::State};
@@ -16,12 +17,27 @@ pub fn vote(
 
     let event = Event::new("erishub/voted").add_attribute("prop", proposal_id.to_string());
 
-    let vote = stake.dao_interface.vote_msg(
+    let vote_msg = stake.dao_interface.vote_msg(
         proposal_id,
-        vote,
+        vote.clone(),
         env.contract.address,
         stake.total_utoken_bonded,
     )?;
 
-    Ok(Response::new().add_message(vote).add_event(event).add_attribute("action", "erishub/vote"))
+    state.votes.save(
+        deps.storage,
+        proposal_id,
+        &HubVote {
+            proposal_id,
+            vote: HubVoteOption::Single(vote),
+            voting_power: stake.total_utoken_bonded,
+            timestamp: env.block.time.seconds(),
+            operator: info.sender,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_message(vote_msg)
+        .add_event(event)
+        .add_attribute("action", "erishub/vote"))
 }
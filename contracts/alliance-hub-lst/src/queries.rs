@@ -4,6 +4,7 @@ use astroport::asset::native_asset_info;
 use cosmwasm_std::{Decimal, Deps, Env, Order, StdResult};
 use cw_storage_plus::Bound;
 
+use eris::hub::HubVote;
 use eris::hub_alliance::{ConfigResponse, ExchangeRatesResponse, PairInfo, StateResponse};
 use eris_chain_adapter::types::CustomQueryType;
 
@@ -102,3 +103,28 @@ pub fn query_pair(deps: Deps<CustomQueryType>, env: Env) -> StdResult<PairInfo>
         pair_type: eris::hub_alliance::PairType::Custom("virtual".to_string()),
     })
 }
+
+pub fn vote(deps: Deps<CustomQueryType>, proposal_id: u64) -> StdResult<HubVote> {
+    State::default().votes.load(deps.storage, proposal_id)
+}
+
+pub fn votes(
+    deps: Deps<CustomQueryType>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<HubVote>> {
+    let state = State::default();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    state
+        .votes
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
+}
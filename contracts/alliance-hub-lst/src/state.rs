@@ -2,6 +2,7 @@ use astroport::asset::Asset;
 use cosmwasm_std::{Addr, Decimal, Storage};
 use cw_storage_plus::{Item, Map};
 
+use eris::hub::HubVote;
 use eris::hub_alliance::{FeeConfig, SingleSwapConfig, StakeToken};
 use eris_chain_adapter::types::{DenomType, WithdrawType};
 use serde::{de::DeserializeOwned, Serialize};
@@ -31,6 +32,9 @@ pub struct State<'a> {
     pub fee_config: Item<'a, FeeConfig>,
     /// Specifies wether the contract allows donations
     pub allow_donations: Item<'a, bool>,
+    /// Latest vote cast by the hub, by proposal id. A changed vote replaces the previous one, as
+    /// only the latest vote counts in the DAO.
+    pub votes: Map<'a, u64, HubVote>,
 
     // history of the exchange_rate
     pub exchange_history: Map<'a, u64, Decimal>,
@@ -51,6 +55,7 @@ impl Default for State<'static> {
             unlocked_coins: Item::new("unlocked_coins"),
             fee_config: Item::new("fee_config"),
             allow_donations: Item::new("allow_donations"),
+            votes: Map::new("votes"),
             exchange_history: Map::new("exchange_history"),
             default_max_spread: Item::new("default_max_spread"),
         }
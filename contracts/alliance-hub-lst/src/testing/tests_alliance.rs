@@ -2,19 +2,20 @@ use astroport::asset::{native_asset, native_asset_info, AssetInfoExt};
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     coin, to_json_binary, Addr, Coin, CosmosMsg, Decimal, Event, StdError, StdResult, SubMsg,
-    Uint128, WasmMsg,
+    Uint128, VoteOption, WasmMsg,
 };
 use eris::DecimalCheckedOps;
 
 use eris::adapters::asset::AssetEx;
 use eris::helper::validate_received_funds;
+use eris::hub::{DaoInterface, HubVote, HubVoteOption};
 use eris::hub_alliance::{
     CallbackMsg, ConfigResponse, ExecuteMsg, FeeConfig, QueryMsg, StakeToken, StateResponse,
 };
 
 use eris_chain_shared::chain_trait::ChainInterface;
 
-use crate::contract::execute;
+use crate::contract::{execute, query};
 use crate::error::ContractError;
 use crate::state::State;
 use crate::testing::helpers::{
@@ -1211,3 +1212,144 @@ fn receiving_funds() {
     let amount = validate_received_funds(&[Coin::new(69420, MOCK_UTOKEN)], &mock_utoken()).unwrap();
     assert_eq!(amount, Uint128::new(69420));
 }
+
+//--------------------------------------------------------------------------------------------------
+// Gov
+//--------------------------------------------------------------------------------------------------
+
+#[test]
+fn voting() {
+    let (mut deps, mut stake) = setup_test();
+    let state = State::default();
+
+    let vote = |proposal_id: u64, vote: VoteOption| ExecuteMsg::Vote {
+        proposal_id,
+        vote,
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), vote(3, VoteOption::Yes))
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoVoteOperatorSet {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            allow_donations: None,
+            default_max_spread: None,
+            vote_operator: Some("vote_operator".to_string()),
+        },
+    )
+    .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), vote(3, VoteOption::Yes))
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedSenderNotVoteOperator {});
+
+    // alliance itself has no governance
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        vote(3, VoteOption::Yes),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("voting not supported for alliance")));
+
+    stake.dao_interface = DaoInterface::Capa {
+        gov: Addr::unchecked("gov"),
+    };
+    stake.total_utoken_bonded = Uint128::new(1000);
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        vote(3, VoteOption::Yes),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        stake
+            .dao_interface
+            .vote_msg(3, VoteOption::Yes, Addr::unchecked(MOCK_CONTRACT_ADDR), Uint128::new(1000))
+            .unwrap()
+    );
+
+    // changing the vote replaces the stored one, as the DAO only counts the latest vote
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(100),
+        mock_info("vote_operator", &[]),
+        vote(3, VoteOption::No),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(200),
+        mock_info("vote_operator", &[]),
+        vote(5, VoteOption::Yes),
+    )
+    .unwrap();
+
+    let vote: HubVote = query_helper(
+        deps.as_ref(),
+        QueryMsg::Vote {
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(
+        vote,
+        HubVote {
+            proposal_id: 3,
+            vote: HubVoteOption::Single(VoteOption::No),
+            voting_power: Uint128::new(1000),
+            timestamp: 100,
+            operator: Addr::unchecked("vote_operator"),
+        }
+    );
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Vote {
+            proposal_id: 4,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, StdError::NotFound { .. }));
+
+    let votes: Vec<HubVote> = query_helper(
+        deps.as_ref(),
+        QueryMsg::Votes {
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(
+        votes.iter().map(|v| (v.proposal_id, v.vote.clone())).collect::<Vec<_>>(),
+        vec![
+            (3, HubVoteOption::Single(VoteOption::No)),
+            (5, HubVoteOption::Single(VoteOption::Yes))
+        ]
+    );
+
+    let votes: Vec<HubVote> = query_helper(
+        deps.as_ref(),
+        QueryMsg::Votes {
+            start_after: Some(3),
+            limit: Some(1),
+        },
+    );
+    assert_eq!(votes.len(), 1);
+    assert_eq!(votes[0].proposal_id, 5);
+    assert_eq!(votes[0].timestamp, 200);
+}
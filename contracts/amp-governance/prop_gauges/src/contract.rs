@@ -2,8 +2,8 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
-    VoteOption,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128, VoteOption,
 };
 use cw2::{get_contract_version, set_contract_version};
 use eris::adapters::hub::Hub;
@@ -12,15 +12,22 @@ use eris_chain_adapter::types::CustomMsgType;
 
 use eris::governance_helper::get_period;
 use eris::helpers::bps::BasicPoints;
-use eris::prop_gauges::{ExecuteMsg, InstantiateMsg, MigrateMsg, PropInfo, PropUserInfo, QueryMsg};
-use eris::voting_escrow::{get_lock_info, get_total_voting_power_at_by_period, LockInfoResponse};
+use eris::prop_gauges::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PropInfo, PropThresholds, PropUserInfo, QueryMsg,
+};
+use eris::voting_escrow::{
+    get_lock_info, get_total_voting_power_at_by_period, LockInfoResponse, DEFAULT_LIMIT, MAX_LIMIT,
+};
 
 use crate::error::ContractError;
 use crate::queries::{
     get_active_props, get_finished_props, get_prop_detail, get_prop_voters, get_user_votes,
 };
 use crate::state::{Config, State};
-use crate::vote::{get_vote_msg, remove_vote_of_user, update_vote_state};
+use crate::vote::{
+    get_vote_msg, get_wanted_vote_msg, is_frozen, is_locked_in, remove_vote_of_user,
+    update_vote_state,
+};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "prop-gauges";
@@ -46,6 +53,9 @@ pub fn instantiate(
     let state = State::default();
 
     BasicPoints::try_from(msg.quorum_bps)?;
+    if let Some(veto_threshold_bps) = msg.veto_threshold_bps {
+        BasicPoints::try_from(veto_threshold_bps)?;
+    }
 
     state.config.save(
         deps.storage,
@@ -54,6 +64,10 @@ pub fn instantiate(
             escrow_addr: deps.api.addr_validate(&msg.escrow_addr)?,
             hub_addr: deps.api.addr_validate(&msg.hub_addr)?,
             quorum_bps: msg.quorum_bps,
+            default_vote: msg.default_vote,
+            veto_threshold_bps: msg.veto_threshold_bps,
+            lock_in_s: msg.lock_in_s.unwrap_or_default(),
+            gov_multiple: msg.gov_multiple.unwrap_or_default(),
         },
     )?;
 
@@ -63,7 +77,12 @@ pub fn instantiate(
 /// Exposes all the execute functions available in the contract.
 ///
 /// ## Execute messages
-/// * **ExecuteMsg::Vote { votes }** Casts votes for pools
+/// * **ExecuteMsg::SyncProps { limit }** Initializes open proposals of the DAO that are not yet known
+///
+/// * **ExecuteMsg::FinalizeProp { proposal_id }** Casts the final vote during the lock-in window
+///
+/// * **ExecuteMsg::Vote { proposal_id, vote, option }** Casts a vote or an option of a multiple
+/// choice proposal
 ///
 /// * **ExecuteMsg::TunePools** Launches pool tuning
 ///
@@ -85,10 +104,17 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
         ExecuteMsg::InitProp {
             proposal_id,
         } => init_prop(deps, env, info, proposal_id),
+        ExecuteMsg::SyncProps {
+            limit,
+        } => sync_props(deps, env, limit),
+        ExecuteMsg::FinalizeProp {
+            proposal_id,
+        } => finalize_prop(deps, env, proposal_id),
         ExecuteMsg::Vote {
             proposal_id,
             vote,
-        } => handle_vote(deps, env, info, proposal_id, vote),
+            option,
+        } => handle_vote(deps, env, info, proposal_id, vote, option),
         ExecuteMsg::UpdateVote {
             user,
             lock_info,
@@ -101,7 +127,26 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
         } => remove_prop(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             quorum_bps,
-        } => update_config(deps, info, quorum_bps),
+            default_vote,
+            veto_threshold_bps,
+            lock_in_s,
+            remove_default_vote,
+            remove_veto_threshold,
+        } => update_config(
+            deps,
+            info,
+            quorum_bps,
+            default_vote,
+            veto_threshold_bps,
+            lock_in_s,
+            remove_default_vote,
+            remove_veto_threshold,
+        ),
+        ExecuteMsg::SetPropThresholds {
+            proposal_id,
+            quorum_bps,
+            veto_threshold_bps,
+        } => set_prop_thresholds(deps, info, proposal_id, quorum_bps, veto_threshold_bps),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -162,13 +207,158 @@ fn init_prop(deps: DepsMut, env: Env, _info: MessageInfo, proposal_id: u64) -> E
     }
 
     let hub_config = Hub(config.hub_addr.clone()).query_config(&deps.querier)?;
-    let proposal = hub_config.dao_interface.query_proposal(&deps.querier, proposal_id)?;
+    let proposal = if config.gov_multiple {
+        hub_config.dao_interface.query_proposal_multiple(&deps.querier, proposal_id)?
+    } else {
+        hub_config.dao_interface.query_proposal(&deps.querier, proposal_id)?
+    };
     let end_time_s = proposal.end_time_s;
 
     if end_time_s < env.block.time.seconds() {
         return Err(StdError::generic_err("End time can't be in the past.").into());
     }
 
+    let default_vote = config.default_vote.clone().unwrap_or(VoteOption::Abstain);
+    let period = save_new_prop(
+        deps,
+        &state,
+        &config,
+        proposal_id,
+        end_time_s,
+        proposal.choices,
+        default_vote.clone(),
+    )?;
+
+    // multiple choice proposals can't be abstained, the hub only votes once the quorum is reached.
+    let default_vote_msg = if proposal.choices.is_none() {
+        Some(Hub(config.hub_addr).vote_msg(proposal_id, default_vote)?)
+    } else {
+        None
+    };
+
+    Ok(Response::new()
+        .add_optional_message(default_vote_msg)
+        .add_attribute("action", "prop/init_prop")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("end", period.to_string()))
+}
+
+fn sync_props(deps: DepsMut, env: Env, limit: Option<u32>) -> ExecuteResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let start_after = state.last_synced_prop.may_load(deps.storage)?;
+
+    let hub_config = Hub(config.hub_addr.clone()).query_config(&deps.querier)?;
+    let proposals = if config.gov_multiple {
+        hub_config.dao_interface.list_proposals_multiple(&deps.querier, start_after, Some(limit))?
+    } else {
+        hub_config.dao_interface.list_proposals(&deps.querier, start_after, Some(limit))?
+    };
+
+    let current_time = env.block.time.seconds();
+    let mut response = Response::new();
+    let mut ids: Vec<String> = vec![];
+    let mut cursor = start_after;
+    let mut cursor_blocked = false;
+
+    for proposal in proposals.iter() {
+        let is_known = state.props.may_load(deps.storage, proposal.id)?.is_some();
+        let sync_end_time_s = match proposal.end_time_s {
+            Some(end_time_s) if proposal.is_open && !is_known && end_time_s >= current_time => {
+                Some(end_time_s)
+            },
+            _ => None,
+        };
+
+        // the cursor only moves over proposals that never need to be checked again. Proposals that
+        // are not open yet or do not end at a specific time hold it until they ended or closed.
+        let is_final = is_known
+            || sync_end_time_s.is_some()
+            || match proposal.end_time_s {
+                Some(end_time_s) => end_time_s < current_time,
+                None => !proposal.is_open,
+            };
+        cursor_blocked = cursor_blocked || !is_final;
+        if !cursor_blocked {
+            cursor = Some(proposal.id);
+        }
+
+        let end_time_s = match sync_end_time_s {
+            Some(end_time_s) => end_time_s,
+            None => continue,
+        };
+
+        // without a configured default vote, the prop is only tracked and the hub does not vote yet.
+        let vote = config.default_vote.clone().unwrap_or(VoteOption::Abstain);
+        save_new_prop(
+            deps.branch(),
+            &state,
+            &config,
+            proposal.id,
+            end_time_s,
+            proposal.choices,
+            vote,
+        )?;
+
+        if let (Some(default_vote), None) = (config.default_vote.clone(), proposal.choices) {
+            response = response
+                .add_message(Hub(config.hub_addr.clone()).vote_msg(proposal.id, default_vote)?);
+        }
+
+        ids.push(proposal.id.to_string());
+    }
+
+    if let Some(cursor) = cursor {
+        state.last_synced_prop.save(deps.storage, &cursor)?;
+    }
+
+    Ok(response.add_attribute("action", "prop/sync_props").add_attribute("props", ids.join(",")))
+}
+
+fn finalize_prop(deps: DepsMut, env: Env, proposal_id: u64) -> ExecuteResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+
+    let mut prop = state.props.load(deps.storage, proposal_id).map_err(|_| {
+        StdError::generic_err(format!("proposal with id {0} not initialized", proposal_id))
+    })?;
+
+    if prop.finalized {
+        return Err(ContractError::PropAlreadyFinalized {});
+    }
+
+    if !is_locked_in(&env, &config, &prop) || env.block.time.seconds() > prop.end_time_s {
+        return Err(ContractError::PropNotInLockIn {});
+    }
+
+    let total_vp = get_total_voting_power_at_by_period(
+        &deps.querier,
+        config.escrow_addr.clone(),
+        prop.period,
+    )?;
+    let vote_msg =
+        get_wanted_vote_msg(deps.storage, &state, &config, &mut prop, proposal_id, total_vp)?;
+
+    prop.total_vp = total_vp;
+    prop.finalized = true;
+    state.props.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_optional_message(vote_msg)
+        .add_attribute("action", "prop/finalize_prop")
+        .add_attribute("prop", proposal_id.to_string()))
+}
+
+fn save_new_prop(
+    deps: DepsMut,
+    state: &State,
+    config: &Config,
+    proposal_id: u64,
+    end_time_s: u64,
+    choices: Option<u32>,
+    current_vote: VoteOption,
+) -> StdResult<u64> {
     let period = get_period(end_time_s)?;
 
     state.props.save(
@@ -179,24 +369,21 @@ fn init_prop(deps: DepsMut, env: Env, _info: MessageInfo, proposal_id: u64) -> E
             period,
             total_vp: get_total_voting_power_at_by_period(
                 &deps.querier,
-                config.escrow_addr,
+                config.escrow_addr.clone(),
                 period,
             )?,
-            current_vote: VoteOption::Abstain,
+            current_vote,
             no_vp: Uint128::zero(),
             abstain_vp: Uint128::zero(),
             yes_vp: Uint128::zero(),
             nwv_vp: Uint128::zero(),
+            option_vp: vec![Uint128::zero(); choices.unwrap_or_default() as usize],
+            current_option: None,
+            finalized: false,
         },
     )?;
 
-    let default_vote_msg = Hub(config.hub_addr).vote_msg(proposal_id, VoteOption::Abstain)?;
-
-    Ok(Response::new()
-        .add_message(default_vote_msg)
-        .add_attribute("action", "prop/init_prop")
-        .add_attribute("prop", proposal_id.to_string())
-        .add_attribute("end", period.to_string()))
+    Ok(period)
 }
 
 /// The function checks that:
@@ -218,10 +405,17 @@ fn handle_vote(
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
-    vote: VoteOption,
+    vote: Option<VoteOption>,
+    option: Option<u32>,
 ) -> ExecuteResult {
     let sender = info.sender;
 
+    let (vote, option) = match (vote, option) {
+        (Some(vote), None) => (vote, None),
+        (None, Some(option)) => (VoteOption::Abstain, Some(option)),
+        _ => return Err(ContractError::InvalidVote {}),
+    };
+
     let state = State::default();
     let config = state.config.load(deps.storage)?;
 
@@ -231,10 +425,20 @@ fn handle_vote(
         return Err(ContractError::ZeroVotingPower {});
     }
 
+    let prop = state.props.load(deps.storage, proposal_id).map_err(|_| {
+        StdError::generic_err(format!("proposal with id {0} not initialized", proposal_id))
+    })?;
+
+    // the tally is frozen once the lock-in window opens, FinalizeProp forwards it.
+    if is_frozen(&env, &config, &prop) {
+        return Err(ContractError::PropLockedIn {});
+    }
+
     let user_info =
         state.get_user_info(deps.storage, proposal_id, &sender)?.unwrap_or(PropUserInfo {
             user: sender.clone(),
             current_vote: VoteOption::Abstain,
+            current_option: None,
             vp: Uint128::zero(),
         });
 
@@ -246,8 +450,9 @@ fn handle_vote(
         &config,
         &sender,
         proposal_id,
-        None,
+        Some(prop),
         vote,
+        option,
         user_info,
         &ve_lock_info,
     )?;
@@ -276,6 +481,10 @@ fn update_vote(
     let mut response = Response::new();
 
     for (proposal_id, prop) in state.all_active_props(deps.storage, &env)?.into_iter() {
+        if is_frozen(&env, &config, &prop) {
+            continue;
+        }
+
         let user = state.get_user_info(deps.storage, proposal_id, &sender)?;
 
         if let Some(user_info) = user {
@@ -289,6 +498,7 @@ fn update_vote(
                 proposal_id,
                 Some(prop),
                 user_info.current_vote.clone(),
+                user_info.current_option,
                 user_info,
                 &ve_lock_info,
             )?;
@@ -311,13 +521,24 @@ fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> Exec
 
     let mut response = Response::new();
     for (proposal_id, prop) in state.all_active_props(deps.storage, &env)?.into_iter() {
+        if is_frozen(&env, &config, &prop) {
+            continue;
+        }
+
         let user = state.get_user_info(deps.storage, proposal_id, &user_addr)?;
 
         if let Some(user) = user {
             let mut prop = remove_vote_of_user(prop, &user)?;
 
-            let (vote_msg, total_vp) =
-                get_vote_msg(&deps.querier, &config, &mut prop, proposal_id)?;
+            let (vote_msg, total_vp) = get_vote_msg(
+                &env,
+                &deps.querier,
+                deps.storage,
+                &state,
+                &config,
+                &mut prop,
+                proposal_id,
+            )?;
 
             prop.total_vp = total_vp;
 
@@ -340,10 +561,44 @@ fn remove_prop(deps: DepsMut, _env: Env, info: MessageInfo, proposal_id: u64) ->
     config.assert_owner(&info.sender)?;
 
     state.props.remove(deps.storage, proposal_id)?;
+    state.prop_thresholds.remove(deps.storage, proposal_id);
 
     Ok(Response::new().add_attribute("action", "prop/remove_prop"))
 }
 
+fn set_prop_thresholds(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    quorum_bps: Option<u16>,
+    veto_threshold_bps: Option<u16>,
+) -> ExecuteResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    config.assert_owner(&info.sender)?;
+
+    if quorum_bps.is_none() && veto_threshold_bps.is_none() {
+        state.prop_thresholds.remove(deps.storage, proposal_id);
+    } else {
+        for bps in [quorum_bps, veto_threshold_bps].into_iter().flatten() {
+            BasicPoints::try_from(bps)?;
+        }
+
+        state.prop_thresholds.save(
+            deps.storage,
+            proposal_id,
+            &PropThresholds {
+                quorum_bps,
+                veto_threshold_bps,
+            },
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "prop/set_prop_thresholds")
+        .add_attribute("prop", proposal_id.to_string()))
+}
+
 /// Only contract owner can call this function.  
 /// The function sets a new limit of blacklisted voters that can be kicked at once.
 ///
@@ -354,7 +609,17 @@ fn remove_prop(deps: DepsMut, _env: Env, info: MessageInfo, proposal_id: u64) ->
 /// * **main_pool_min_alloc** is a minimum percentage of ASTRO emissions that this pool should get every block
 ///
 /// * **remove_main_pool** should the main pool be removed or not
-fn update_config(deps: DepsMut, info: MessageInfo, quorum_bps: Option<u16>) -> ExecuteResult {
+#[allow(clippy::too_many_arguments)]
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    quorum_bps: Option<u16>,
+    default_vote: Option<VoteOption>,
+    veto_threshold_bps: Option<u16>,
+    lock_in_s: Option<u64>,
+    remove_default_vote: Option<bool>,
+    remove_veto_threshold: Option<bool>,
+) -> ExecuteResult {
     let state = State::default();
     let mut config = state.config.load(deps.storage)?;
     config.assert_owner(&info.sender)?;
@@ -364,6 +629,27 @@ fn update_config(deps: DepsMut, info: MessageInfo, quorum_bps: Option<u16>) -> E
         config.quorum_bps = quorum_bps;
     }
 
+    if let Some(default_vote) = default_vote {
+        config.default_vote = Some(default_vote);
+    }
+
+    if remove_default_vote.unwrap_or_default() {
+        config.default_vote = None;
+    }
+
+    if let Some(veto_threshold_bps) = veto_threshold_bps {
+        BasicPoints::try_from(veto_threshold_bps)?;
+        config.veto_threshold_bps = Some(veto_threshold_bps);
+    }
+
+    if remove_veto_threshold.unwrap_or_default() {
+        config.veto_threshold_bps = None;
+    }
+
+    if let Some(lock_in_s) = lock_in_s {
+        config.lock_in_s = lock_in_s;
+    }
+
     state.config.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attribute("action", "prop/update_config"))
@@ -13,6 +13,18 @@ pub enum ContractError {
     #[error("You can't vote with zero voting power")]
     ZeroVotingPower {},
 
+    #[error("Either a vote or an option needs to be provided")]
+    InvalidVote {},
+
+    #[error("Proposal has already been finalized")]
+    PropAlreadyFinalized {},
+
+    #[error("Proposal can only be finalized during its lock-in window")]
+    PropNotInLockIn {},
+
+    #[error("Votes can't be changed during the lock-in window of a proposal")]
+    PropLockedIn {},
+
     #[error("Invalid validator address: {0}")]
     InvalidValidatorAddress(String),
 
@@ -72,6 +72,7 @@ pub fn get_user_votes(
             Ok(UserPropResponseItem {
                 id: id.0,
                 current_vote: prop.current_vote,
+                current_option: prop.current_option,
                 vp: prop.vp,
             })
         })
@@ -128,9 +129,12 @@ pub fn get_prop_detail(
         None
     };
 
+    let thresholds = state.prop_thresholds.may_load(deps.storage, proposal_id)?;
+
     Ok(PropDetailResponse {
         prop,
         user,
+        thresholds,
     })
 }
 
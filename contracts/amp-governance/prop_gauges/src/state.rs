@@ -2,7 +2,7 @@ use astroport::common::OwnershipProposal;
 
 use cosmwasm_std::{Addr, Env, StdResult, Storage, VoteOption};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
-use eris::prop_gauges::{ConfigResponse, PropInfo, PropUserInfo};
+use eris::prop_gauges::{ConfigResponse, PropInfo, PropThresholds, PropUserInfo};
 
 pub type Config = ConfigResponse;
 
@@ -12,6 +12,10 @@ pub(crate) struct State<'a> {
     pub ownership_proposal: Item<'a, OwnershipProposal>,
     pub users: IndexedMap<'a, (u64, Addr), PropUserInfo, UserIndexes<'a>>,
     pub voters: Map<'a, (u64, u128, Addr), VoteOption>,
+    /// Last proposal id of the DAO that has been checked by SyncProps
+    pub last_synced_prop: Item<'a, u64>,
+    /// Overrides of the quorum and veto threshold per proposal
+    pub prop_thresholds: Map<'a, u64, PropThresholds>,
 }
 
 impl Default for State<'static> {
@@ -29,6 +33,8 @@ impl Default for State<'static> {
             ownership_proposal: Item::new("ownership_proposal"),
             users: IndexedMap::new("users", user_indexes),
             voters: Map::new("voters"),
+            last_synced_prop: Item::new("last_synced_prop"),
+            prop_thresholds: Map::new("prop_thresholds"),
         }
     }
 }
@@ -9,7 +9,11 @@ use cosmwasm_std::{
     SystemError, Timestamp, Uint128, WasmQuery,
 };
 use cw20::Cw20QueryMsg;
-use eris::adapters::dao::{Cw3ProposalResponse, EnterpriseProposalResponse};
+use eris::adapters::dao::{
+    Cw3ProposalListItem, Cw3ProposalListResponse, Cw3ProposalResponse, DaoDaoChoice,
+    DaoDaoProposalListItem, DaoDaoProposalListResponse, DaoDaoProposalResponse,
+    DaoDaoProposalStatus, DaoDaoQueryMsg, DaoDaoSingleChoiceProposal, EnterpriseProposalResponse,
+};
 use eris::voting_escrow::{LockInfoResponse, VotingPowerResponse};
 
 use super::cw20_querier::Cw20Querier;
@@ -23,6 +27,13 @@ pub(super) struct CustomQuerier {
 
     pub vp: HashMap<String, LockInfoResponse>,
     pub prop_map: HashMap<u64, u64>,
+    /// multiple choice proposals, if set the hub uses a DaoDao interface
+    pub prop_choices: HashMap<u64, u32>,
+    /// status of listed proposals (default open)
+    pub prop_status: HashMap<u64, cw3::Status>,
+    /// end time and choices of the proposals of the "gov_multiple" module, if set the hub uses a
+    /// DaoDao interface with a separate multiple choice module
+    pub multiple_props: HashMap<u64, (u64, u32)>,
 }
 
 impl Querier for CustomQuerier {
@@ -85,6 +96,19 @@ impl CustomQuerier {
         self.prop_map.insert(proposal, end_time_s);
     }
 
+    pub fn set_prop_status(&mut self, proposal: u64, status: cw3::Status) {
+        self.prop_status.insert(proposal, status);
+    }
+
+    pub fn set_multiple_prop(&mut self, proposal: u64, end_time_s: u64, choices: u32) {
+        self.multiple_props.insert(proposal, (end_time_s, choices));
+    }
+
+    pub fn set_prop_choices(&mut self, proposal: u64, end_time_s: u64, choices: u32) {
+        self.prop_map.insert(proposal, end_time_s);
+        self.prop_choices.insert(proposal, choices);
+    }
+
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match request {
             QueryRequest::Wasm(WasmQuery::Smart {
@@ -112,11 +136,28 @@ impl CustomQuerier {
                         allow_donations: false,
                         vote_operator: None,
                         utoken: native_asset_info("utoken".to_string()),
-                        dao_interface: eris::hub::DaoInterface::Cw4 {
-                            addr: Addr::unchecked("cw4"),
-                            gov: Addr::unchecked("gov"),
-                            fund_distributor: Addr::unchecked("fund"),
+                        dao_interface: if self.prop_choices.is_empty()
+                            && self.multiple_props.is_empty()
+                        {
+                            eris::hub::DaoInterface::Cw4 {
+                                addr: Addr::unchecked("cw4"),
+                                gov: Addr::unchecked("gov"),
+                                fund_distributor: Addr::unchecked("fund"),
+                            }
+                        } else {
+                            eris::hub::DaoInterface::DaoDao {
+                                staking: Addr::unchecked("staking"),
+                                gov: Addr::unchecked("gov"),
+                                gov_multiple: if self.multiple_props.is_empty() {
+                                    None
+                                } else {
+                                    Some(Addr::unchecked("gov_multiple"))
+                                },
+                                cw_rewards: Addr::unchecked("cw_rewards"),
+                            }
                         },
+                        reward_sources: vec![],
+                        ustake_snapshot: None,
                     });
                 }
 
@@ -124,8 +165,67 @@ impl CustomQuerier {
                     return self.handle_vp_query(contract_addr, query);
                 }
 
+                if contract_addr == "gov_multiple" {
+                    return match from_json::<DaoDaoQueryMsg>(msg) {
+                        Ok(DaoDaoQueryMsg::Proposal {
+                            proposal_id,
+                        }) => match self.multiple_props.get(&proposal_id) {
+                            Some((end_time_s, choices)) => self.to_result(DaoDaoProposalResponse {
+                                id: proposal_id,
+                                proposal: DaoDaoSingleChoiceProposal {
+                                    expiration: cw20::Expiration::AtTime(Timestamp::from_seconds(
+                                        *end_time_s,
+                                    )),
+                                    choices: dao_dao_choices(*choices),
+                                },
+                            }),
+                            None => err_unsupported_query(msg),
+                        },
+                        Ok(DaoDaoQueryMsg::ListProposals {
+                            start_after,
+                            limit,
+                        }) => {
+                            let mut ids = self.multiple_props.keys().copied().collect::<Vec<u64>>();
+                            ids.sort();
+
+                            self.to_result(DaoDaoProposalListResponse {
+                                proposals: ids
+                                    .into_iter()
+                                    .filter(|id| start_after.map_or(true, |start| *id > start))
+                                    .take(limit.unwrap_or(10) as usize)
+                                    .map(|id| DaoDaoProposalListItem {
+                                        id,
+                                        proposal: DaoDaoProposalStatus {
+                                            expiration: cw20::Expiration::AtTime(
+                                                Timestamp::from_seconds(self.multiple_props[&id].0),
+                                            ),
+                                            status: "open".to_string(),
+                                            choices: dao_dao_choices(self.multiple_props[&id].1),
+                                        },
+                                    })
+                                    .collect(),
+                            })
+                        },
+                        Err(_) => err_unsupported_query(msg),
+                    };
+                }
+
                 if let Ok(query) = from_json::<eris::adapters::dao::Cw3QueryMsg>(msg) {
                     return match query {
+                        // cw3 and dao-proposal-multiple share the same query
+                        eris::adapters::dao::Cw3QueryMsg::Proposal {
+                            proposal_id,
+                        } if self.prop_choices.contains_key(&proposal_id) => {
+                            self.to_result(DaoDaoProposalResponse {
+                                id: proposal_id,
+                                proposal: DaoDaoSingleChoiceProposal {
+                                    expiration: cw20::Expiration::AtTime(Timestamp::from_seconds(
+                                        self.prop_map[&proposal_id],
+                                    )),
+                                    choices: dao_dao_choices(self.prop_choices[&proposal_id]),
+                                },
+                            })
+                        },
                         eris::adapters::dao::Cw3QueryMsg::Proposal {
                             proposal_id,
                         } => match self.prop_map.get(&proposal_id) {
@@ -135,6 +235,32 @@ impl CustomQuerier {
                             }),
                             None => err_unsupported_query(msg),
                         },
+                        eris::adapters::dao::Cw3QueryMsg::ListProposals {
+                            start_after,
+                            limit,
+                        } => {
+                            let mut ids = self.prop_map.keys().copied().collect::<Vec<u64>>();
+                            ids.sort();
+
+                            self.to_result(Cw3ProposalListResponse {
+                                proposals: ids
+                                    .into_iter()
+                                    .filter(|id| start_after.map_or(true, |start| *id > start))
+                                    .take(limit.unwrap_or(10) as usize)
+                                    .map(|id| Cw3ProposalListItem {
+                                        id,
+                                        status: self
+                                            .prop_status
+                                            .get(&id)
+                                            .cloned()
+                                            .unwrap_or(cw3::Status::Open),
+                                        expires: cw20::Expiration::AtTime(Timestamp::from_seconds(
+                                            self.prop_map[&id],
+                                        )),
+                                    })
+                                    .collect(),
+                            })
+                        },
                     };
                 }
 
@@ -153,6 +279,9 @@ impl CustomQuerier {
                                 None => err_unsupported_query(msg),
                             }
                         },
+                        eris::adapters::dao::EnterpriseQueryMsg::Proposals(_) => {
+                            err_unsupported_query(msg)
+                        },
                         // eris::adapters::dao::EnterpriseQueryMsg::Proposal(params) => {
                         //     match self.prop_map.get(&params.proposal_id) {
                         //         Some(val) => self.to_result(EnterpriseProposalResponse {
@@ -249,3 +378,11 @@ impl CustomQuerier {
         }
     }
 }
+
+fn dao_dao_choices(choices: u32) -> Vec<DaoDaoChoice> {
+    (0..choices)
+        .map(|index| DaoDaoChoice {
+            index,
+        })
+        .collect()
+}
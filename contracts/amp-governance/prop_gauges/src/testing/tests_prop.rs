@@ -7,8 +7,9 @@ use cosmwasm_std::{
 
 use eris::governance_helper::{get_period, EPOCH_START, WEEK};
 use eris::prop_gauges::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, PropDetailResponse, PropInfo, PropUserInfo,
-    PropVotersResponse, PropsResponse, QueryMsg, UserPropResponseItem, UserVotesResponse,
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PropDetailResponse, PropInfo, PropThresholds,
+    PropUserInfo, PropVotersResponse, PropsResponse, QueryMsg, UserPropResponseItem,
+    UserVotesResponse,
 };
 use eris::voting_escrow::LockInfoResponse;
 use itertools::Itertools;
@@ -36,6 +37,10 @@ fn setup_test() -> OwnedDeps<MockStorage, MockApi, CustomQuerier> {
             escrow_addr: "escrow".to_string(),
             hub_addr: "hub".to_string(),
             quorum_bps: 500,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            gov_multiple: None,
         },
     )
     .unwrap();
@@ -61,6 +66,10 @@ fn proper_instantiation() {
             escrow_addr: Addr::unchecked("escrow"),
             hub_addr: Addr::unchecked("hub"),
             quorum_bps: 500,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: 0,
+            gov_multiple: false,
         }
     );
 
@@ -150,11 +159,14 @@ fn check_init_prop() {
                     abstain_vp: Uint128::zero(),
                     no_vp: Uint128::zero(),
                     nwv_vp: Uint128::zero(),
+                    option_vp: vec![],
                     yes_vp: Uint128::zero(),
                     current_vote: VoteOption::Abstain,
                     end_time_s: EPOCH_START + WEEK * 10,
                     period: get_period(EPOCH_START + WEEK * 10).unwrap(),
-                    total_vp: Uint128::zero()
+                    total_vp: Uint128::zero(),
+                    current_option: None,
+                    finalized: false
                 }
             )]
         }
@@ -177,11 +189,14 @@ fn check_init_prop() {
                     abstain_vp: Uint128::zero(),
                     no_vp: Uint128::zero(),
                     nwv_vp: Uint128::zero(),
+                    option_vp: vec![],
                     yes_vp: Uint128::zero(),
                     current_vote: VoteOption::Abstain,
                     end_time_s: EPOCH_START + WEEK * 10,
                     period: get_period(EPOCH_START + WEEK * 10).unwrap(),
-                    total_vp: Uint128::zero()
+                    total_vp: Uint128::zero(),
+                    current_option: None,
+                    finalized: false
                 }
             )]
         }
@@ -231,11 +246,14 @@ fn check_init_prop() {
                         abstain_vp: Uint128::zero(),
                         no_vp: Uint128::zero(),
                         nwv_vp: Uint128::zero(),
+                        option_vp: vec![],
                         yes_vp: Uint128::zero(),
                         current_vote: VoteOption::Abstain,
                         end_time_s: EPOCH_START + WEEK * 10,
                         period: get_period(EPOCH_START + WEEK * 10).unwrap(),
-                        total_vp: Uint128::zero()
+                        total_vp: Uint128::zero(),
+                        current_option: None,
+                        finalized: false
                     },
                 ),
                 (
@@ -244,11 +262,14 @@ fn check_init_prop() {
                         abstain_vp: Uint128::zero(),
                         no_vp: Uint128::zero(),
                         nwv_vp: Uint128::zero(),
+                        option_vp: vec![],
                         yes_vp: Uint128::zero(),
                         current_vote: VoteOption::Abstain,
                         end_time_s: EPOCH_START + WEEK * 11,
                         period: get_period(EPOCH_START + WEEK * 11).unwrap(),
-                        total_vp: Uint128::zero()
+                        total_vp: Uint128::zero(),
+                        current_option: None,
+                        finalized: false
                     },
                 )
             ]
@@ -270,7 +291,8 @@ fn vote_prop() {
         mock_info("user", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+            vote: Some(cosmwasm_std::VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -284,7 +306,8 @@ fn vote_prop() {
         mock_info("user2", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::No,
+            vote: Some(cosmwasm_std::VoteOption::No),
+            option: None,
         },
     )
     .unwrap();
@@ -321,11 +344,14 @@ fn vote_prop() {
                     abstain_vp: Uint128::zero(),
                     no_vp: Uint128::new(198),
                     nwv_vp: Uint128::zero(),
+                    option_vp: vec![],
                     yes_vp: Uint128::new(7),
                     current_vote: cosmwasm_std::VoteOption::No,
                     end_time_s: EPOCH_START + WEEK * 3,
                     period: get_period(EPOCH_START + WEEK * 3).unwrap(),
-                    total_vp: Uint128::new(204)
+                    total_vp: Uint128::new(204),
+                    current_option: None,
+                    finalized: false
                 },
             )]
         }
@@ -337,7 +363,8 @@ fn vote_prop() {
         mock_info("user2", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+            vote: Some(cosmwasm_std::VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -374,11 +401,14 @@ fn vote_prop() {
                     abstain_vp: Uint128::zero(),
                     no_vp: Uint128::zero(),
                     nwv_vp: Uint128::zero(),
+                    option_vp: vec![],
                     yes_vp: Uint128::new(198 + 7),
                     current_vote: cosmwasm_std::VoteOption::Yes,
                     end_time_s: EPOCH_START + WEEK * 3,
                     period: get_period(EPOCH_START + WEEK * 3).unwrap(),
-                    total_vp: Uint128::new(204)
+                    total_vp: Uint128::new(204),
+                    current_option: None,
+                    finalized: false
                 },
             )]
         }
@@ -399,7 +429,8 @@ fn remove_user() {
         mock_info("user", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+            vote: Some(cosmwasm_std::VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -410,7 +441,8 @@ fn remove_user() {
         mock_info("user2", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::No,
+            vote: Some(cosmwasm_std::VoteOption::No),
+            option: None,
         },
     )
     .unwrap();
@@ -422,7 +454,8 @@ fn remove_user() {
         mock_info("user2", &[]),
         ExecuteMsg::Vote {
             proposal_id: 2,
-            vote: cosmwasm_std::VoteOption::Yes,
+            vote: Some(cosmwasm_std::VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -440,6 +473,7 @@ fn remove_user() {
         PropDetailResponse {
             user: Some(PropUserInfo {
                 current_vote: cosmwasm_std::VoteOption::No,
+                current_option: None,
                 vp: Uint128::new(198),
                 user: Addr::unchecked("user2")
             }),
@@ -447,12 +481,16 @@ fn remove_user() {
                 abstain_vp: Uint128::zero(),
                 no_vp: Uint128::new(198),
                 nwv_vp: Uint128::zero(),
+                option_vp: vec![],
                 yes_vp: Uint128::new(7),
                 current_vote: cosmwasm_std::VoteOption::No,
                 end_time_s: EPOCH_START + WEEK * 3,
                 period: get_period(EPOCH_START + WEEK * 3).unwrap(),
-                total_vp: Uint128::new(204)
-            }
+                total_vp: Uint128::new(204),
+                current_option: None,
+                finalized: false
+            },
+            thresholds: None
         }
     );
 
@@ -494,12 +532,16 @@ fn remove_user() {
                 abstain_vp: Uint128::zero(),
                 no_vp: Uint128::new(198),
                 nwv_vp: Uint128::zero(),
+                option_vp: vec![],
                 yes_vp: Uint128::zero(),
                 current_vote: cosmwasm_std::VoteOption::No,
                 end_time_s: EPOCH_START + WEEK * 3,
                 period: get_period(EPOCH_START + WEEK * 3).unwrap(),
-                total_vp: Uint128::new(204)
-            }
+                total_vp: Uint128::new(204),
+                current_option: None,
+                finalized: false
+            },
+            thresholds: None
         }
     );
 
@@ -554,12 +596,16 @@ fn remove_user() {
                 abstain_vp: Uint128::zero(),
                 no_vp: Uint128::zero(),
                 nwv_vp: Uint128::zero(),
+                option_vp: vec![],
                 yes_vp: Uint128::zero(),
                 current_vote: cosmwasm_std::VoteOption::Abstain,
                 end_time_s: EPOCH_START + WEEK * 3,
                 period: get_period(EPOCH_START + WEEK * 3).unwrap(),
-                total_vp: Uint128::new(204)
-            }
+                total_vp: Uint128::new(204),
+                current_option: None,
+                finalized: false
+            },
+            thresholds: None
         }
     );
 }
@@ -700,7 +746,8 @@ fn test_query_user_votes() {
         mock_info(user.as_str(), &[]),
         ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: VoteOption::Yes,
+            vote: Some(VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -710,7 +757,8 @@ fn test_query_user_votes() {
         mock_info(user.as_str(), &[]),
         ExecuteMsg::Vote {
             proposal_id: 2,
-            vote: VoteOption::Yes,
+            vote: Some(VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -721,7 +769,8 @@ fn test_query_user_votes() {
         mock_info(user.as_str(), &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: VoteOption::No,
+            vote: Some(VoteOption::No),
+            option: None,
         },
     )
     .unwrap();
@@ -742,16 +791,19 @@ fn test_query_user_votes() {
                 UserPropResponseItem {
                     id: 3,
                     current_vote: VoteOption::No,
+                    current_option: None,
                     vp: Uint128::new(3)
                 },
                 UserPropResponseItem {
                     id: 2,
                     current_vote: VoteOption::Yes,
+                    current_option: None,
                     vp: Uint128::new(4)
                 },
                 UserPropResponseItem {
                     id: 1,
                     current_vote: VoteOption::Yes,
+                    current_option: None,
                     vp: Uint128::new(5)
                 },
             ]
@@ -772,6 +824,7 @@ fn test_query_user_votes() {
             props: vec![UserPropResponseItem {
                 id: 2,
                 current_vote: VoteOption::Yes,
+                current_option: None,
                 vp: Uint128::new(4)
             },]
         }
@@ -791,6 +844,7 @@ fn test_query_user_votes() {
             props: vec![UserPropResponseItem {
                 id: 1,
                 current_vote: VoteOption::Yes,
+                current_option: None,
                 vp: Uint128::new(5)
             },]
         }
@@ -873,6 +927,632 @@ fn test_query_finished_props() {
     assert_eq!(res.props.into_iter().map(|p| p.0).collect_vec(), vec![7, 6, 5]);
 }
 
+#[test]
+fn vote_prop_multiple_choice() {
+    let mut deps = setup_test();
+
+    deps.querier.set_prop_choices(3, EPOCH_START + WEEK * 3, 3);
+    deps.querier.set_lock("user", 5, 5);
+    deps.querier.set_lock("user2", 100, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitProp {
+            proposal_id: 3,
+        },
+    )
+    .unwrap();
+
+    // no default vote for multiple choice proposals
+    assert_eq!(res.messages.len(), 0);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: None,
+            option: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Either a vote or an option needs to be provided");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::Yes),
+            option: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: multiple choice proposal requires an option");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: None,
+            option: Some(3),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: invalid option 3");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: None,
+            option: Some(2),
+        },
+    )
+    .unwrap();
+
+    // user does not reach quorum
+    assert_eq!(res.messages.len(), 0);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK),
+        mock_info("user2", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: None,
+            option: Some(1),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::VoteMultiple {
+                proposal_id: 3,
+                option_id: 1
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: Some("user2".to_string()),
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(res.prop.option_vp, vec![Uint128::zero(), Uint128::new(198), Uint128::new(7)]);
+    assert_eq!(res.prop.current_option, Some(1));
+    assert_eq!(res.user.unwrap().current_option, Some(1));
+
+    // changing the vote to the same option does not vote again
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: None,
+            option: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(res.prop.option_vp, vec![Uint128::zero(), Uint128::new(205), Uint128::zero()]);
+}
+
+#[test]
+fn finalize_prop() {
+    let mut deps = setup_test();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            quorum_bps: None,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: Some(WEEK),
+            remove_default_vote: None,
+            remove_veto_threshold: None,
+        },
+    )
+    .unwrap();
+
+    let mut deps = setup_props(deps);
+
+    deps.querier.set_lock("user", 5, 5);
+    deps.querier.set_lock("user2", 100, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK),
+        mock_info("user2", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::No),
+            option: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK),
+        mock_info("anyone", &[]),
+        ExecuteMsg::FinalizeProp {
+            proposal_id: 3,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Proposal can only be finalized during its lock-in window");
+
+    let before: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+
+    // votes during the lock-in window are rejected
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 2),
+        mock_info("user2", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::Yes),
+            option: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Votes can't be changed during the lock-in window of a proposal");
+
+    // lock changes don't change the tally either
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 2),
+        mock_info("escrow", &[]),
+        ExecuteMsg::UpdateVote {
+            user: "user2".to_string(),
+            lock_info: LockInfoResponse {
+                amount: Uint128::zero(),
+                coefficient: Decimal::zero(),
+                start: 0,
+                end: 10,
+                slope: Uint128::new(1),
+                fixed_amount: Uint128::new(10000),
+                voting_power: Uint128::new(10),
+            },
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(res.prop, before.prop);
+
+    // the frozen tally still votes no, so the forwarded vote is kept
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 2),
+        mock_info("anyone", &[]),
+        ExecuteMsg::FinalizeProp {
+            proposal_id: 3,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+    assert!(res.prop.finalized);
+    assert_eq!(res.prop.current_vote, VoteOption::No);
+    assert_eq!(res.prop.no_vp, before.prop.no_vp);
+
+    // the vote of the hub can't be changed anymore
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 2),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::NoWithVeto),
+            option: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Votes can't be changed during the lock-in window of a proposal");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 2),
+        mock_info("anyone", &[]),
+        ExecuteMsg::FinalizeProp {
+            proposal_id: 3,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Proposal has already been finalized");
+}
+
+#[test]
+fn prop_thresholds() {
+    let deps = setup_test();
+    let mut deps = setup_props(deps);
+
+    deps.querier.set_lock("user", 5, 5);
+    deps.querier.set_lock("user2", 100, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::SetPropThresholds {
+            proposal_id: 3,
+            quorum_bps: Some(300),
+            veto_threshold_bps: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: unauthorized");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetPropThresholds {
+            proposal_id: 3,
+            quorum_bps: Some(300),
+            veto_threshold_bps: None,
+        },
+    )
+    .unwrap();
+
+    // user reaches the lowered quorum
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::NoWithVeto),
+            option: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK),
+        mock_info("user2", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::Yes),
+            option: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::Vote {
+                proposal_id: 3,
+                vote: cosmwasm_std::VoteOption::Yes
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetPropThresholds {
+            proposal_id: 3,
+            quorum_bps: Some(300),
+            veto_threshold_bps: Some(300),
+        },
+    )
+    .unwrap();
+
+    // 7 of 205 voted with veto
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: Some(VoteOption::NoWithVeto),
+            option: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::Vote {
+                proposal_id: 3,
+                vote: cosmwasm_std::VoteOption::NoWithVeto
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(
+        res.thresholds,
+        Some(PropThresholds {
+            quorum_bps: Some(300),
+            veto_threshold_bps: Some(300),
+        })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetPropThresholds {
+            proposal_id: 3,
+            quorum_bps: None,
+            veto_threshold_bps: None,
+        },
+    )
+    .unwrap();
+
+    let res: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+    );
+    assert_eq!(res.thresholds, None);
+}
+
+#[test]
+fn sync_props() {
+    let mut deps = setup_test();
+
+    deps.querier.set_prop_expiry(1, EPOCH_START - WEEK);
+    deps.querier.set_prop_expiry(2, EPOCH_START + WEEK);
+    deps.querier.set_prop_expiry(3, EPOCH_START + WEEK * 2);
+    deps.querier.set_prop_expiry(4, EPOCH_START + WEEK * 3);
+    deps.querier.set_prop_expiry(5, EPOCH_START + WEEK * 3);
+    deps.querier.set_prop_status(5, cw3::Status::Pending);
+
+    // prop 3 is already known
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::InitProp {
+            proposal_id: 3,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SyncProps {
+            limit: Some(3),
+        },
+    )
+    .unwrap();
+
+    // no default vote configured
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(res.attributes[1].value, "2".to_string());
+
+    let res: PropsResponse = query_helper_env(
+        deps.as_ref(),
+        QueryMsg::ActiveProps {
+            limit: None,
+            start_after: None,
+        },
+        EPOCH_START,
+    );
+    assert_eq!(res.props.iter().map(|p| p.0).collect_vec(), vec![2, 3]);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            quorum_bps: None,
+            default_vote: Some(VoteOption::No),
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            remove_default_vote: None,
+            remove_veto_threshold: None,
+        },
+    )
+    .unwrap();
+
+    // continues after the last synced prop
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SyncProps {
+            limit: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::Vote {
+                proposal_id: 4,
+                vote: cosmwasm_std::VoteOption::No
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    let prop: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 4,
+        },
+    );
+    assert_eq!(prop.prop.current_vote, VoteOption::No);
+
+    // the pending prop is synced once it is open
+    deps.querier.set_prop_status(5, cw3::Status::Open);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SyncProps {
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.attributes[1].value, "5".to_string());
+
+    // nothing new to sync
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SyncProps {
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+}
+
+#[test]
+fn sync_props_multiple_choice() {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env_at_timestamp(10000),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            escrow_addr: "escrow".to_string(),
+            hub_addr: "hub".to_string(),
+            quorum_bps: 500,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            gov_multiple: Some(true),
+        },
+    )
+    .unwrap();
+
+    // the ids of the single choice module overlap with the ones of the multiple choice module
+    deps.querier.set_prop_expiry(1, EPOCH_START + WEEK);
+    deps.querier.set_multiple_prop(1, EPOCH_START + WEEK * 2, 3);
+    deps.querier.set_multiple_prop(2, EPOCH_START + WEEK * 3, 2);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SyncProps {
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(res.attributes[1].value, "1,2".to_string());
+
+    let prop: PropDetailResponse = query_helper(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 1,
+        },
+    );
+    assert_eq!(prop.prop.end_time_s, EPOCH_START + WEEK * 2);
+    assert_eq!(prop.prop.option_vp, vec![Uint128::zero(); 3]);
+
+    // votes are forwarded for the option of the multiple choice module
+    deps.querier.set_lock("user", 100, 100);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: None,
+            option: Some(2),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::VoteMultiple {
+                proposal_id: 1,
+                option_id: 2,
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+}
+
 fn addr(str: &str) -> Addr {
     Addr::unchecked(str)
 }
@@ -891,7 +1571,8 @@ fn update_vote() {
         mock_info("user", &[]),
         ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+            vote: Some(cosmwasm_std::VoteOption::Yes),
+            option: None,
         },
     )
     .unwrap();
@@ -1017,6 +1698,11 @@ fn update_config() {
         mock_info("jake", &[]),
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(1000),
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            remove_default_vote: None,
+            remove_veto_threshold: None,
         },
     )
     .unwrap_err();
@@ -1028,6 +1714,11 @@ fn update_config() {
         mock_info("owner", &[]),
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(12000),
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            remove_default_vote: None,
+            remove_veto_threshold: None,
         },
     )
     .unwrap_err();
@@ -1039,6 +1730,11 @@ fn update_config() {
         mock_info("owner", &[]),
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(5000),
+            default_vote: Some(VoteOption::No),
+            veto_threshold_bps: Some(3340),
+            lock_in_s: Some(3600),
+            remove_default_vote: None,
+            remove_veto_threshold: None,
         },
     )
     .unwrap();
@@ -1052,8 +1748,32 @@ fn update_config() {
             escrow_addr: Addr::unchecked("escrow"),
             hub_addr: Addr::unchecked("hub"),
             quorum_bps: 5000,
+            default_vote: Some(VoteOption::No),
+            veto_threshold_bps: Some(3340),
+            lock_in_s: 3600,
+            gov_multiple: false,
         }
     );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            quorum_bps: None,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            remove_default_vote: Some(true),
+            remove_veto_threshold: Some(true),
+        },
+    )
+    .unwrap();
+
+    let res: ConfigResponse = query_helper(deps.as_ref(), QueryMsg::Config {});
+    assert_eq!(res.default_vote, None);
+    assert_eq!(res.veto_threshold_bps, None);
+    assert_eq!(res.lock_in_s, 3600);
 }
 
 fn setup_props(
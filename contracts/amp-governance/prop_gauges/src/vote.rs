@@ -18,6 +18,15 @@ pub fn remove_vote_of_user(mut prop: PropInfo, user: &PropUserInfo) -> StdResult
         return Ok(prop);
     }
 
+    if let Some(option) = user.current_option {
+        let option_vp = prop
+            .option_vp
+            .get_mut(option as usize)
+            .ok_or_else(|| StdError::generic_err(format!("invalid option {0}", option)))?;
+        *option_vp = option_vp.checked_sub(vp)?;
+        return Ok(prop);
+    }
+
     match user.current_vote {
         cosmwasm_std::VoteOption::Yes => prop.yes_vp = prop.yes_vp.checked_sub(vp)?,
         cosmwasm_std::VoteOption::No => prop.no_vp = prop.no_vp.checked_sub(vp)?,
@@ -33,6 +42,7 @@ pub fn apply_vote_of_user(
     mut prop: PropInfo,
     ve_lock_info: &LockInfoResponse,
     vote: VoteOption,
+    option: Option<u32>,
     user: Addr,
 ) -> StdResult<(PropInfo, PropUserInfo)> {
     let current_period = get_period(env.block.time.seconds())?;
@@ -43,12 +53,35 @@ pub fn apply_vote_of_user(
             prop,
             PropUserInfo {
                 current_vote: VoteOption::Abstain,
+                current_option: None,
                 vp,
                 user,
             },
         ));
     }
 
+    if let Some(option) = option {
+        let option_vp = prop
+            .option_vp
+            .get_mut(option as usize)
+            .ok_or_else(|| StdError::generic_err(format!("invalid option {0}", option)))?;
+        *option_vp = option_vp.checked_add(vp)?;
+
+        return Ok((
+            prop,
+            PropUserInfo {
+                current_vote: VoteOption::Abstain,
+                current_option: Some(option),
+                vp,
+                user,
+            },
+        ));
+    }
+
+    if prop.is_multiple_choice() {
+        return Err(StdError::generic_err("multiple choice proposal requires an option"));
+    }
+
     match vote {
         cosmwasm_std::VoteOption::Yes => prop.yes_vp = prop.yes_vp.checked_add(vp)?,
         cosmwasm_std::VoteOption::No => prop.no_vp = prop.no_vp.checked_add(vp)?,
@@ -60,6 +93,7 @@ pub fn apply_vote_of_user(
         prop,
         PropUserInfo {
             current_vote: vote,
+            current_option: None,
             vp,
             user,
         },
@@ -77,6 +111,7 @@ pub(crate) fn update_vote_state(
     proposal_id: u64,
     prop: Option<PropInfo>,
     vote: VoteOption,
+    option: Option<u32>,
     user_info: PropUserInfo,
     ve_lock_info: &LockInfoResponse,
 ) -> Result<(PropUserInfo, Option<CosmosMsg<CustomMsgType>>), ContractError> {
@@ -89,9 +124,11 @@ pub(crate) fn update_vote_state(
     };
 
     let prop = remove_vote_of_user(prop, &user_info)?;
-    let (mut prop, user) = apply_vote_of_user(env, prop, ve_lock_info, vote, sender.clone())?;
+    let (mut prop, user) =
+        apply_vote_of_user(env, prop, ve_lock_info, vote, option, sender.clone())?;
 
-    let (vote_msg, total_vp) = get_vote_msg(querier, config, &mut prop, proposal_id)?;
+    let (vote_msg, total_vp) =
+        get_vote_msg(env, querier, store, state, config, &mut prop, proposal_id)?;
     prop.total_vp = total_vp;
 
     state.props.save(store, proposal_id, &prop)?;
@@ -101,8 +138,11 @@ pub(crate) fn update_vote_state(
     Ok((user, vote_msg))
 }
 
-pub fn get_vote_msg(
+pub(crate) fn get_vote_msg(
+    env: &Env,
     querier: &QuerierWrapper,
+    store: &mut dyn Storage,
+    state: &State,
     config: &ConfigResponse,
     prop: &mut PropInfo,
     proposal_id: u64,
@@ -110,16 +150,61 @@ pub fn get_vote_msg(
     let total_vp =
         get_total_voting_power_at_by_period(querier, config.escrow_addr.clone(), prop.period)?;
 
+    // during the lock-in window the vote is only cast by FinalizeProp.
+    if is_frozen(env, config, prop) {
+        return Ok((None, total_vp));
+    }
+
+    let vote_msg = get_wanted_vote_msg(store, state, config, prop, proposal_id, total_vp)?;
+    Ok((vote_msg, total_vp))
+}
+
+pub(crate) fn is_locked_in(env: &Env, config: &ConfigResponse, prop: &PropInfo) -> bool {
+    config.lock_in_s > 0 && env.block.time.seconds() + config.lock_in_s >= prop.end_time_s
+}
+
+/// Whether the tally of the proposal can no longer be changed by votes.
+pub(crate) fn is_frozen(env: &Env, config: &ConfigResponse, prop: &PropInfo) -> bool {
+    prop.finalized || is_locked_in(env, config, prop)
+}
+
+/// Returns the vote message for the hub, if the wanted vote differs from the current one.
+pub(crate) fn get_wanted_vote_msg(
+    store: &mut dyn Storage,
+    state: &State,
+    config: &ConfigResponse,
+    prop: &mut PropInfo,
+    proposal_id: u64,
+    total_vp: Uint128,
+) -> Result<Option<CosmosMsg<CustomMsgType>>, ContractError> {
+    let thresholds = state.prop_thresholds.may_load(store, proposal_id)?;
+    let quorum_bps = thresholds.as_ref().and_then(|t| t.quorum_bps).unwrap_or(config.quorum_bps);
+    let veto_threshold_bps =
+        thresholds.and_then(|t| t.veto_threshold_bps).or(config.veto_threshold_bps);
+
+    if prop.is_multiple_choice() {
+        // the hub keeps its vote if the quorum is lost again, as it can't abstain.
+        let wanted = prop.get_wanted_option(total_vp, quorum_bps)?;
+        let vote_msg = match wanted {
+            Some(option) if wanted != prop.current_option => {
+                prop.current_option = wanted;
+                Some(Hub(config.hub_addr.clone()).vote_multiple_msg(proposal_id, option)?)
+            },
+            _ => None,
+        };
+        return Ok(vote_msg);
+    }
+
     // if normal vote, check if the current vote is already set.
     let current_vote = prop.current_vote.clone();
-    let wanted = prop.get_wanted_vote(total_vp, config.quorum_bps)?;
+    let wanted = prop.get_wanted_vote(total_vp, quorum_bps, veto_threshold_bps)?;
     let vote_msg: Option<CosmosMsg<CustomMsgType>> = if wanted != current_vote {
         Some(Hub(config.hub_addr.clone()).vote_msg(proposal_id, wanted.clone())?)
     } else {
         None
     };
     prop.current_vote = wanted;
-    Ok((vote_msg, total_vp))
+    Ok(vote_msg)
 }
 
 fn calc_voting_power_for_prop(
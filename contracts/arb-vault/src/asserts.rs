@@ -2,7 +2,7 @@ use crate::error::ContractError;
 use crate::extensions::BalancesEx;
 
 use cosmwasm_std::{Decimal, Uint128};
-use eris::arb_vault::{BalancesDetails, ValidatedConfig};
+use eris::arb_vault::{BalancesDetails, LsdRisk, ValidatedConfig};
 
 //----------------------------------------------------------------------------------------
 //  ASSERTS
@@ -38,3 +38,22 @@ pub fn assert_has_funds(funds_amount: &Uint128) -> Result<(), ContractError> {
 
     Ok(())
 }
+
+/// Asserts that arbitrage with the funds_amount stays within the risk limits of the LSD.
+pub fn assert_risk_limits(risk: &LsdRisk, funds_amount: Uint128) -> Result<(), ContractError> {
+    if let Some(circuit_breaker) = &risk.circuit_breaker {
+        return Err(ContractError::CircuitBreakerTriggered(
+            risk.name.clone(),
+            format!("{:?}", circuit_breaker),
+        ));
+    }
+
+    if let Some(max_exposure) = risk.max_exposure {
+        // the LSD receives at least the value of the used funds
+        if risk.exposure.checked_add(funds_amount)? > max_exposure {
+            return Err(ContractError::ExposureLimitReached(risk.name.clone()));
+        }
+    }
+
+    Ok(())
+}
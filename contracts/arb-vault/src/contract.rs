@@ -3,20 +3,26 @@ use crate::domain;
 use crate::domain::callback::handle_callback;
 use crate::domain::config::execute_update_config;
 use crate::domain::execute::{
-    execute_arbitrage, execute_deposit, execute_unbond_liquidity, execute_unbond_user,
-    execute_withdraw_liquidity, execute_withdraw_unbonded, execute_withdraw_unbonding_immediate,
+    execute_arbitrage, execute_crystallize_fees, execute_deposit, execute_flash_loan,
+    execute_push_share_updates, execute_receive, execute_track_transfer, execute_unbond_liquidity,
+    execute_unbond_user, execute_withdraw_liquidity, execute_withdraw_unbonded,
+    execute_withdraw_unbonding_immediate,
 };
 use crate::domain::ownership::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+use crate::domain::swap::execute_swap;
 use crate::error::{ContractError, ContractResult, CustomResult};
 use crate::query::{
-    query_config, query_exchange_rates, query_state, query_takeable, query_unbond_requests,
-    query_user_info,
+    query_config, query_exchange_rates, query_simulation, query_state, query_takeable,
+    query_unbond_plan, query_unbond_requests, query_user_info, query_withdraw_immediate_quote,
 };
+use crate::state::{LegacyConfig, State};
 use cosmwasm_std::{entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Item;
 
+use astroport::asset::native_asset_info;
 use eris::arb_vault::InstantiateMsg;
-use eris::arb_vault::{ExecuteMsg, MigrateMsg, QueryMsg};
+use eris::arb_vault::{ExecuteMsg, MigrateMsg, QueryMsg, SudoMsg, ValidatedConfig};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -54,29 +60,26 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
             asset,
             receiver,
         } => execute_deposit(deps, env, info, asset, receiver),
+        ExecuteMsg::Receive(cw20_msg) => execute_receive(deps, env, info, cw20_msg),
         ExecuteMsg::WithdrawUnbonded {
             ..
         } => execute_withdraw_unbonded(deps, env, info),
         ExecuteMsg::WithdrawImmediate {
             id,
-        } => execute_withdraw_unbonding_immediate(deps, env, info, id),
-
-        // ExecuteMsg::Swap {
-        //     offer_asset,
-        //     ask_asset_info,
-        //     belief_price,
-        //     max_spread,
-        //     to,
-        // } => domain::swap::execute_swap_native(
-        //     deps,
-        //     env,
-        //     info,
-        //     offer_asset,
-        //     ask_asset_info,
-        //     belief_price,
-        //     max_spread,
-        //     to,
-        // ),
+            amount,
+        } => execute_withdraw_unbonding_immediate(deps, env, info, id, amount),
+
+        ExecuteMsg::FlashLoan {
+            amount,
+            msg,
+            recipient,
+        } => execute_flash_loan(deps, env, info, amount, msg, recipient),
+        ExecuteMsg::Swap {
+            offer_asset,
+            min_receive,
+            to,
+        } => execute_swap(deps, env, info, offer_asset, min_receive, to),
+
         // Allowed by Owner
         ExecuteMsg::UpdateConfig {
             ..
@@ -88,10 +91,29 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
         } => propose_new_owner(deps, info, env, owner, expires_in),
         ExecuteMsg::DropOwnershipProposal {} => drop_ownership_proposal(deps, info),
         ExecuteMsg::ClaimOwnership {} => claim_ownership(deps, info, env),
+        ExecuteMsg::CrystallizeFees {} => execute_crystallize_fees(deps, env),
+        ExecuteMsg::PushShareUpdates {
+            users,
+        } => execute_push_share_updates(deps, users),
         ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, msg),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> ContractResult {
+    match msg {
+        // transfers are never blocked, errors of the tracking hook are ignored by the chain
+        SudoMsg::BlockBeforeSend {
+            ..
+        } => Ok(Response::new()),
+        SudoMsg::TrackBeforeSend {
+            from,
+            to,
+            amount,
+        } => execute_track_transfer(deps, env, from, to, amount),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> CustomResult<Binary> {
     let res = match msg {
@@ -116,6 +138,18 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> CustomResult<Binary> {
             start_after_d,
             limit,
         } => to_json_binary(&query_exchange_rates(deps, env, start_after_d, limit)?)?,
+
+        QueryMsg::Simulation {
+            offer_asset,
+        } => to_json_binary(&query_simulation(deps, env, offer_asset)?)?,
+        QueryMsg::WithdrawImmediateQuote {
+            address,
+            id,
+            amount,
+        } => to_json_binary(&query_withdraw_immediate_quote(deps, env, address, id, amount)?)?,
+        QueryMsg::UnbondPlan {
+            names,
+        } => to_json_binary(&query_unbond_plan(deps, env, names)?)?,
     };
     Ok(res)
 }
@@ -124,6 +158,21 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> CustomResult<Binary> {
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let contract_version = get_contract_version(deps.storage)?;
 
+    let state = State::default();
+    if state.config.load(deps.storage).is_err() {
+        // utoken was stored as a native denom
+        let legacy: LegacyConfig = Item::new("config").load(deps.storage)?;
+        state.config.save(
+            deps.storage,
+            &ValidatedConfig {
+                utoken: native_asset_info(legacy.utoken),
+                utilization_method: legacy.utilization_method,
+                unbond_time_s: legacy.unbond_time_s,
+                lsds: legacy.lsds,
+            },
+        )?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
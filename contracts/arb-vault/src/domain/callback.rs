@@ -1,7 +1,7 @@
 use std::ops::Div;
 
-use astroport::asset::{native_asset, AssetInfo, AssetInfoExt};
-use cosmwasm_std::{attr, Decimal, DepsMut, Env, MessageInfo, Response};
+use astroport::asset::{AssetInfo, AssetInfoExt};
+use cosmwasm_std::{attr, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
 use eris::arb_vault::{CallbackMsg, ExchangeHistory};
 use eris::constants::DAY;
 use eris::{CustomMsgExt, CustomResponse};
@@ -25,6 +25,9 @@ pub fn handle_callback(
             result_token,
             wanted_profit,
         } => execute_assert_result(deps, env, result_token, wanted_profit),
+        CallbackMsg::AssertFlashLoanRepaid {
+            fee_amount,
+        } => execute_assert_flash_loan_repaid(deps, env, fee_amount),
     }
 }
 
@@ -43,6 +46,8 @@ pub fn execute_assert_result(
     let mut lsds = config.lsd_group(&env);
 
     let old_balance = state.assert_is_nested(deps.storage)?;
+    let old_active_balance =
+        old_balance.active_balance.clone().ok_or(ContractError::NotExecuting {})?;
     let new_balances = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
     let total_lp_supply = lp_token.total_supply;
 
@@ -64,7 +69,7 @@ pub fn execute_assert_result(
 
     let received_x_amount = active_lsd_balance
         .xbalance
-        .checked_sub(old_balance.active_balance.xbalance)
+        .checked_sub(old_active_balance.xbalance)
         .map_err(|e| ContractError::CalculationError("profit_by_asset".into(), e.to_string()))?;
     let profit_by_xasset = received_x_amount * active_lsd_balance.xfactor - used_balance;
 
@@ -75,7 +80,7 @@ pub fn execute_assert_result(
         return Err(ContractError::ProfitBalancesDoesNotMatch {
             profit,
             profit_by_xasset,
-            old_balance: old_balance.active_balance.xbalance,
+            old_balance: old_active_balance.xbalance,
         });
     }
 
@@ -95,14 +100,14 @@ pub fn execute_assert_result(
 
     // calculate fee
     let fee_config = state.fee_config.load(deps.storage)?;
-    let fee_percent = fee_config.protocol_performance_fee;
+    let fee_percent = fee_config.trade_performance_fee();
     let fee_amount = profit * fee_percent;
 
     let (fee_msg, fee_attributes) = if fee_amount.is_zero() {
         (None, vec![])
     } else if new_balances.vault_takeable >= fee_amount {
         // send fees in utoken if takeable allows it.
-        let utoken = native_asset(config.utoken, fee_amount);
+        let utoken = config.utoken.with_balance(fee_amount);
         let fee_msg = utoken.into_msg(fee_config.protocol_fee_contract)?.to_specific()?;
 
         (Some(fee_msg), vec![])
@@ -157,3 +162,67 @@ pub fn execute_assert_result(
         ])
         .add_attributes(fee_attributes))
 }
+
+pub fn execute_assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: Env,
+    fee_amount: Uint128,
+) -> ContractResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let lp_token = state.lp_token.load(deps.storage)?;
+    let mut lsds = config.lsd_group(&env);
+
+    let old_balance = state.assert_is_nested(deps.storage)?;
+    if old_balance.active_balance.is_some() {
+        // checkpoint was created by an arbitrage execution
+        return Err(ContractError::NotExecuting {});
+    }
+
+    let new_balances = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
+
+    let expected = old_balance.vault_available.checked_add(fee_amount)?;
+    if new_balances.vault_available < expected || new_balances.tvl_utoken < old_balance.tvl_utoken {
+        return Err(ContractError::FlashLoanNotRepaid {
+            expected,
+            available: new_balances.vault_available,
+        });
+    }
+
+    // the protocol receives the performance fee from the flash loan fee
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let protocol_fee = fee_amount * fee_config.trade_performance_fee();
+    let fee_msg = if protocol_fee.is_zero() {
+        None
+    } else {
+        Some(
+            config
+                .utoken
+                .with_balance(protocol_fee)
+                .into_msg(fee_config.protocol_fee_contract)?
+                .to_specific()?,
+        )
+    };
+
+    state.balance_checkpoint.remove(deps.storage);
+
+    let new_vault_total = new_balances.vault_total - protocol_fee;
+    let exchange_rate = Decimal::from_ratio(new_vault_total, lp_token.total_supply);
+    state.exchange_history.save(
+        deps.storage,
+        env.block.time.seconds().div(DAY),
+        &ExchangeHistory {
+            exchange_rate,
+            time_s: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new().add_optional_message(fee_msg).add_attributes(vec![
+        attr("action", "arb/assert_flash_loan_repaid"),
+        attr("old_available", old_balance.vault_available),
+        attr("new_available", new_balances.vault_available),
+        attr("fee_amount", fee_amount),
+        attr("protocol_fee", protocol_fee),
+        attr("exchange_rate", exchange_rate.to_string()),
+    ]))
+}
@@ -1,5 +1,7 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdError};
-use eris::arb_vault::ExecuteMsg;
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use eris::arb_vault::{ExecuteMsg, PerformanceFeeMode};
+use eris_chain_adapter::types::chain;
+use eris_chain_shared::chain_trait::ChainInterface;
 use itertools::Itertools;
 
 use crate::{
@@ -26,11 +28,16 @@ pub fn execute_update_config(
             fee_config,
             remove_whitelist,
             set_whitelist,
+            set_unbond_schedule,
+            set_risk_limits,
+            reset_xfactor_high,
+            set_push_update_contracts,
         } => {
             let state = State::default();
             state.assert_owner(deps.storage, &info.sender)?;
 
             let mut config = state.config.load(deps.storage)?;
+            let mut response = Response::new();
 
             let mut config_changed = false;
             if let Some(unbond_time_s) = unbond_time_s {
@@ -80,6 +87,7 @@ pub fn execute_update_config(
 
                 config.lsds =
                     config.lsds.into_iter().filter(|lsd| lsd.name != remove_lsd).collect_vec();
+                state.xfactor_high.remove(deps.storage, remove_lsd);
 
                 config_changed = true;
             } else if let Some(force_remove_lsd) = force_remove_lsd {
@@ -88,10 +96,43 @@ pub fn execute_update_config(
                     .into_iter()
                     .filter(|lsd| lsd.name != force_remove_lsd)
                     .collect_vec();
+                state.xfactor_high.remove(deps.storage, force_remove_lsd);
 
                 config_changed = true;
             }
 
+            if let Some((name, risk_limits)) = set_risk_limits {
+                if let Some(risk_limits) = &risk_limits {
+                    risk_limits.validate()?;
+                }
+
+                let lsd = config.lsds.iter_mut().find(|a| a.name == name);
+                if let Some(lsd) = lsd {
+                    lsd.risk_limits = risk_limits;
+                    config_changed = true;
+                } else {
+                    Err(ContractError::AdapterNotFound(name))?
+                }
+            }
+
+            if let Some(name) = reset_xfactor_high {
+                if !config.lsds.iter().any(|a| a.name == name) {
+                    Err(ContractError::AdapterNotFound(name.clone()))?
+                }
+                // the next risk check starts again from the current exchange rate
+                state.xfactor_high.remove(deps.storage, name);
+            }
+
+            if let Some((name, unbond_schedule)) = set_unbond_schedule {
+                let lsd = config.lsds.iter_mut().find(|a| a.name == name);
+                if let Some(lsd) = lsd {
+                    lsd.unbond_schedule = unbond_schedule;
+                    config_changed = true;
+                } else {
+                    Err(ContractError::AdapterNotFound(name))?
+                }
+            }
+
             if config_changed {
                 // after the config change, it still needs to be able to query all assets.
                 let mut lsds = config.lsd_group(&env);
@@ -101,7 +142,26 @@ pub fn execute_update_config(
             }
 
             if let Some(fee_config) = fee_config {
-                state.fee_config.save(deps.storage, &fee_config.validate(deps.api)?)?;
+                let fee_config = fee_config.validate(deps.api)?;
+                let old_fee_config = state.fee_config.load(deps.storage)?;
+
+                // growth before the switch was already charged per trade
+                if fee_config.performance_fee_mode == PerformanceFeeMode::Crystallized
+                    && old_fee_config.performance_fee_mode != PerformanceFeeMode::Crystallized
+                {
+                    let lp_token = state.lp_token.load(deps.storage)?;
+                    let high_water_mark = if lp_token.total_supply.is_zero() {
+                        Decimal::one()
+                    } else {
+                        let mut lsds = config.lsd_group(&env);
+                        let balances =
+                            lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
+                        Decimal::from_ratio(balances.vault_total, lp_token.total_supply)
+                    };
+                    state.high_water_mark.save(deps.storage, &high_water_mark)?;
+                }
+
+                state.fee_config.save(deps.storage, &fee_config)?;
             }
 
             if let Some(set_whitelist) = set_whitelist {
@@ -112,13 +172,35 @@ pub fn execute_update_config(
                 }
             }
 
+            if let Some(set_push_update_contracts) = set_push_update_contracts {
+                let push_update_contracts = set_push_update_contracts
+                    .into_iter()
+                    .map(|a| deps.api.addr_validate(&a))
+                    .collect::<StdResult<Vec<Addr>>>()?;
+
+                // transfers are only seen by the vault as before-send hook of the LP denom
+                let pushing = state
+                    .push_update_contracts
+                    .may_load(deps.storage)?
+                    .map_or(false, |contracts| !contracts.is_empty());
+                if !pushing && !push_update_contracts.is_empty() {
+                    let lp_token = state.lp_token.load(deps.storage)?;
+                    response = response.add_message(chain(&env).create_set_before_send_hook_msg(
+                        lp_token.denom,
+                        env.contract.address.to_string(),
+                    )?);
+                }
+
+                state.push_update_contracts.save(deps.storage, &push_update_contracts)?;
+            }
+
             if let Some(remove_whitelist) = remove_whitelist {
                 if remove_whitelist {
                     state.whitelisted_addrs.remove(deps.storage);
                 }
             }
 
-            Ok(Response::new().add_attribute("action", "update_config"))
+            Ok(response.add_attribute("action", "update_config"))
         },
         _ => Err(StdError::generic_err("not supported").into()),
     }
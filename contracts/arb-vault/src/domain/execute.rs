@@ -1,18 +1,24 @@
-use crate::asserts::{assert_has_funds, assert_max_amount, assert_min_profit};
+use crate::asserts::{assert_has_funds, assert_max_amount, assert_min_profit, assert_risk_limits};
 use crate::error::{ContractError, ContractResult};
 use crate::extensions::{BalancesEx, ConfigEx};
 use crate::helpers::{calc_fees, get_share_from_deposit};
 use crate::state::{BalanceCheckpoint, BalanceLocked, State, UnbondHistory};
-use astroport::asset::{native_asset, native_asset_info, Asset, AssetInfo};
+use astroport::asset::{token_asset, Asset, AssetInfo, AssetInfoExt};
 use cosmwasm_std::{
-    attr, coin, Addr, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Order, QuerierWrapper,
-    Response, StdResult, Storage, Uint128, WasmMsg,
+    attr, coin, from_json, to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal, DepsMut, Env,
+    MessageInfo, Order, QuerierWrapper, Response, StdResult, Storage, Uint128, WasmMsg,
 };
-use eris::arb_vault::{CallbackMsg, ExecuteSubMsg, LpToken, ValidatedConfig};
+use cw20::Cw20ReceiveMsg;
+use eris::arb_vault::{
+    CallbackMsg, ExchangeHistory, ExecuteSubMsg, LpToken, PerformanceFeeMode, PushExecuteMsg,
+    ReceiveMsg, ValidatedConfig,
+};
+use eris::constants::DAY;
 use eris::{CustomMsgExt, CustomResponse};
 use eris_chain_adapter::types::{chain, CustomMsgType};
 use eris_chain_shared::chain_trait::ChainInterface;
 use itertools::Itertools;
+use std::ops::Div;
 use std::vec;
 
 //----------------------------------------------------------------------------------------
@@ -37,6 +43,11 @@ pub fn execute_arbitrage(
     state.assert_sender_whitelisted(deps.storage, &info.sender)?;
     state.assert_not_nested(deps.storage)?;
     assert_has_funds(&message.funds_amount)?;
+
+    let risk = lsd.get_risk(&deps.as_ref(), &env, &state, &balances)?;
+    assert_risk_limits(&risk, message.funds_amount)?;
+    state.xfactor_high.save(deps.storage, lsd.name.clone(), &risk.xfactor_high)?;
+
     assert_min_profit(&wanted_profit)?;
     assert_max_amount(&config, &balances, &wanted_profit, &message.funds_amount)?;
 
@@ -55,18 +66,12 @@ pub fn execute_arbitrage(
         &BalanceCheckpoint {
             vault_available: balances.vault_available,
             tvl_utoken: balances.tvl_utoken,
-            active_balance,
+            active_balance: Some(active_balance),
         },
     )?;
 
-    let execute_flashloan: CosmosMsg<CustomMsgType> = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: contract_addr.to_string(),
-        msg: message.msg.clone(),
-        funds: vec![Coin {
-            denom: config.utoken,
-            amount: message.funds_amount,
-        }],
-    });
+    let execute_flashloan =
+        config.utoken_execute_msg(&contract_addr, message.msg.clone(), message.funds_amount)?;
 
     let validate_flashloan_result = CallbackMsg::AssertResult {
         result_token,
@@ -80,6 +85,65 @@ pub fn execute_arbitrage(
         .add_attribute("action", "arb/execute_arbitrage"))
 }
 
+pub fn execute_flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    msg: Binary,
+    recipient: Option<String>,
+) -> ContractResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let mut lsds = config.lsd_group(&env);
+
+    state.assert_not_nested(deps.storage)?;
+    assert_has_funds(&amount)?;
+
+    // by default the sender is called with the funds requested
+    let recipient = if let Some(recipient) = recipient {
+        deps.api.addr_validate(&recipient)?
+    } else {
+        info.sender.clone()
+    };
+    lsds.assert_not_lsd_contract(&recipient)?;
+
+    let balances = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
+    if amount > balances.vault_takeable {
+        return Err(ContractError::NotEnoughFundsTakeable {});
+    }
+
+    // the vault needs to receive back the amount + fee in the same transaction
+    let fee_amount = amount * fee_config.flash_loan_fee;
+    state.balance_checkpoint.save(
+        deps.storage,
+        &BalanceCheckpoint {
+            vault_available: balances.vault_available,
+            tvl_utoken: balances.tvl_utoken,
+            active_balance: None,
+        },
+    )?;
+
+    let execute_flash_loan = config.utoken_execute_msg(&recipient, msg, amount)?;
+
+    let validate_flash_loan_repaid = CallbackMsg::AssertFlashLoanRepaid {
+        fee_amount,
+    }
+    .into_cosmos_msg(&env.contract.address)?;
+
+    Ok(Response::new()
+        .add_message(execute_flash_loan)
+        .add_message(validate_flash_loan_repaid)
+        .add_attributes(vec![
+            attr("action", "arb/execute_flash_loan"),
+            attr("sender", info.sender.to_string()),
+            attr("recipient", recipient.to_string()),
+            attr("amount", amount),
+            attr("fee_amount", fee_amount),
+        ]))
+}
+
 pub fn execute_withdraw_liquidity(
     deps: DepsMut,
     env: Env,
@@ -115,7 +179,8 @@ pub fn execute_unbond_liquidity(
     state.assert_not_nested(deps.storage)?;
     state.assert_sender_whitelisted(deps.storage, &info.sender)?;
 
-    let (messages, attributes) = lsds.get_unbond_msgs(&deps)?;
+    let plan = lsds.get_unbond_plan(&deps.as_ref(), &env)?;
+    let (messages, attributes) = lsds.get_unbond_msgs(&deps, &plan)?;
 
     if messages.is_empty() {
         return Err(ContractError::NothingToUnbond {});
@@ -130,6 +195,42 @@ pub fn execute_deposit(
     info: MessageInfo,
     deposit: Asset,
     recipient: Option<String>,
+) -> ContractResult {
+    deposit.info.check(deps.api)?;
+    if !deposit.is_native_token() {
+        return Err(ContractError::Cw20DirectSwap {});
+    }
+    deposit.assert_sent_native_token_balance(&info)?;
+
+    deposit_utoken(deps, env, info.sender, deposit, recipient)
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> ContractResult {
+    let api = deps.api;
+    match from_json(&cw20_msg.msg)? {
+        ReceiveMsg::Deposit {
+            receiver,
+        } => deposit_utoken(
+            deps,
+            env,
+            api.addr_validate(&cw20_msg.sender)?,
+            token_asset(info.sender, cw20_msg.amount),
+            receiver,
+        ),
+    }
+}
+
+fn deposit_utoken(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    deposit: Asset,
+    recipient: Option<String>,
 ) -> ContractResult {
     let state = State::default();
     let config = state.config.load(deps.storage)?;
@@ -137,14 +238,12 @@ pub fn execute_deposit(
     let mut lsds = config.lsd_group(&env);
 
     state.assert_not_nested(deps.storage)?;
-    deposit.info.check(deps.api)?;
-    deposit.assert_sent_native_token_balance(&info)?;
 
     if deposit.amount.is_zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
 
-    if deposit.info != native_asset_info(config.utoken.clone()) {
+    if deposit.info != config.utoken {
         return Err(ContractError::AssetMismatch {});
     }
 
@@ -160,26 +259,22 @@ pub fn execute_deposit(
     let recipient = if let Some(recipient) = recipient {
         deps.api.addr_validate(&recipient)?
     } else {
-        info.sender.clone()
+        sender.clone()
     };
 
-    Ok(Response::new()
-        .add_messages(create_mint_msgs(
-            &env,
-            deps.storage,
-            &state,
-            &mut lp_token,
-            recipient.clone(),
-            share,
-        )?)
-        .add_attributes(vec![
-            attr("action", "arb/execute_deposit"),
-            attr("sender", info.sender.to_string()),
-            attr("recipient", recipient.to_string()),
-            attr("deposit_amount", deposit_amount),
-            attr("share", share.to_string()),
-            attr("vault_utoken_new", assets.vault_total),
-        ]))
+    let mint_msgs =
+        create_mint_msgs(&env, deps.storage, &state, &mut lp_token, recipient.clone(), share)?;
+    let push_update_msgs =
+        create_push_update_msgs(&deps.querier, deps.storage, &state, &lp_token, &recipient, share)?;
+
+    Ok(Response::new().add_messages(mint_msgs).add_messages(push_update_msgs).add_attributes(vec![
+        attr("action", "arb/execute_deposit"),
+        attr("sender", sender.to_string()),
+        attr("recipient", recipient.to_string()),
+        attr("deposit_amount", deposit_amount),
+        attr("share", share.to_string()),
+        attr("vault_utoken_new", assets.vault_total),
+    ]))
 }
 
 pub fn execute_unbond_user(
@@ -212,6 +307,17 @@ pub fn execute_unbond_user(
     let assets = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
     let withdraw_amount = assets.vault_total.multiply_ratio(lp_amount, total_lp_supply);
 
+    // always burn when receiving LP token
+    let burn_msg = create_burn_msg(&env, deps.storage, &state, &mut lp_token, lp_amount)?;
+    let push_update_msgs = create_push_update_msgs(
+        &deps.querier,
+        deps.storage,
+        &state,
+        &lp_token,
+        &sender,
+        Uint128::zero(),
+    )?;
+
     let mut response = if let Some(true) = immediate {
         // use full fee, zero unlocked
         create_withdraw_msgs(
@@ -253,9 +359,9 @@ pub fn execute_unbond_user(
         ])
     };
 
-    // always burn when receiving LP token
     response = response
-        .add_message(create_burn_msg(&env, deps.storage, &state, &mut lp_token, lp_amount)?)
+        .add_message(burn_msg)
+        .add_messages(push_update_msgs)
         .add_attribute("burnt_amount", lp_amount);
 
     Ok(response)
@@ -266,15 +372,16 @@ pub fn execute_withdraw_unbonding_immediate(
     env: Env,
     info: MessageInfo,
     id: u64,
+    amount: Option<Uint128>,
 ) -> ContractResult {
     let state = State::default();
     let config = state.config.load(deps.storage)?;
     state.assert_not_nested(deps.storage)?;
 
     let key = (info.sender.clone(), id);
-    let unbond_history = state.unbond_history.load(deps.storage, key.clone())?;
+    let mut unbond_history = state.unbond_history.load(deps.storage, key.clone())?;
 
-    let withdraw_amount = unbond_history.amount_asset;
+    let withdraw_amount = unbond_history.withdraw_amount(amount)?;
 
     let withdraw_pool_fee_factor = unbond_history.pool_fee_factor(env.block.time.seconds());
     let response = create_withdraw_msgs(
@@ -289,7 +396,13 @@ pub fn execute_withdraw_unbonding_immediate(
         withdraw_amount,
     )?;
 
-    state.unbond_history.remove(deps.storage, key);
+    // the remaining amount keeps unbonding with the same timing
+    unbond_history.amount_asset = unbond_history.amount_asset.checked_sub(withdraw_amount)?;
+    if unbond_history.amount_asset.is_zero() {
+        state.unbond_history.remove(deps.storage, key);
+    } else {
+        state.unbond_history.save(deps.storage, key, &unbond_history)?;
+    }
 
     Ok(response)
 }
@@ -378,7 +491,9 @@ fn create_withdraw_msgs(
 
     let protocol_fee_msg = if !withdraw_protocol_fee.is_zero() {
         Some(
-            native_asset(config.utoken.clone(), withdraw_protocol_fee)
+            config
+                .utoken
+                .with_balance(withdraw_protocol_fee)
                 .into_msg(fee_config.protocol_fee_contract)?
                 .to_specific()?,
         )
@@ -386,9 +501,8 @@ fn create_withdraw_msgs(
         None
     };
 
-    let withdraw_msg = native_asset(config.utoken.clone(), receive_amount)
-        .into_msg(receiver.clone())?
-        .to_specific()?;
+    let withdraw_msg =
+        config.utoken.with_balance(receive_amount).into_msg(receiver.clone())?.to_specific()?;
 
     Ok(Response::<CustomMsgType>::new()
         // send assets to the sender
@@ -407,6 +521,108 @@ fn create_withdraw_msgs(
         ]))
 }
 
+pub fn execute_crystallize_fees(deps: DepsMut, env: Env) -> ContractResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let mut lp_token = state.lp_token.load(deps.storage)?;
+    let mut lsds = config.lsd_group(&env);
+
+    state.assert_not_nested(deps.storage)?;
+
+    if fee_config.performance_fee_mode != PerformanceFeeMode::Crystallized {
+        return Err(ContractError::FeesNotCrystallized {});
+    }
+
+    if lp_token.total_supply.is_zero() {
+        return Err(ContractError::NothingToCrystallize {});
+    }
+
+    let balances = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
+    let exchange_rate = Decimal::from_ratio(balances.vault_total, lp_token.total_supply);
+    let high_water_mark = state.get_high_water_mark(deps.storage)?;
+
+    if exchange_rate <= high_water_mark {
+        return Err(ContractError::NothingToCrystallize {});
+    }
+
+    // only the value growth above the high-water mark is charged
+    let growth = balances.vault_total.checked_sub(lp_token.total_supply * high_water_mark)?;
+    let fee_amount = growth * fee_config.protocol_performance_fee;
+
+    // shares that are worth the fee_amount after minting: fee * supply / (total - fee)
+    let fee_shares = fee_amount
+        .multiply_ratio(lp_token.total_supply, balances.vault_total.checked_sub(fee_amount)?);
+
+    let mut messages: Vec<CosmosMsg<CustomMsgType>> = vec![];
+    if !fee_shares.is_zero() {
+        let recipient = fee_config.protocol_fee_contract;
+        messages.append(&mut create_mint_msgs(
+            &env,
+            deps.storage,
+            &state,
+            &mut lp_token,
+            recipient.clone(),
+            fee_shares,
+        )?);
+        messages.append(&mut create_push_update_msgs(
+            &deps.querier,
+            deps.storage,
+            &state,
+            &lp_token,
+            &recipient,
+            fee_shares,
+        )?);
+    }
+
+    let new_exchange_rate = Decimal::from_ratio(balances.vault_total, lp_token.total_supply);
+    state.high_water_mark.save(deps.storage, &new_exchange_rate)?;
+    state.exchange_history.save(
+        deps.storage,
+        env.block.time.seconds().div(DAY),
+        &ExchangeHistory {
+            exchange_rate: new_exchange_rate,
+            time_s: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "arb/execute_crystallize_fees"),
+        attr("old_high_water_mark", high_water_mark.to_string()),
+        attr("exchange_rate", exchange_rate.to_string()),
+        attr("fee_amount", fee_amount),
+        attr("fee_shares", fee_shares),
+        attr("high_water_mark", new_exchange_rate.to_string()),
+    ]))
+}
+
+pub fn execute_push_share_updates(deps: DepsMut, users: Vec<String>) -> ContractResult {
+    let state = State::default();
+    let lp_token = state.lp_token.load(deps.storage)?;
+
+    let push_update_contracts = state.push_update_contracts.may_load(deps.storage)?;
+    if push_update_contracts.unwrap_or_default().is_empty() {
+        return Err(ContractError::NoPushUpdateContracts {});
+    }
+
+    let mut messages: Vec<CosmosMsg<CustomMsgType>> = vec![];
+    for user in users {
+        let user = deps.api.addr_validate(&user)?;
+        messages.append(&mut create_push_update_msgs(
+            &deps.querier,
+            deps.storage,
+            &state,
+            &lp_token,
+            &user,
+            Uint128::zero(),
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "arb/execute_push_share_updates"))
+}
+
 fn create_burn_msg(
     env: &Env,
     storage: &mut dyn Storage,
@@ -431,3 +647,94 @@ fn create_mint_msgs(
     state.lp_token.save(storage, lp_token)?;
     Ok(chain(env).create_mint_msgs(lp_token.denom.clone(), amount, recipient))
 }
+
+/// Pushes the share balances of both sides of a LP token transfer. Called by the before-send hook,
+/// so the transfer is not applied to the balances yet.
+pub fn execute_track_transfer(
+    deps: DepsMut,
+    env: Env,
+    from: String,
+    to: String,
+    amount: Coin,
+) -> ContractResult {
+    let state = State::default();
+    let lp_token = state.lp_token.load(deps.storage)?;
+
+    if amount.denom != lp_token.denom {
+        return Err(ContractError::ExpectingLPToken(amount.to_string()));
+    }
+
+    let from = deps.api.addr_validate(&from)?;
+    let to = deps.api.addr_validate(&to)?;
+    let response = Response::new().add_attribute("action", "arb/execute_track_transfer");
+
+    // shares sent to or by the vault are burned or unbonded, which pushes the update itself.
+    // Mints are sent by the tokenfactory module, which is pushed with a zero balance.
+    let push_update_contracts =
+        state.push_update_contracts.may_load(deps.storage)?.unwrap_or_default();
+    if push_update_contracts.is_empty()
+        || from == env.contract.address
+        || to == env.contract.address
+    {
+        return Ok(response);
+    }
+
+    let from_balance = deps
+        .querier
+        .query_balance(&from, lp_token.denom.clone())?
+        .amount
+        .checked_sub(amount.amount)?;
+    let to_balance = deps
+        .querier
+        .query_balance(&to, lp_token.denom.clone())?
+        .amount
+        .checked_add(amount.amount)?;
+
+    let mut messages = push_update_msgs(&push_update_contracts, &lp_token, &from, from_balance)?;
+    messages.append(&mut push_update_msgs(&push_update_contracts, &lp_token, &to, to_balance)?);
+
+    Ok(response.add_messages(messages))
+}
+
+/// Notifies the push_update_contracts about the new share balance of the user.
+/// Shares sent to the vault are already deducted, while minted shares are not yet received.
+fn create_push_update_msgs(
+    querier: &QuerierWrapper,
+    storage: &dyn Storage,
+    state: &State,
+    lp_token: &LpToken,
+    user: &Addr,
+    minted: Uint128,
+) -> Result<Vec<CosmosMsg<CustomMsgType>>, ContractError> {
+    let push_update_contracts = state.push_update_contracts.may_load(storage)?.unwrap_or_default();
+    if push_update_contracts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let balance =
+        querier.query_balance(user, lp_token.denom.clone())?.amount.checked_add(minted)?;
+
+    push_update_msgs(&push_update_contracts, lp_token, user, balance)
+}
+
+fn push_update_msgs(
+    push_update_contracts: &[Addr],
+    lp_token: &LpToken,
+    user: &Addr,
+    balance: Uint128,
+) -> Result<Vec<CosmosMsg<CustomMsgType>>, ContractError> {
+    push_update_contracts
+        .iter()
+        .map(|contract| -> Result<CosmosMsg<CustomMsgType>, ContractError> {
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: to_json_binary(&PushExecuteMsg::UpdateShare {
+                    user: user.to_string(),
+                    balance,
+                    total_supply: lp_token.total_supply,
+                })?,
+                funds: vec![],
+            }))
+        })
+        .collect()
+}
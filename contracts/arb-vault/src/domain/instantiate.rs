@@ -4,9 +4,9 @@ use crate::{
     extensions::UtilizationMethodEx,
     state::{BalanceLocked, State},
 };
-use cosmwasm_std::{Addr, DepsMut, Env, Response, StdResult, Uint128};
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, Response, StdResult, Uint128};
 use cw2::set_contract_version;
-use eris::arb_vault::{InstantiateMsg, LpToken, LsdConfig, ValidatedConfig};
+use eris::arb_vault::{InstantiateMsg, LpToken, LsdConfig, PerformanceFeeMode, ValidatedConfig};
 use eris_chain_adapter::types::chain;
 use eris_chain_shared::chain_trait::ChainInterface;
 
@@ -27,6 +27,7 @@ pub fn instantiate(deps: DepsMut, env: Env, msg: InstantiateMsg) -> ContractResu
         .collect::<StdResult<Vec<LsdConfig<Addr>>>>()?;
 
     msg.utilization_method.validate()?;
+    msg.utoken.check(deps.api)?;
 
     let config = ValidatedConfig {
         unbond_time_s: msg.unbond_time_s,
@@ -37,7 +38,12 @@ pub fn instantiate(deps: DepsMut, env: Env, msg: InstantiateMsg) -> ContractResu
     state.config.save(deps.storage, &config)?;
     state.owner.save(deps.storage, &deps.api.addr_validate(&msg.owner)?)?;
     state.unbond_id.save(deps.storage, &0)?;
-    state.fee_config.save(deps.storage, &msg.fee_config.validate(deps.api)?)?;
+    let fee_config = msg.fee_config.validate(deps.api)?;
+    if fee_config.performance_fee_mode == PerformanceFeeMode::Crystallized {
+        // no shares exist yet, so the vault starts at an exchange rate of one
+        state.high_water_mark.save(deps.storage, &Decimal::one())?;
+    }
+    state.fee_config.save(deps.storage, &fee_config)?;
 
     state.update_whitelist(deps.storage, deps.api, msg.whitelist)?;
 
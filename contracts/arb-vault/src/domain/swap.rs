@@ -1,68 +1,162 @@
-// use crate::extensions::ConfigEx;
-// use astroport::asset::{token_asset, Asset, AssetInfo};
-// use cosmwasm_std::{Addr, Decimal, DepsMut, Env, MessageInfo, Response};
-// use eris::helper::addr_opt_validate;
-
-// use crate::{
-//     error::{ContractError, ContractResult},
-//     state::State,
-// };
-
-// #[allow(clippy::too_many_arguments)]
-// pub(crate) fn execute_swap_native(
-//     deps: DepsMut,
-//     env: Env,
-//     info: MessageInfo,
-//     offer_asset: Asset,
-//     ask_asset_info: Option<AssetInfo>,
-//     belief_price: Option<Decimal>,
-//     max_spread: Option<Decimal>,
-//     to: Option<String>,
-// ) -> ContractResult {
-//     let receiver_addr = addr_opt_validate(deps.api, &to)?;
-//     let to_addr = receiver_addr.unwrap_or_else(|| info.sender.clone());
-
-//     offer_asset.info.check(deps.api)?;
-//     if !offer_asset.is_native_token() {
-//         return Err(ContractError::Cw20DirectSwap {});
-//     }
-//     offer_asset.assert_sent_native_token_balance(&info)?;
-
-//     execute_swap(deps, env, offer_asset, ask_asset_info, belief_price, max_spread, to_addr)
-// }
-
-// #[allow(clippy::too_many_arguments)]
-// pub(crate) fn execute_swap_cw20(
-//     deps: DepsMut,
-//     env: Env,
-//     cw20_sender: String,
-//     offer_asset: Asset,
-//     ask_asset_info: Option<AssetInfo>,
-//     belief_price: Option<Decimal>,
-//     max_spread: Option<Decimal>,
-//     to: Option<String>,
-// ) -> ContractResult {
-//     let receiver_addr = addr_opt_validate(deps.api, &to)?;
-//     let cw20_addr = deps.api.addr_validate(&cw20_sender)?;
-//     let to_addr = receiver_addr.unwrap_or(cw20_addr);
-
-//     execute_swap(deps, env, offer_asset, ask_asset_info, belief_price, max_spread, to_addr)
-// }
-
-// fn execute_swap(
-//     deps: DepsMut,
-//     env: Env,
-//     offer_asset: Asset,
-//     ask_asset_info: Option<AssetInfo>,
-//     belief_price: Option<Decimal>,
-//     max_spread: Option<Decimal>,
-//     to_addr: cosmwasm_std::Addr,
-// ) -> ContractResult {
-//     let state = State::default();
-//     let config = state.config.load(deps.storage)?;
-//     let lsds = config.lsd_group(&env);
-
-//     let mut allowed_assets = lsds.;
-
-//     Ok(Response::new().add_attribute("action", "arb/execute_swap"))
-// }
+use astroport::asset::{Asset, AssetInfoExt};
+use cosmwasm_std::{attr, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use eris::arb_vault::{BalancesDetails, ExchangeHistory, ValidatedConfig, ValidatedFeeConfig};
+use eris::constants::DAY;
+use eris::{CustomMsgExt, CustomResponse};
+use std::ops::Div;
+
+use crate::asserts::assert_risk_limits;
+use crate::error::{ContractError, ContractResult, CustomResult};
+use crate::extensions::{BalancesEx, ConfigEx};
+use crate::state::State;
+
+pub(crate) struct SwapResult {
+    /// utoken value of the offered xtoken
+    pub offer_value: Uint128,
+    /// utoken paid out to the user
+    pub return_amount: Uint128,
+    /// discount applied to the offer value
+    pub profit_step: Decimal,
+    /// profit of the vault (offer_value - return_amount)
+    pub profit_amount: Uint128,
+    /// protocol fee on the profit in utoken
+    pub fee_amount: Uint128,
+}
+
+/// Selects the lowest profit step whose takeable amount covers the swap.
+pub(crate) fn calc_swap(
+    config: &ValidatedConfig,
+    fee_config: &ValidatedFeeConfig,
+    balances: &BalancesDetails,
+    xfactor: Decimal,
+    offer_amount: Uint128,
+) -> CustomResult<SwapResult> {
+    let offer_value = offer_amount * xfactor;
+
+    let mut profit_steps = balances.calc_all_takeable_steps(config)?;
+    profit_steps.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (profit_step, _) in profit_steps {
+        let profit_amount = offer_value * profit_step;
+        let return_amount = offer_value.checked_sub(profit_amount)?;
+
+        if balances.calc_takeable_for_profit(config, &profit_step)? >= return_amount {
+            return Ok(SwapResult {
+                offer_value,
+                return_amount,
+                profit_step,
+                profit_amount,
+                fee_amount: profit_amount * fee_config.trade_performance_fee(),
+            });
+        }
+    }
+
+    Err(ContractError::NotEnoughFundsTakeable {})
+}
+
+pub fn execute_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_asset: Asset,
+    min_receive: Option<Uint128>,
+    to: Option<String>,
+) -> ContractResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let mut lsds = config.lsd_group(&env);
+
+    state.assert_not_nested(deps.storage)?;
+    offer_asset.info.check(deps.api)?;
+    if !offer_asset.is_native_token() {
+        return Err(ContractError::Cw20DirectSwap {});
+    }
+    offer_asset.assert_sent_native_token_balance(&info)?;
+
+    if offer_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let recipient = if let Some(to) = to {
+        deps.api.addr_validate(&to)?
+    } else {
+        info.sender.clone()
+    };
+
+    let mut balances = lsds.get_total_assets_err(deps.as_ref(), &env, &state, &config)?;
+
+    let lsd = lsds.get_adapter_by_asset(offer_asset.info.clone())?;
+    lsd.assert_not_disabled()?;
+    let xfactor = balances.get_by_name(&lsd.name)?.xfactor;
+
+    // the offered amount is already part of the balances and therefore of the exposure
+    let risk = lsd.get_risk(&deps.as_ref(), &env, &state, &balances)?;
+    assert_risk_limits(&risk, Uint128::zero())?;
+    state.xfactor_high.save(deps.storage, lsd.name.clone(), &risk.xfactor_high)?;
+
+    // removing the offered amount, as it is already part of the balances
+    let offer_value = offer_asset.amount * xfactor;
+    balances.vault_total = balances.vault_total.checked_sub(offer_value)?;
+    balances.tvl_utoken = balances.tvl_utoken.checked_sub(offer_value)?;
+
+    let swap = calc_swap(&config, &fee_config, &balances, xfactor, offer_asset.amount)?;
+
+    if let Some(min_receive) = min_receive {
+        if swap.return_amount < min_receive {
+            return Err(ContractError::MinReceiveNotReached {
+                return_amount: swap.return_amount,
+                min_receive,
+            });
+        }
+    }
+
+    let return_msg = config
+        .utoken
+        .with_balance(swap.return_amount)
+        .into_msg(recipient.clone())?
+        .to_specific()?;
+
+    // fees are paid in the offered xtoken, as it is always available
+    let fee_xamount = swap.fee_amount * Decimal::one().div(xfactor);
+    let fee_msg = if fee_xamount.is_zero() {
+        None
+    } else {
+        Some(
+            lsd.adapter
+                .asset()
+                .with_balance(fee_xamount)
+                .into_msg(fee_config.protocol_fee_contract)?
+                .to_specific()?,
+        )
+    };
+
+    // the vault keeps the profit minus the fee
+    let lp_token = state.lp_token.load(deps.storage)?;
+    let new_vault_total =
+        balances.vault_total.checked_add(swap.profit_amount)?.checked_sub(swap.fee_amount)?;
+    let exchange_rate = Decimal::from_ratio(new_vault_total, lp_token.total_supply);
+    state.exchange_history.save(
+        deps.storage,
+        env.block.time.seconds().div(DAY),
+        &ExchangeHistory {
+            exchange_rate,
+            time_s: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new().add_message(return_msg).add_optional_message(fee_msg).add_attributes(vec![
+        attr("action", "arb/execute_swap"),
+        attr("type", lsd.name.clone()),
+        attr("sender", info.sender.to_string()),
+        attr("recipient", recipient.to_string()),
+        attr("offer_amount", offer_asset.amount),
+        attr("offer_value", swap.offer_value),
+        attr("return_amount", swap.return_amount),
+        attr("profit_step", swap.profit_step.to_string()),
+        attr("profit", swap.profit_amount),
+        attr("fee_amount", swap.fee_amount),
+        attr("fee_xamount", fee_xamount),
+        attr("exchange_rate", exchange_rate.to_string()),
+    ]))
+}
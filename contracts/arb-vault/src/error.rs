@@ -85,9 +85,18 @@ pub enum ContractError {
     #[error("Specified {0} is too high")]
     ConfigTooHigh(String),
 
+    #[error("Utilization curve must be monotonically increasing")]
+    UtilizationCurveNotMonotonic {},
+
     #[error("Adapter {0} is disabled")]
     AdapterDisabled(String),
 
+    #[error("Adapter {0} exposure limit reached")]
+    ExposureLimitReached(String),
+
+    #[error("Adapter {0} circuit breaker triggered: {1}")]
+    CircuitBreakerTriggered(String, String),
+
     #[error("Adapter {adapter}: {msg} - {orig}")]
     AdapterError {
         adapter: String,
@@ -142,6 +151,33 @@ pub enum ContractError {
 
     #[error("Invalid funds deposited")]
     InvalidFunds {},
+
+    #[error("Withdraw amount {amount} higher than the unbond item {available}")]
+    WithdrawAmountTooHigh {
+        amount: Uint128,
+        available: Uint128,
+    },
+
+    #[error("Performance fees are not crystallized")]
+    FeesNotCrystallized {},
+
+    #[error("Exchange rate not above the high-water mark")]
+    NothingToCrystallize {},
+
+    #[error("No push update contracts configured")]
+    NoPushUpdateContracts {},
+
+    #[error("Flash loan not repaid: expected {expected}, available {available}")]
+    FlashLoanNotRepaid {
+        expected: Uint128,
+        available: Uint128,
+    },
+
+    #[error("Return amount {return_amount} is lower than the min receive {min_receive}")]
+    MinReceiveNotReached {
+        return_amount: Uint128,
+        min_receive: Uint128,
+    },
 }
 
 pub fn adapter_error(adapter: &str, msg: &str, orig: StdError) -> ContractError {
@@ -29,15 +29,16 @@ impl BalancesEx for BalancesDetails {
         config: &ValidatedConfig,
         profit: &Decimal,
     ) -> CustomResult<Decimal> {
-        match config.utilization_method.clone() {
+        match &config.utilization_method {
             UtilizationMethod::Steps(steps) => {
                 let step = steps
-                    .into_iter()
+                    .iter()
                     .find(|step| step.0.eq(profit))
                     .ok_or(ContractError::NotSupportedProfitStep(*profit))?;
 
                 Ok(step.1)
             },
+            method => interpolate_utilization(&method.points(), profit),
         }
     }
 
@@ -45,21 +46,19 @@ impl BalancesEx for BalancesDetails {
         &self,
         config: &ValidatedConfig,
     ) -> CustomResult<Vec<(Decimal, Uint128)>> {
-        match config.utilization_method.clone() {
-            UtilizationMethod::Steps(steps) => steps
-                .into_iter()
-                .map(|step| {
-                    let max_utilization = step.1;
-                    let vault_takeable = calc_vault_takeable(
-                        max_utilization,
-                        self.vault_total,
-                        self.vault_takeable,
-                    )?;
+        // for curves the takeable amount is returned for every point defining the curve
+        config
+            .utilization_method
+            .points()
+            .into_iter()
+            .map(|step| {
+                let max_utilization = step.1;
+                let vault_takeable =
+                    calc_vault_takeable(max_utilization, self.vault_total, self.vault_takeable)?;
 
-                    Ok((step.0, vault_takeable))
-                })
-                .collect::<CustomResult<Vec<(Decimal, Uint128)>>>(),
-        }
+                Ok((step.0, vault_takeable))
+            })
+            .collect::<CustomResult<Vec<(Decimal, Uint128)>>>()
     }
 
     fn calc_takeable_for_profit(
@@ -84,6 +83,29 @@ impl BalancesEx for BalancesDetails {
     }
 }
 
+/// Linear interpolation of the max utilization between the points of a curve.
+/// Points are validated to be sorted by profit and monotonically increasing.
+fn interpolate_utilization(
+    points: &[(Decimal, Decimal)],
+    profit: &Decimal,
+) -> CustomResult<Decimal> {
+    let first = points.first().ok_or(ContractError::NotSupportedProfitStep(*profit))?;
+    if *profit < first.0 {
+        return Err(ContractError::NotSupportedProfitStep(*profit));
+    }
+
+    for (lower, upper) in points.iter().zip(points.iter().skip(1)) {
+        if *profit <= upper.0 {
+            // u = u0 + (u1 - u0) * (p - p0) / (p1 - p0)
+            let share = (*profit - lower.0) / (upper.0 - lower.0);
+            return Ok(lower.1 + (upper.1 - lower.1) * share);
+        }
+    }
+
+    // profit higher than the last point uses the max utilization of the curve
+    Ok(points.last().map(|point| point.1).unwrap_or_default())
+}
+
 fn calc_vault_takeable(
     max_utilization: Decimal,
     vault_total: Uint128,
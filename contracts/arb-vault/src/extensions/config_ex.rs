@@ -1,13 +1,23 @@
 use crate::lsds::lsdgroup::LsdGroup;
-use astroport::asset::native_asset_info;
-use cosmwasm_std::{Addr, Env, QuerierWrapper, StdResult, Uint128};
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, CosmosMsg, Env, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 use eris::arb_vault::Config;
+use eris_chain_adapter::types::CustomMsgType;
 use itertools::Itertools;
 
 pub trait ConfigEx {
     fn lsd_group(&self, env: &Env) -> LsdGroup;
     fn lsd_group_by_names(&self, env: &Env, names: Option<Vec<String>>) -> LsdGroup;
     fn query_utoken_amount(&self, querier: &QuerierWrapper, env: &Env) -> StdResult<Uint128>;
+    fn utoken_execute_msg(
+        &self,
+        contract: &Addr,
+        msg: Binary,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>>;
 }
 
 impl ConfigEx for Config<Addr> {
@@ -26,6 +36,36 @@ impl ConfigEx for Config<Addr> {
     }
 
     fn query_utoken_amount(&self, querier: &QuerierWrapper, env: &Env) -> StdResult<Uint128> {
-        native_asset_info(self.utoken.clone()).query_pool(querier, env.contract.address.clone())
+        self.utoken.query_pool(querier, env.contract.address.clone())
+    }
+
+    /// Executes the contract with the utoken amount attached.
+    /// For cw20 utokens the msg is forwarded through a Cw20 Send.
+    fn utoken_execute_msg(
+        &self,
+        contract: &Addr,
+        msg: Binary,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(match &self.utoken {
+            AssetInfo::NativeToken {
+                denom,
+            } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg,
+                funds: vec![coin(amount.u128(), denom)],
+            }),
+            AssetInfo::Token {
+                contract_addr,
+            } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: contract.to_string(),
+                    amount,
+                    msg,
+                })?,
+                funds: vec![],
+            }),
+        })
     }
 }
@@ -28,8 +28,42 @@ impl UtilizationMethodEx for UtilizationMethod {
                     }
                 }
             },
+            UtilizationMethod::PiecewiseLinear(points) => {
+                if points.is_empty() {
+                    return Err(ContractError::ConfigToLow("curve points".into()));
+                }
+                validate_curve(points)?;
+            },
+            UtilizationMethod::Kinked {
+                ..
+            } => {
+                validate_curve(&self.points())?;
+            },
         }
 
         Ok(())
     }
 }
+
+/// A curve needs to have a strictly increasing profit and a non decreasing utilization.
+fn validate_curve(points: &[(Decimal, Decimal)]) -> CustomResult<()> {
+    for (index, point) in points.iter().enumerate() {
+        if point.0 < Decimal::permille(5) {
+            // less than 0.5 % profit not allowed
+            return Err(ContractError::ConfigTooHigh("curve min profit".into()));
+        }
+
+        if point.1 > Decimal::one() {
+            return Err(ContractError::ConfigTooHigh("curve max take".into()));
+        }
+
+        if index > 0 {
+            let previous = points[index - 1];
+            if point.0 <= previous.0 || point.1 < previous.1 {
+                return Err(ContractError::UtilizationCurveNotMonotonic {});
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use astroport::asset::{native_asset_info, AssetInfo};
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, CosmosMsg, Decimal, Deps, QueryRequest, Uint128, WasmMsg, WasmQuery,
+};
+
+use eris::hub::{
+    ExecuteMsg, PendingBatch, QueryMsg, StateResponse, UnbondRequestsByUserResponseItemDetails,
+};
+use eris_chain_adapter::types::CustomMsgType;
+
+use crate::error::{adapter_error, CustomResult};
+
+use super::lsdadapter::LsdAdapter;
+
+pub struct ErisDao {
+    pub state_cache: Option<StateResponse>,
+    pub undelegation_records_cache: Option<Vec<UndelegationCacheItem>>,
+
+    pub wallet: Addr,
+    pub addr: Addr,
+    pub denom: String,
+}
+
+pub struct UndelegationCacheItem {
+    pub token_amount: Uint128,
+    pub reconciled: bool,
+    pub exchange_rate: Decimal,
+    pub batch_id: u64,
+    // not set for the pending batch
+    pub est_unbond_end_time: Option<u64>,
+}
+
+impl ErisDao {
+    fn query_unbond_requests_by_user_details(
+        &mut self,
+        deps: &Deps,
+    ) -> CustomResult<Vec<UnbondRequestsByUserResponseItemDetails>> {
+        deps.querier
+            .query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: self.addr.to_string(),
+                msg: to_json_binary(&QueryMsg::UnbondRequestsByUserDetails {
+                    user: self.wallet.to_string(),
+                    limit: Some(100u32),
+                    start_after: None,
+                })
+                .unwrap(),
+            }))
+            .map_err(|a| adapter_error("eris_dao", "query_unbond_requests_by_user_details", a))
+    }
+
+    fn query_pending_batch(&mut self, deps: &Deps) -> CustomResult<PendingBatch> {
+        deps.querier
+            .query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: self.addr.to_string(),
+                msg: to_json_binary(&QueryMsg::PendingBatch {}).unwrap(),
+            }))
+            .map_err(|a| adapter_error("eris_dao", "query_pending_batch", a))
+    }
+
+    fn query_state(&mut self, deps: &Deps) -> CustomResult<StateResponse> {
+        deps.querier
+            .query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: self.addr.to_string(),
+                msg: to_json_binary(&QueryMsg::State {}).unwrap(),
+            }))
+            .map_err(|a| adapter_error("eris_dao", "query_state", a))
+    }
+
+    fn get_unbond_msg(&self, amount: Uint128) -> CustomResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            funds: vec![coin(amount.u128(), self.denom.to_string())],
+            msg: to_json_binary(&ExecuteMsg::QueueUnbond {
+                receiver: None,
+            })?,
+        }))
+    }
+
+    fn get_withdraw_unbonded_msg(&mut self) -> CustomResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            funds: vec![],
+            msg: to_json_binary(&ExecuteMsg::WithdrawUnbonded {
+                receiver: None,
+            })?,
+        }))
+    }
+
+    fn cached_query_undelegation_records(
+        &mut self,
+        deps: &Deps,
+    ) -> CustomResult<&Vec<UndelegationCacheItem>> {
+        if self.undelegation_records_cache.is_none() {
+            // the details already contain the batches, no need to query each batch separately
+            let result: Vec<UnbondRequestsByUserResponseItemDetails> =
+                self.query_unbond_requests_by_user_details(deps)?;
+
+            let result: Vec<UndelegationCacheItem> = result
+                .iter()
+                .map(|item| {
+                    Ok(match &item.batch {
+                        Some(batch) => UndelegationCacheItem {
+                            batch_id: item.id,
+                            token_amount: item.shares,
+                            reconciled: batch.reconciled,
+                            est_unbond_end_time: Some(batch.est_unbond_end_time),
+                            exchange_rate: Decimal::from_ratio(
+                                batch.utoken_unclaimed,
+                                batch.total_shares,
+                            ),
+                        },
+                        None => {
+                            let state = self.cached_state(deps)?;
+                            let exchange_rate = state.exchange_rate;
+                            UndelegationCacheItem {
+                                batch_id: item.id,
+                                token_amount: item.shares,
+                                reconciled: false,
+                                exchange_rate,
+                                est_unbond_end_time: None,
+                            }
+                        },
+                    })
+                })
+                .collect::<CustomResult<Vec<UndelegationCacheItem>>>()?;
+
+            self.undelegation_records_cache = Some(result);
+        }
+
+        Ok(self.undelegation_records_cache.as_ref().unwrap())
+    }
+
+    fn cached_state(&mut self, deps: &Deps) -> CustomResult<&StateResponse> {
+        if self.state_cache.is_none() {
+            let result: StateResponse = self.query_state(deps)?;
+            self.state_cache = Some(result);
+        }
+
+        Ok(self.state_cache.as_ref().unwrap())
+    }
+}
+
+impl LsdAdapter for ErisDao {
+    fn used_contracts(&self) -> Vec<Addr> {
+        vec![self.addr.clone()]
+    }
+
+    fn asset(&self) -> AssetInfo {
+        native_asset_info(self.denom.clone())
+    }
+
+    fn unbond(&self, _deps: &Deps, amount: Uint128) -> CustomResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![self.get_unbond_msg(amount)?])
+    }
+
+    fn query_unbonding(&mut self, deps: &Deps) -> CustomResult<Uint128> {
+        Ok(self
+            .cached_query_undelegation_records(deps)?
+            .iter()
+            .filter(|a| !a.reconciled)
+            .map(|batch| batch.exchange_rate * batch.token_amount)
+            .sum())
+    }
+
+    fn withdraw(
+        &mut self,
+        _deps: &Deps,
+        _amount: Uint128,
+    ) -> CustomResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![self.get_withdraw_unbonded_msg()?])
+    }
+
+    fn query_withdrawable(&mut self, deps: &Deps) -> CustomResult<Uint128> {
+        Ok(self
+            .cached_query_undelegation_records(deps)?
+            .iter()
+            .filter(|a| a.reconciled)
+            .map(|batch| batch.exchange_rate * batch.token_amount)
+            .sum())
+    }
+
+    fn query_oldest_unbonding_end(&mut self, deps: &Deps) -> CustomResult<Option<u64>> {
+        Ok(self
+            .cached_query_undelegation_records(deps)?
+            .iter()
+            .filter(|a| !a.reconciled)
+            .filter_map(|batch| batch.est_unbond_end_time)
+            .min())
+    }
+
+    fn query_factor_x_to_normal(&mut self, deps: &Deps) -> CustomResult<Decimal> {
+        Ok(self.cached_state(deps)?.exchange_rate)
+    }
+
+    fn query_pending_batch_start(&mut self, deps: &Deps) -> CustomResult<u64> {
+        Ok(self.query_pending_batch(deps)?.est_unbond_start_time)
+    }
+}
@@ -26,6 +26,8 @@ pub struct UndelegationCacheItem {
     pub reconciled: bool,
     pub exchange_rate: Decimal,
     pub batch_id: u64,
+    // not set for the pending batch
+    pub est_unbond_end_time: Option<u64>,
 }
 
 impl ErisTf {
@@ -126,6 +128,7 @@ impl ErisTf {
                             token_amount: item.shares,
                             reconciled: false,
                             exchange_rate,
+                            est_unbond_end_time: None,
                         }
                     } else {
                         let previous: Batch = self.query_previous_batch(deps, item.id)?;
@@ -134,6 +137,7 @@ impl ErisTf {
                             batch_id: item.id,
                             token_amount: item.shares,
                             reconciled: previous.reconciled,
+                            est_unbond_end_time: Some(previous.est_unbond_end_time),
                             exchange_rate: Decimal::from_ratio(
                                 previous.utoken_unclaimed,
                                 previous.total_shares,
@@ -198,7 +202,20 @@ impl LsdAdapter for ErisTf {
             .sum())
     }
 
+    fn query_oldest_unbonding_end(&mut self, deps: &Deps) -> CustomResult<Option<u64>> {
+        Ok(self
+            .cached_query_undelegation_records(deps)?
+            .iter()
+            .filter(|a| !a.reconciled)
+            .filter_map(|batch| batch.est_unbond_end_time)
+            .min())
+    }
+
     fn query_factor_x_to_normal(&mut self, deps: &Deps) -> CustomResult<Decimal> {
         Ok(self.cached_state(deps)?.exchange_rate)
     }
+
+    fn query_pending_batch_start(&mut self, deps: &Deps) -> CustomResult<u64> {
+        Ok(self.query_pending_batch(deps)?.est_unbond_start_time)
+    }
 }
@@ -22,4 +22,10 @@ pub trait LsdAdapter {
     fn query_withdrawable(&mut self, deps: &Deps) -> CustomResult<Uint128>;
 
     fn query_factor_x_to_normal(&mut self, deps: &Deps) -> CustomResult<Decimal>;
+
+    /// Expected end of the oldest unbonding that is not withdrawable yet
+    fn query_oldest_unbonding_end(&mut self, deps: &Deps) -> CustomResult<Option<u64>>;
+
+    /// Time at which the hub can submit its pending unbonding batch
+    fn query_pending_batch_start(&mut self, deps: &Deps) -> CustomResult<u64>;
 }
@@ -1,6 +1,9 @@
 use astroport::asset::AssetInfo;
 use cosmwasm_std::{attr, Addr, Attribute, CosmosMsg, Deps, DepsMut, Env, Uint128};
-use eris::arb_vault::{BalancesDetails, ClaimBalance, LsdConfig, LsdType, ValidatedConfig};
+use eris::arb_vault::{
+    BalancesDetails, ClaimBalance, LsdConfig, LsdRisk, LsdType, UnbondPlanItem, UnbondPlanReason,
+    ValidatedConfig,
+};
 use eris_chain_adapter::types::CustomMsgType;
 use itertools::Itertools;
 
@@ -10,7 +13,7 @@ use crate::{
     state::State,
 };
 
-use super::{eris_tf::ErisTf, lsdwrapper::LsdWrapper, steak_tf::SteakTf};
+use super::{eris_dao::ErisDao, eris_tf::ErisTf, lsdwrapper::LsdWrapper, steak_tf::SteakTf};
 
 pub struct LsdGroup {
     lsds: Vec<LsdWrapper>,
@@ -25,6 +28,8 @@ impl LsdGroup {
                     disabled: config.disabled,
                     name: config.name.clone(),
                     wallet: wallet_address.clone(),
+                    unbond_schedule: config.unbond_schedule.clone(),
+                    risk_limits: config.risk_limits.clone(),
                     adapter: match config.lsd_type.clone() {
                         LsdType::Eris {
                             addr,
@@ -46,6 +51,16 @@ impl LsdGroup {
                             denom,
                             wallet: wallet_address.clone(),
                         }),
+                        LsdType::ErisDao {
+                            addr,
+                            denom,
+                        } => Box::new(ErisDao {
+                            state_cache: None,
+                            undelegation_records_cache: None,
+                            addr,
+                            denom,
+                            wallet: wallet_address.clone(),
+                        }),
                     },
                 }
             })
@@ -81,6 +96,16 @@ impl LsdGroup {
             .collect::<CustomResult<Vec<ClaimBalance>>>()
     }
 
+    pub fn get_risks(
+        &mut self,
+        deps: &Deps,
+        env: &Env,
+        state: &State,
+        balances: &BalancesDetails,
+    ) -> CustomResult<Vec<LsdRisk>> {
+        self.lsds.iter_mut().map(|lsd| lsd.get_risk(deps, env, state, balances)).collect()
+    }
+
     pub fn get_withdraw_msgs(
         &mut self,
         deps: &DepsMut,
@@ -101,22 +126,57 @@ impl LsdGroup {
         Ok((messages, attributes))
     }
 
+    /// Checks for each LSD if the xtoken balance can be unbonded based on its unbond schedule.
+    /// Scheduled LSDs wait until the pending batch of their hub is due.
+    pub fn get_unbond_plan(&mut self, deps: &Deps, env: &Env) -> CustomResult<Vec<UnbondPlanItem>> {
+        let now = env.block.time.seconds();
+
+        self.lsds
+            .iter_mut()
+            .map(|lsd| -> CustomResult<UnbondPlanItem> {
+                let amount = lsd.adapter.asset().query_pool(&deps.querier, &lsd.wallet)?;
+
+                let (min_batch, next_unbond_time) = match &lsd.unbond_schedule {
+                    Some(schedule) => {
+                        (schedule.min_batch, lsd.adapter.query_pending_batch_start(deps)?)
+                    },
+                    None => (Uint128::zero(), now),
+                };
+
+                let reason = if amount.is_zero() {
+                    UnbondPlanReason::NothingToUnbond
+                } else if amount < min_batch {
+                    UnbondPlanReason::BelowMinBatch
+                } else if now < next_unbond_time {
+                    UnbondPlanReason::WaitingForBatch
+                } else {
+                    UnbondPlanReason::Ready
+                };
+
+                Ok(UnbondPlanItem {
+                    name: lsd.name.clone(),
+                    amount,
+                    next_unbond_time: next_unbond_time.max(now),
+                    reason,
+                })
+            })
+            .collect()
+    }
+
     pub fn get_unbond_msgs(
         &mut self,
         deps: &DepsMut,
+        plan: &[UnbondPlanItem],
     ) -> CustomResult<(Vec<CosmosMsg<CustomMsgType>>, Vec<Attribute>)> {
         let mut messages: Vec<CosmosMsg<CustomMsgType>> = vec![];
         let mut attributes: Vec<Attribute> = vec![attr("action", "arb/execute_unbond_liquidity")];
 
-        for lsd in self.lsds.iter_mut() {
-            let unbondable_amount = lsd.adapter.asset().query_pool(&deps.querier, &lsd.wallet)?;
-
-            if !unbondable_amount.is_zero() {
-                let mut msgs = lsd.adapter.unbond(&deps.as_ref(), unbondable_amount)?;
-                messages.append(&mut msgs);
-                attributes.push(attr("type", lsd.name.clone()));
-                attributes.push(attr("unbond_amount", unbondable_amount))
-            }
+        for item in plan.iter().filter(|item| item.is_ready()) {
+            let lsd = self.get_adapter_by_name(&item.name)?;
+            let mut msgs = lsd.adapter.unbond(&deps.as_ref(), item.amount)?;
+            messages.append(&mut msgs);
+            attributes.push(attr("type", item.name.clone()));
+            attributes.push(attr("unbond_amount", item.amount))
         }
         Ok((messages, attributes))
     }
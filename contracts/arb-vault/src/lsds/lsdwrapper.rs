@@ -1,7 +1,11 @@
-use cosmwasm_std::{Addr, Deps};
-use eris::arb_vault::ClaimBalance;
+use cosmwasm_std::{Addr, Decimal, Deps, Env};
+use eris::arb_vault::{
+    BalancesDetails, CircuitBreaker, ClaimBalance, LsdRisk, RiskLimits, UnbondSchedule,
+};
 
 use crate::error::{ContractError, CustomResult};
+use crate::extensions::BalancesEx;
+use crate::state::State;
 
 use super::lsdadapter::LsdAdapter;
 
@@ -10,6 +14,8 @@ pub struct LsdWrapper {
     pub disabled: bool,
     pub name: String,
     pub wallet: Addr,
+    pub unbond_schedule: Option<UnbondSchedule>,
+    pub risk_limits: Option<RiskLimits>,
 }
 
 impl LsdWrapper {
@@ -30,4 +36,56 @@ impl LsdWrapper {
             xfactor: self.adapter.query_factor_x_to_normal(deps)?,
         })
     }
+
+    /// Checks the exposure and the circuit breaker of the LSD against its risk limits.
+    pub fn get_risk(
+        &mut self,
+        deps: &Deps,
+        env: &Env,
+        state: &State,
+        balances: &BalancesDetails,
+    ) -> CustomResult<LsdRisk> {
+        let balance = balances.get_by_name(&self.name)?;
+        let exposure = (balance.xbalance * balance.xfactor)
+            .checked_add(balance.unbonding)?
+            .checked_add(balance.withdrawable)?;
+
+        let xfactor_high = state
+            .xfactor_high
+            .may_load(deps.storage, self.name.clone())?
+            .unwrap_or_default()
+            .max(balance.xfactor);
+        let oldest_unbonding_end = self.adapter.query_oldest_unbonding_end(deps)?;
+
+        let limits = self.risk_limits.clone().unwrap_or_default();
+
+        let max_exposure =
+            limits.max_exposure_bps.map(|bps| balances.tvl_utoken.multiply_ratio(bps, 10000u128));
+
+        let depegged = limits.max_xfactor_drop_bps.map_or(false, |bps| {
+            balance.xfactor < xfactor_high * (Decimal::one() - Decimal::from_ratio(bps, 10000u128))
+        });
+        let stalled = match (limits.max_unbonding_delay_s, oldest_unbonding_end) {
+            (Some(max_delay), Some(end)) => env.block.time.seconds() > end + max_delay,
+            _ => false,
+        };
+
+        let circuit_breaker = if depegged {
+            Some(CircuitBreaker::Depeg)
+        } else if stalled {
+            Some(CircuitBreaker::UnbondingStalled)
+        } else {
+            None
+        };
+
+        Ok(LsdRisk {
+            name: self.name.clone(),
+            exposure,
+            max_exposure,
+            xfactor: balance.xfactor,
+            xfactor_high,
+            oldest_unbonding_end,
+            circuit_breaker,
+        })
+    }
 }
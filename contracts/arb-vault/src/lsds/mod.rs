@@ -1,3 +1,4 @@
+pub mod eris_dao;
 pub mod eris_tf;
 pub mod lsdadapter;
 pub mod lsdgroup;
@@ -24,6 +24,8 @@ pub struct UndelegationCacheItem {
     pub reconciled: bool,
     pub exchange_rate: Decimal,
     pub batch_id: u64,
+    // not set for the pending batch
+    pub est_unbond_end_time: Option<u64>,
 }
 
 // SAME AS ERIS, ALL CHANGES ARE MARKED
@@ -125,6 +127,7 @@ impl SteakTf {
                             token_amount: item.shares,
                             reconciled: false,
                             exchange_rate,
+                            est_unbond_end_time: None,
                         }
                     } else {
                         let previous: Batch = self.query_previous_batch(deps, item.id)?;
@@ -133,6 +136,7 @@ impl SteakTf {
                             batch_id: item.id,
                             token_amount: item.shares,
                             reconciled: previous.reconciled,
+                            est_unbond_end_time: Some(previous.est_unbond_end_time),
                             exchange_rate: Decimal::from_ratio(
                                 // SAME AS ERIS, ONLY THIS LINE CHANGED
                                 previous.amount_unclaimed,
@@ -198,7 +202,20 @@ impl LsdAdapter for SteakTf {
             .sum())
     }
 
+    fn query_oldest_unbonding_end(&mut self, deps: &Deps) -> CustomResult<Option<u64>> {
+        Ok(self
+            .cached_query_undelegation_records(deps)?
+            .iter()
+            .filter(|a| !a.reconciled)
+            .filter_map(|batch| batch.est_unbond_end_time)
+            .min())
+    }
+
     fn query_factor_x_to_normal(&mut self, deps: &Deps) -> CustomResult<Decimal> {
         Ok(self.cached_state(deps)?.exchange_rate)
     }
+
+    fn query_pending_batch_start(&mut self, deps: &Deps) -> CustomResult<u64> {
+        Ok(self.query_pending_batch(deps)?.est_unbond_start_time)
+    }
 }
@@ -1,13 +1,16 @@
+use crate::asserts::assert_risk_limits;
+use crate::domain::swap::calc_swap;
 use crate::error::{ContractError, CustomResult};
 use crate::extensions::{BalancesEx, ConfigEx};
 use crate::helpers::calc_fees;
 use crate::state::{State, UnbondHistory};
-use astroport::asset::native_asset_info;
+use astroport::asset::{native_asset_info, Asset};
 use cosmwasm_std::{Decimal, Deps, Env, Order, StdResult, Uint128};
 use cw_storage_plus::Bound;
 use eris::arb_vault::{
-    BalancesOptionalDetails, ConfigResponse, ExchangeHistory, ExchangeRatesResponse, StateDetails,
-    StateResponse, TakeableResponse, UnbondItem, UnbondRequestsResponse, UserInfoResponse,
+    BalancesOptionalDetails, ConfigResponse, ExchangeHistory, ExchangeRatesResponse,
+    SimulationResponse, StateDetails, StateResponse, TakeableResponse, UnbondItem,
+    UnbondPlanResponse, UnbondRequestsResponse, UserInfoResponse, WithdrawImmediateQuoteResponse,
 };
 use eris::constants::DAY;
 use eris::voting_escrow::{DEFAULT_LIMIT, MAX_LIMIT};
@@ -21,12 +24,15 @@ pub fn query_config(deps: Deps) -> CustomResult<ConfigResponse> {
     let fee_config = state.fee_config.load(deps.storage)?;
     let owner = state.owner.load(deps.storage)?;
     let whitelist = state.whitelisted_addrs.may_load(deps.storage)?;
+    let push_update_contracts =
+        state.push_update_contracts.may_load(deps.storage)?.unwrap_or_default();
     Ok(ConfigResponse {
         config,
         owner,
         fee_config,
         whitelist,
         lp_token,
+        push_update_contracts,
     })
 }
 
@@ -99,6 +105,35 @@ pub fn query_unbond_requests(
     })
 }
 
+pub fn query_withdraw_immediate_quote(
+    deps: Deps,
+    env: Env,
+    address: String,
+    id: u64,
+    amount: Option<Uint128>,
+) -> CustomResult<WithdrawImmediateQuoteResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let state = State::default();
+    let fee_config = state.fee_config.load(deps.storage)?;
+
+    let unbond_history = state.unbond_history.load(deps.storage, (address, id))?;
+    let withdraw_amount = unbond_history.withdraw_amount(amount)?;
+    let withdraw_pool_fee_factor = unbond_history.pool_fee_factor(env.block.time.seconds());
+
+    let (withdraw_protocol_fee, withdraw_pool_fee) =
+        calc_fees(&fee_config, withdraw_amount, withdraw_pool_fee_factor)?;
+
+    Ok(WithdrawImmediateQuoteResponse {
+        withdraw_amount,
+        receive_amount: withdraw_amount
+            .checked_sub(withdraw_protocol_fee)?
+            .checked_sub(withdraw_pool_fee)?,
+        withdraw_protocol_fee,
+        withdraw_pool_fee,
+        remaining_amount: unbond_history.amount_asset.checked_sub(withdraw_amount)?,
+    })
+}
+
 pub fn query_state(
     deps: Deps,
     env: Env,
@@ -116,6 +151,7 @@ pub fn query_state(
             takeable_steps: balances.calc_all_takeable_steps(&config).map_err(|e| {
                 ContractError::CalculationError("takeable for steps".into(), e.to_string())
             })?,
+            lsd_risks: lsds.get_risks(&deps, &env, &state, &balances)?,
         })
     } else {
         None
@@ -210,3 +246,46 @@ pub fn query_exchange_rates(
         apr,
     })
 }
+
+pub fn query_simulation(
+    deps: Deps,
+    env: Env,
+    offer_asset: Asset,
+) -> CustomResult<SimulationResponse> {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let mut lsds = config.lsd_group(&env);
+    let balances = lsds.get_total_assets_err(deps, &env, &state, &config)?;
+
+    let lsd = lsds.get_adapter_by_asset(offer_asset.info)?;
+    lsd.assert_not_disabled()?;
+    let xfactor = balances.get_by_name(&lsd.name)?.xfactor;
+
+    // unlike in the swap, the offered amount is not yet part of the exposure
+    let risk = lsd.get_risk(&deps, &env, &state, &balances)?;
+    assert_risk_limits(&risk, offer_asset.amount * xfactor)?;
+
+    let swap = calc_swap(&config, &fee_config, &balances, xfactor, offer_asset.amount)?;
+
+    Ok(SimulationResponse {
+        return_amount: swap.return_amount,
+        offer_value: swap.offer_value,
+        profit_step: swap.profit_step,
+        profit_amount: swap.profit_amount,
+    })
+}
+
+pub fn query_unbond_plan(
+    deps: Deps,
+    env: Env,
+    names: Option<Vec<String>>,
+) -> CustomResult<UnbondPlanResponse> {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+    let mut lsds = config.lsd_group_by_names(&env, names);
+
+    Ok(UnbondPlanResponse {
+        items: lsds.get_unbond_plan(&deps, &env)?,
+    })
+}
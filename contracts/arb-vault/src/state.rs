@@ -3,14 +3,25 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Api, Decimal, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use eris::arb_vault::{
-    ClaimBalance, ExchangeHistory, LpToken, ValidatedConfig, ValidatedFeeConfig,
+    ClaimBalance, ExchangeHistory, LpToken, LsdConfig, UtilizationMethod, ValidatedConfig,
+    ValidatedFeeConfig,
 };
 
+/// Config stored before the utoken supported cw20 tokens, only used for the migration
+#[cw_serde]
+pub struct LegacyConfig {
+    pub utoken: String,
+    pub utilization_method: UtilizationMethod,
+    pub unbond_time_s: u64,
+    pub lsds: Vec<LsdConfig<Addr>>,
+}
+
 #[cw_serde]
 pub struct BalanceCheckpoint {
     pub vault_available: Uint128,
     pub tvl_utoken: Uint128,
-    pub active_balance: ClaimBalance,
+    // not set for flash loans
+    pub active_balance: Option<ClaimBalance>,
 }
 
 #[cw_serde]
@@ -39,6 +50,20 @@ impl UnbondHistory {
 
         Decimal::one() - progress
     }
+
+    /// Returns the requested withdraw amount, by default the full amount of the item.
+    pub fn withdraw_amount(&self, amount: Option<Uint128>) -> Result<Uint128, ContractError> {
+        match amount {
+            Some(amount) if amount > self.amount_asset => {
+                Err(ContractError::WithdrawAmountTooHigh {
+                    amount,
+                    available: self.amount_asset,
+                })
+            },
+            Some(amount) => Ok(amount),
+            None => Ok(self.amount_asset),
+        }
+    }
 }
 
 pub(crate) struct State<'a> {
@@ -53,6 +78,10 @@ pub(crate) struct State<'a> {
     pub balance_checkpoint: Item<'a, BalanceCheckpoint>,
     pub balance_locked: Item<'a, BalanceLocked>,
     pub whitelisted_addrs: Item<'a, Vec<Addr>>,
+    pub push_update_contracts: Item<'a, Vec<Addr>>,
+    pub high_water_mark: Item<'a, Decimal>,
+    // highest exchange rate seen during arbitrage by LSD name
+    pub xfactor_high: Map<'a, String, Decimal>,
 }
 
 impl Default for State<'static> {
@@ -69,6 +98,9 @@ impl Default for State<'static> {
             balance_checkpoint: Item::new("balance_checkpoint"),
             balance_locked: Item::new("balance_locked"),
             whitelisted_addrs: Item::new("whitelisted_addrs"),
+            push_update_contracts: Item::new("push_update_contracts"),
+            high_water_mark: Item::new("high_water_mark"),
+            xfactor_high: Map::new("xfactor_high"),
         }
     }
 }
@@ -148,6 +180,11 @@ impl<'a> State<'a> {
         Ok(())
     }
 
+    /// Returns the high-water mark. It is seeded whenever the fees switch to being crystallized.
+    pub fn get_high_water_mark(&self, storage: &dyn Storage) -> Result<Decimal, ContractError> {
+        Ok(self.high_water_mark.may_load(storage)?.unwrap_or_else(Decimal::one))
+    }
+
     pub(crate) fn update_whitelist(
         &self,
         store: &mut dyn Storage,
@@ -2,8 +2,8 @@ use super::cw20_querier::Cw20Querier;
 use super::helpers::err_unsupported_query;
 use cosmwasm_std::testing::{BankQuerier, StakingQuerier, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coin, from_json, from_json, to_json_binary, Coin, Decimal, Empty, Querier, QuerierResult,
-    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+    coin, from_json, to_json_binary, Coin, Decimal, Empty, Querier, QuerierResult, QueryRequest,
+    SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cw20::Cw20QueryMsg;
 use std::str::FromStr;
@@ -144,6 +144,72 @@ impl CustomQuerier {
                         ),
                         _ => err_unsupported_query(msg),
                     };
+                } else if contract_addr == "eris_dao" {
+                    return match from_json(msg).unwrap() {
+                        eris::hub::QueryMsg::PendingBatch {} => SystemResult::Ok(
+                            to_json_binary(&eris::hub::PendingBatch {
+                                id: 3,
+                                ustake_to_burn: Uint128::from(1000u128),
+                                est_unbond_start_time: 123,
+                            })
+                            .into(),
+                        ),
+                        eris::hub::QueryMsg::UnbondRequestsByUserDetails {
+                            ..
+                        } => {
+                            let batch = |id: u64| eris::hub::Batch {
+                                id,
+                                reconciled: id < 2,
+                                total_shares: Uint128::from(1000u128),
+                                utoken_unclaimed: Uint128::from(1100u128),
+                                est_unbond_end_time: 100,
+                            };
+
+                            SystemResult::Ok(
+                                to_json_binary(&vec![
+                                    eris::hub::UnbondRequestsByUserResponseItemDetails {
+                                        id: 1,
+                                        shares: self.withdrawable_amount,
+                                        state: "COMPLETED".to_string(),
+                                        batch: Some(batch(1)),
+                                        pending: None,
+                                    },
+                                    eris::hub::UnbondRequestsByUserResponseItemDetails {
+                                        id: 2,
+                                        shares: self.unbonding_amount,
+                                        state: "UNBONDING".to_string(),
+                                        batch: Some(batch(2)),
+                                        pending: None,
+                                    },
+                                    eris::hub::UnbondRequestsByUserResponseItemDetails {
+                                        id: 3,
+                                        shares: self.unbonding_amount_eris.unwrap_or_default(),
+                                        state: "PENDING".to_string(),
+                                        batch: None,
+                                        pending: Some(eris::hub::PendingBatch {
+                                            id: 3,
+                                            ustake_to_burn: Uint128::from(1000u128),
+                                            est_unbond_start_time: 123,
+                                        }),
+                                    },
+                                ])
+                                .into(),
+                            )
+                        },
+                        eris::hub::QueryMsg::State {} => SystemResult::Ok(
+                            to_json_binary(&eris::hub::StateResponse {
+                                total_ustake: Uint128::from(1000u128),
+                                total_utoken: Uint128::from(1200u128),
+                                exchange_rate: Decimal::from_str("1.2").unwrap(),
+                                unlocked_coins: vec![],
+                                unbonding: Uint128::new(0),
+                                available: Uint128::new(0),
+                                tvl_utoken: Uint128::new(1200),
+                            })
+                            .into(),
+                        ),
+                        _ => err_unsupported_query(msg),
+                    };
                 } else if contract_addr == "backbone" {
                     return match from_json(msg).unwrap() {
                         steak::hub::QueryMsg::PendingBatch {} => SystemResult::Ok(
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use astroport::asset::native_asset_info;
 use cosmwasm_std::testing::{mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_json, Addr, BlockInfo, ContractInfo, Decimal, Deps, Env, OwnedDeps, QuerierResult,
@@ -69,6 +70,8 @@ pub fn create_default_lsd_configs() -> Vec<LsdConfig<String>> {
                 addr: "eris".into(),
                 denom: "eriscw".into(),
             },
+            unbond_schedule: None,
+            risk_limits: None,
         },
         LsdConfig {
             disabled: false,
@@ -77,6 +80,8 @@ pub fn create_default_lsd_configs() -> Vec<LsdConfig<String>> {
                 addr: "backbone".into(),
                 denom: "backbonecw".into(),
             },
+            unbond_schedule: None,
+            risk_limits: None,
         },
     ]
 }
@@ -143,7 +148,7 @@ pub fn create_default_init() -> InstantiateMsg {
     InstantiateMsg {
         denom: "arbtoken".into(),
         owner: "owner".into(),
-        utoken: "utoken".into(),
+        utoken: native_asset_info("utoken".into()),
         utilization_method: eris::arb_vault::UtilizationMethod::Steps(vec![
             (
                 // 1% = 50% of pool
@@ -173,6 +178,8 @@ pub fn create_default_init() -> InstantiateMsg {
             protocol_performance_fee: Decimal::from_str("0.01").unwrap(),
             protocol_withdraw_fee: Decimal::from_str("0.02").unwrap(),
             immediate_withdraw_fee: Decimal::from_str("0.05").unwrap(),
+            flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+            performance_fee_mode: eris::arb_vault::PerformanceFeeMode::PerTrade,
         },
         whitelist: vec!["whitelisted_exec".to_string()],
     }
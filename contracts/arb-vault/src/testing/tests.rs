@@ -1,32 +1,42 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::{
-    contract::execute,
+    contract::{execute, instantiate, sudo},
     error::ContractError,
-    query::{query_state, query_takeable, query_unbond_requests},
+    lsds::{eris_dao::ErisDao, lsdadapter::LsdAdapter},
+    query::{
+        query_simulation, query_state, query_takeable, query_unbond_plan, query_unbond_requests,
+        query_withdraw_immediate_quote,
+    },
+    state::State,
     testing::helpers::{
-        _mock_env_at_timestamp, chain_test, create_default_lsd_configs, mock_env, setup_test,
+        _mock_env_at_timestamp, chain_test, create_default_init, create_default_lsd_configs,
+        mock_dependencies, mock_env, setup_test,
     },
 };
 
-use crate::query::{query_config, query_user_info};
+use crate::query::{query_config, query_exchange_rates, query_user_info};
 
-use astroport::asset::{native_asset, native_asset_info, token_asset_info};
+use astroport::asset::{native_asset, native_asset_info, token_asset_info, Asset};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     attr, coin, from_json, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps,
-    OwnedDeps, Response, Uint128, WasmMsg,
+    OwnedDeps, Response, StdError, Uint128, WasmMsg,
 };
 use eris::{
     arb_vault::{
-        Balances, ClaimBalance, Config, ConfigResponse, ExecuteMsg, ExecuteSubMsg, FeeConfig,
-        LpToken, StateDetails, StateResponse, TakeableResponse, UnbondItem, UnbondRequestsResponse,
-        UserInfoResponse, UtilizationMethod,
+        Balances, CircuitBreaker, ClaimBalance, Config, ConfigResponse, ExecuteMsg, ExecuteSubMsg,
+        FeeConfig, LpToken, LsdRisk, PushExecuteMsg, ReceiveMsg, RiskLimits, SimulationResponse,
+        StateDetails, StateResponse, SudoMsg, TakeableResponse, UnbondItem, UnbondPlanItem,
+        UnbondPlanReason, UnbondRequestsResponse, UnbondSchedule, UserInfoResponse,
+        UtilizationMethod, WithdrawImmediateQuoteResponse,
     },
     CustomMsgExt,
 };
 
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use eris_chain_adapter::types::CustomMsgType;
 use eris_chain_shared::chain_trait::ChainInterface;
 use itertools::Itertools;
@@ -46,7 +56,7 @@ fn proper_initialization() {
         config,
         ConfigResponse {
             config: Config {
-                utoken: "utoken".into(),
+                utoken: native_asset_info("utoken".into()),
                 utilization_method: eris::arb_vault::UtilizationMethod::Steps(vec![
                     (Decimal::from_ratio(10u128, 1000u128), Decimal::from_ratio(50u128, 100u128),),
                     (Decimal::from_ratio(15u128, 1000u128), Decimal::from_ratio(70u128, 100u128),),
@@ -64,6 +74,8 @@ fn proper_initialization() {
                 protocol_performance_fee: Decimal::from_str("0.01").unwrap(),
                 protocol_withdraw_fee: Decimal::from_str("0.02").unwrap(),
                 immediate_withdraw_fee: Decimal::from_str("0.05").unwrap(),
+                flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+                performance_fee_mode: eris::arb_vault::PerformanceFeeMode::PerTrade,
             },
             lp_token: LpToken {
                 denom: TEST_LP_TOKEN.into(),
@@ -71,6 +83,7 @@ fn proper_initialization() {
             },
             whitelist: Some(vec![Addr::unchecked("whitelisted_exec")]),
             owner: Addr::unchecked("owner"),
+            push_update_contracts: vec![],
         }
     );
 }
@@ -89,6 +102,10 @@ fn update_config() {
         fee_config: None,
         set_whitelist: None,
         remove_whitelist: None,
+        set_unbond_schedule: None,
+        set_risk_limits: None,
+        reset_xfactor_high: None,
+        set_push_update_contracts: None,
     };
 
     let res =
@@ -103,7 +120,7 @@ fn update_config() {
         config,
         ConfigResponse {
             config: Config {
-                utoken: "utoken".into(),
+                utoken: native_asset_info("utoken".into()),
                 utilization_method: UtilizationMethod::Steps(vec![
                     (Decimal::from_ratio(10u128, 1000u128), Decimal::from_ratio(50u128, 100u128),),
                     (Decimal::from_ratio(15u128, 1000u128), Decimal::from_ratio(70u128, 100u128),),
@@ -121,6 +138,8 @@ fn update_config() {
                 protocol_performance_fee: Decimal::from_str("0.01").unwrap(),
                 protocol_withdraw_fee: Decimal::from_str("0.02").unwrap(),
                 immediate_withdraw_fee: Decimal::from_str("0.05").unwrap(),
+                flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+                performance_fee_mode: eris::arb_vault::PerformanceFeeMode::PerTrade,
             },
             lp_token: LpToken {
                 denom: TEST_LP_TOKEN.into(),
@@ -128,6 +147,7 @@ fn update_config() {
             },
             whitelist: Some(vec![Addr::unchecked("whitelisted_exec")]),
             owner: Addr::unchecked("owner"),
+            push_update_contracts: vec![],
         }
     );
 
@@ -141,6 +161,10 @@ fn update_config() {
         fee_config: None,
         remove_whitelist: None,
         set_whitelist: None,
+        set_unbond_schedule: None,
+        set_risk_limits: None,
+        reset_xfactor_high: None,
+        set_push_update_contracts: None,
     };
 
     let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), upd_msg).unwrap();
@@ -151,7 +175,7 @@ fn update_config() {
         config,
         ConfigResponse {
             config: Config {
-                utoken: "utoken".into(),
+                utoken: native_asset_info("utoken".into()),
                 utilization_method: UtilizationMethod::Steps(vec![]),
                 unbond_time_s: 10,
                 lsds: create_default_lsd_configs()
@@ -164,6 +188,8 @@ fn update_config() {
                 protocol_performance_fee: Decimal::from_str("0.01").unwrap(),
                 protocol_withdraw_fee: Decimal::from_str("0.02").unwrap(),
                 immediate_withdraw_fee: Decimal::from_str("0.05").unwrap(),
+                flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+                performance_fee_mode: eris::arb_vault::PerformanceFeeMode::PerTrade,
             },
             lp_token: LpToken {
                 denom: TEST_LP_TOKEN.into(),
@@ -171,10 +197,190 @@ fn update_config() {
             },
             whitelist: Some(vec![Addr::unchecked("whitelisted_exec")]),
             owner: Addr::unchecked("owner"),
+            push_update_contracts: vec![],
         }
     );
 }
 
+#[test]
+fn utilization_curves() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let update = |method: UtilizationMethod| ExecuteMsg::UpdateConfig {
+        utilization_method: Some(method),
+        unbond_time_s: None,
+        disable_lsd: None,
+        insert_lsd: None,
+        remove_lsd: None,
+        force_remove_lsd: None,
+        fee_config: None,
+        remove_whitelist: None,
+        set_whitelist: None,
+        set_unbond_schedule: None,
+        set_risk_limits: None,
+        reset_xfactor_high: None,
+        set_push_update_contracts: None,
+    };
+    let takeable = |deps: Deps, profit: &str| {
+        query_takeable(deps, mock_env(), Some(Decimal::from_str(profit).unwrap()))
+            .map(|res| res.takeable.unwrap())
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update(UtilizationMethod::PiecewiseLinear(vec![
+            (Decimal::from_str("0.02").unwrap(), Decimal::from_str("0.5").unwrap()),
+            (Decimal::from_str("0.01").unwrap(), Decimal::from_str("0.9").unwrap()),
+        ])),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UtilizationCurveNotMonotonic {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update(UtilizationMethod::Kinked {
+            min_profit: Decimal::from_str("0.005").unwrap(),
+            kink_profit: Decimal::from_str("0.015").unwrap(),
+            kink_utilization: Decimal::from_str("1.1").unwrap(),
+            max_profit: Decimal::from_str("0.025").unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ConfigTooHigh("curve max take".into()));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update(UtilizationMethod::PiecewiseLinear(vec![
+            (Decimal::from_str("0.01").unwrap(), Decimal::from_str("0.5").unwrap()),
+            (Decimal::from_str("0.02").unwrap(), Decimal::from_str("0.9").unwrap()),
+        ])),
+    )
+    .unwrap();
+
+    assert_eq!(
+        takeable(deps.as_ref(), "0.005").unwrap_err(),
+        ContractError::NotSupportedProfitStep(Decimal::from_str("0.005").unwrap())
+    );
+    assert_eq!(takeable(deps.as_ref(), "0.01").unwrap(), Uint128::new(50_000000u128));
+    // interpolated between 50 % and 90 %
+    assert_eq!(takeable(deps.as_ref(), "0.015").unwrap(), Uint128::new(70_000000u128));
+    // capped at the last point
+    assert_eq!(takeable(deps.as_ref(), "0.03").unwrap(), Uint128::new(90_000000u128));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update(UtilizationMethod::Kinked {
+            min_profit: Decimal::from_str("0.005").unwrap(),
+            kink_profit: Decimal::from_str("0.015").unwrap(),
+            kink_utilization: Decimal::from_str("0.8").unwrap(),
+            max_profit: Decimal::from_str("0.025").unwrap(),
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(takeable(deps.as_ref(), "0.01").unwrap(), Uint128::new(40_000000u128));
+    assert_eq!(takeable(deps.as_ref(), "0.02").unwrap(), Uint128::new(90_000000u128));
+    assert_eq!(
+        query_takeable(deps.as_ref(), mock_env(), None).unwrap().steps,
+        vec![
+            (Decimal::from_str("0.005").unwrap(), Uint128::zero()),
+            (Decimal::from_str("0.015").unwrap(), Uint128::new(80_000000u128)),
+            (Decimal::from_str("0.025").unwrap(), Uint128::new(100_000000u128)),
+        ]
+    );
+}
+
+#[test]
+fn cw20_utoken() {
+    let mut deps = mock_dependencies();
+    let mut msg = create_default_init();
+    msg.utoken = token_asset_info(Addr::unchecked("utokencw"));
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    deps.querier.cw20_querier.balances.insert(
+        "utokencw".to_string(),
+        HashMap::from([(MOCK_CONTRACT_ADDR.to_string(), 100_000000u128)]),
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(100_000000, "utoken")]),
+        ExecuteMsg::Deposit {
+            asset: native_asset("utoken".to_string(), Uint128::new(100_000000)),
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let deposit = |sender: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100_000000),
+            msg: to_json_binary(&ReceiveMsg::Deposit {
+                receiver: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("othercw", &[]), deposit("user")).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("utokencw", &[]), deposit("user")).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "arb/execute_deposit"),
+            attr("sender", "user"),
+            attr("recipient", "user"),
+            attr("deposit_amount", "100000000"),
+            attr("share", "100000000"),
+            attr("vault_utoken_new", "100000000"),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("whitelisted_exec", &[]),
+        ExecuteMsg::ExecuteArbitrage {
+            msg: ExecuteSubMsg {
+                contract_addr: None,
+                funds_amount: Uint128::new(10_000000u128),
+                msg: to_json_binary("exec_any_swap").unwrap(),
+            },
+            result_token: native_asset_info("eriscw".into()),
+            wanted_profit: Decimal::from_str("0.01").unwrap(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "utokencw".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: "whitelisted_exec".to_string(),
+                amount: Uint128::new(10_000000u128),
+                msg: to_json_binary("exec_any_swap").unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
 #[test]
 fn provide_liquidity_wrong_token() {
     let mut deps = setup_test();
@@ -946,6 +1152,7 @@ fn withdraw_liquidity_unbonding_query_requests_success() {
     //
     let withdraw_immediate = ExecuteMsg::WithdrawImmediate {
         id: 0,
+        amount: None,
     };
 
     let res = execute(deps.as_mut(), mid_time.clone(), user.clone(), withdraw_immediate).unwrap();
@@ -1064,6 +1271,7 @@ fn withdraw_liquidity_unbonding_query_requests_success() {
 
     let withdraw_immediate = ExecuteMsg::WithdrawImmediate {
         id: 1,
+        amount: None,
     };
 
     let res = execute(deps.as_mut(), end_time.clone(), user, withdraw_immediate).unwrap();
@@ -1151,6 +1359,113 @@ fn withdraw_liquidity_unbonding_query_requests_success() {
     assert_eq!(share2, share3);
 }
 
+#[test]
+fn withdraw_liquidity_immediate_partial_success() {
+    let (mut deps, _res) = _unbonding_slow_120();
+
+    let user = mock_info("user001", &[]);
+    let mid_time = _mock_env_at_timestamp(51);
+
+    // half of the pool fee after half of the unbond time
+    let quote = query_withdraw_immediate_quote(
+        deps.as_ref(),
+        mid_time.clone(),
+        "user001".to_string(),
+        0,
+        Some(Uint128::new(20_000000u128)),
+    )
+    .unwrap();
+    assert_eq!(
+        quote,
+        WithdrawImmediateQuoteResponse {
+            withdraw_amount: Uint128::new(20_000000u128),
+            receive_amount: Uint128::new(19_100000u128),
+            withdraw_protocol_fee: Uint128::new(400000u128),
+            withdraw_pool_fee: Uint128::new(500000u128),
+            remaining_amount: Uint128::new(100_000000u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mid_time.clone(),
+        user.clone(),
+        ExecuteMsg::WithdrawImmediate {
+            id: 0,
+            amount: Some(Uint128::new(20_000000u128)),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "arb/execute_withdraw"),
+            attr("from", "cosmos2contract"),
+            attr("receiver", "user001"),
+            attr("withdraw_amount", "20000000"),
+            attr("receive_amount", "19100000"),
+            attr("protocol_fee", "400000"),
+            attr("pool_fee", "500000"),
+            attr("immediate", true.to_string()),
+        ]
+    );
+
+    // the rest keeps unbonding with the same timing and locked balance
+    let unbonding =
+        query_unbond_requests(deps.as_ref(), mid_time.clone(), "user001".to_string(), None, None)
+            .unwrap();
+    assert_eq!(
+        unbonding,
+        UnbondRequestsResponse {
+            requests: vec![UnbondItem {
+                start_time: 1,
+                release_time: 1 + 100,
+                amount_asset: Uint128::new(100_000000u128),
+                id: 0,
+                withdraw_protocol_fee: Uint128::new(2000000),
+                withdraw_pool_fee: Uint128::new(2500000),
+                released: false
+            }]
+        }
+    );
+    deps.querier.set_bank_balance(220_000000u128 - 19_100000u128 - 400000u128);
+    let state = query_state(deps.as_ref(), mid_time.clone(), None).unwrap();
+    assert_eq!(state.balances.locked_user_withdrawls, Uint128::new(100_000000u128));
+
+    let err = execute(
+        deps.as_mut(),
+        mid_time.clone(),
+        user.clone(),
+        ExecuteMsg::WithdrawImmediate {
+            id: 0,
+            amount: Some(Uint128::new(100_000001u128)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WithdrawAmountTooHigh {
+            amount: Uint128::new(100_000001u128),
+            available: Uint128::new(100_000000u128),
+        }
+    );
+
+    // withdrawing the rest removes the item
+    execute(
+        deps.as_mut(),
+        mid_time.clone(),
+        user,
+        ExecuteMsg::WithdrawImmediate {
+            id: 0,
+            amount: Some(Uint128::new(100_000000u128)),
+        },
+    )
+    .unwrap();
+    let unbonding =
+        query_unbond_requests(deps.as_ref(), mid_time, "user001".to_string(), None, None).unwrap();
+    assert_eq!(unbonding.requests, vec![]);
+}
+
 #[test]
 fn withdraw_liquidity_unbonded_all_success() {
     let (mut deps, _res) = _unbonding_slow_120();
@@ -1542,6 +1857,27 @@ fn query_check_balances() {
                     (Decimal::from_ratio(15u128, 1000u128), Uint128::new(48580000),),
                     (Decimal::from_ratio(20u128, 1000u128), Uint128::new(82860000),),
                     (Decimal::from_ratio(25u128, 1000u128), Uint128::new(100000000),),
+                ],
+                lsd_risks: vec![
+                    LsdRisk {
+                        name: "eris".to_string(),
+                        exposure: eris_exchange_rate * withdrawable_per_lsd
+                            + eris_exchange_rate * unbonding_per_lsd,
+                        max_exposure: None,
+                        xfactor: eris_exchange_rate,
+                        xfactor_high: eris_exchange_rate,
+                        oldest_unbonding_end: Some(100),
+                        circuit_breaker: None,
+                    },
+                    LsdRisk {
+                        name: "backbone".to_string(),
+                        exposure: withdrawable_per_lsd + unbonding_per_lsd,
+                        max_exposure: None,
+                        xfactor: Decimal::one(),
+                        xfactor_high: Decimal::one(),
+                        oldest_unbonding_end: Some(100),
+                        circuit_breaker: None,
+                    }
                 ]
             })
         }
@@ -1994,6 +2330,767 @@ fn execute_arb() {
     assert_eq!(takeable, Uint128::zero());
 }
 
+#[test]
+fn eris_dao_adapter() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_withdrawable(Uint128::new(10_000000u128));
+    deps.querier.with_unbonding(Uint128::new(20_000000u128));
+    deps.querier.with_unbonding_eris(Uint128::new(30_000000u128));
+
+    let mut adapter = ErisDao {
+        state_cache: None,
+        undelegation_records_cache: None,
+        wallet: Addr::unchecked(MOCK_CONTRACT_ADDR),
+        addr: Addr::unchecked("eris_dao"),
+        denom: "erisdao".to_string(),
+    };
+
+    let deps = deps.as_ref();
+    assert_eq!(adapter.asset(), native_asset_info("erisdao".into()));
+    assert_eq!(adapter.query_factor_x_to_normal(&deps).unwrap(), Decimal::from_str("1.2").unwrap());
+    // batch 1 is reconciled: 10 * 1.1
+    assert_eq!(adapter.query_withdrawable(&deps).unwrap(), Uint128::new(11_000000u128));
+    // batch 2 is unbonding: 20 * 1.1, pending batch uses the current exchange rate: 30 * 1.2
+    assert_eq!(adapter.query_unbonding(&deps).unwrap(), Uint128::new(58_000000u128));
+
+    assert_eq!(
+        adapter.unbond(&deps, Uint128::new(100)).unwrap(),
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "eris_dao".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::QueueUnbond {
+                receiver: None,
+            })
+            .unwrap(),
+            funds: vec![coin(100, "erisdao")],
+        })]
+    );
+    assert_eq!(
+        adapter.withdraw(&deps, Uint128::new(100)).unwrap(),
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "eris_dao".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::WithdrawUnbonded {
+                receiver: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn swap() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let simulation = query_simulation(
+        deps.as_ref(),
+        mock_env(),
+        native_asset("eriscw".into(), Uint128::new(10_000000u128)),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            // 10 * 1.1
+            offer_value: Uint128::new(11_000000u128),
+            // 11 * (1 - 0.01)
+            return_amount: Uint128::new(10_890000u128),
+            profit_step: Decimal::from_str("0.01").unwrap(),
+            profit_amount: Uint128::new(110000u128),
+        }
+    );
+
+    // bigger swaps need a higher utilization -> 88 * (1 - 0.02)
+    let simulation = query_simulation(
+        deps.as_ref(),
+        mock_env(),
+        native_asset("eriscw".into(), Uint128::new(80_000000u128)),
+    )
+    .unwrap();
+    assert_eq!(simulation.return_amount, Uint128::new(86_240000u128));
+    assert_eq!(simulation.profit_step, Decimal::from_str("0.02").unwrap());
+
+    let err = query_simulation(
+        deps.as_ref(),
+        mock_env(),
+        native_asset("eriscw".into(), Uint128::new(100_000000u128)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsTakeable {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: token_asset_info(Addr::unchecked("eriscw")),
+                amount: Uint128::new(10_000000u128),
+            },
+            min_receive: None,
+            to: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Cw20DirectSwap {});
+
+    // the offered funds are already part of the balance
+    deps.querier.set_bank_balances(&[coin(100_000000, "utoken"), coin(10_000000, "eriscw")]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(10_000000, "eriscw")]),
+        ExecuteMsg::Swap {
+            offer_asset: native_asset("eriscw".into(), Uint128::new(10_000000u128)),
+            min_receive: Some(Uint128::new(10_900000u128)),
+            to: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinReceiveNotReached {
+            return_amount: Uint128::new(10_890000u128),
+            min_receive: Uint128::new(10_900000u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(10_000000, "eriscw")]),
+        ExecuteMsg::Swap {
+            offer_asset: native_asset("eriscw".into(), Uint128::new(10_000000u128)),
+            min_receive: Some(Uint128::new(10_890000u128)),
+            to: Some("receiver".into()),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "receiver".into(),
+                amount: vec![coin(10_890000u128, "utoken")],
+            }),
+            // fee 110000 * 0.01 = 1100 utoken / 1.1 = 999 eriscw (rounded down)
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "fee".into(),
+                amount: vec![coin(999u128, "eriscw")],
+            })
+        ]
+    );
+
+    // (100 + 0.11 - 0.0011) / 100
+    let rates = query_exchange_rates(deps.as_ref(), mock_env(), None, None).unwrap();
+    assert_eq!(rates.exchange_rates[0].1.exchange_rate, Decimal::from_str("1.001089").unwrap());
+}
+
+#[test]
+fn flash_loan() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let flash_loan = |amount: u128| ExecuteMsg::FlashLoan {
+        amount: Uint128::new(amount),
+        msg: to_json_binary("exec_any_swap").unwrap(),
+        recipient: None,
+    };
+
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("user", &[]), flash_loan(101_000000u128))
+            .unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsTakeable {});
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), flash_loan(50_000000u128))
+        .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "user".to_string(),
+                msg: to_json_binary("exec_any_swap").unwrap(),
+                funds: vec![coin(50_000000u128, "utoken")],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                // 50 * 0.001
+                msg: to_json_binary(&ExecuteMsg::Callback(
+                    eris::arb_vault::CallbackMsg::AssertFlashLoanRepaid {
+                        fee_amount: Uint128::new(50000u128),
+                    }
+                ))
+                .unwrap(),
+                funds: vec![],
+            })
+        ]
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), flash_loan(1_000000u128))
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyExecuting {});
+
+    let assert_repaid = ExecuteMsg::Callback(eris::arb_vault::CallbackMsg::AssertFlashLoanRepaid {
+        fee_amount: Uint128::new(50000u128),
+    });
+
+    // only principal repaid
+    deps.querier.set_bank_balances(&[coin(100_000000, "utoken")]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        assert_repaid.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FlashLoanNotRepaid {
+            expected: Uint128::new(100_050000u128),
+            available: Uint128::new(100_000000u128),
+        }
+    );
+
+    deps.querier.set_bank_balances(&[coin(100_050000, "utoken")]);
+    let res = execute(deps.as_mut(), mock_env(), mock_info(MOCK_CONTRACT_ADDR, &[]), assert_repaid)
+        .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![
+            // 50000 * 0.01
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "fee".into(),
+                amount: vec![coin(500u128, "utoken")],
+            })
+        ]
+    );
+
+    // checkpoint removed
+    execute(deps.as_mut(), mock_env(), mock_info("user", &[]), flash_loan(1_000000u128)).unwrap();
+}
+
+#[test]
+fn unbond_schedule() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let set_schedule = |name: &str, min_batch: u128| ExecuteMsg::UpdateConfig {
+        utilization_method: None,
+        unbond_time_s: None,
+        disable_lsd: None,
+        insert_lsd: None,
+        remove_lsd: None,
+        force_remove_lsd: None,
+        fee_config: None,
+        remove_whitelist: None,
+        set_whitelist: None,
+        set_unbond_schedule: Some((
+            name.to_string(),
+            Some(UnbondSchedule {
+                min_batch: Uint128::new(min_batch),
+            }),
+        )),
+        set_risk_limits: None,
+        reset_xfactor_high: None,
+        set_push_update_contracts: None,
+    };
+    let unbond = ExecuteMsg::UnbondFromLiquidStaking {
+        names: None,
+    };
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), set_schedule("x", 0))
+        .unwrap_err();
+    assert_eq!(err, ContractError::AdapterNotFound("x".into()));
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), set_schedule("eris", 10_000000))
+        .unwrap();
+
+    deps.querier.set_bank_balances(&[
+        coin(100_000000, "utoken"),
+        coin(5_000000, "eriscw"),
+        coin(20_000000, "backbonecw"),
+    ]);
+
+    let plan = query_unbond_plan(deps.as_ref(), mock_env(), None).unwrap();
+    assert_eq!(
+        plan.items,
+        vec![
+            UnbondPlanItem {
+                name: "eris".into(),
+                amount: Uint128::new(5_000000),
+                next_unbond_time: 123,
+                reason: UnbondPlanReason::BelowMinBatch,
+            },
+            UnbondPlanItem {
+                name: "backbone".into(),
+                amount: Uint128::new(20_000000),
+                next_unbond_time: 1,
+                reason: UnbondPlanReason::Ready,
+            }
+        ]
+    );
+
+    // only backbone is unbonded
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("whitelisted_exec", &[]), unbond.clone())
+            .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "arb/execute_unbond_liquidity"),
+            attr("type", "backbone"),
+            attr("unbond_amount", "20000000"),
+        ]
+    );
+
+    // with a schedule backbone waits until the pending batch of its hub is due
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), set_schedule("backbone", 0))
+        .unwrap();
+
+    let plan = query_unbond_plan(deps.as_ref(), mock_env(), Some(vec!["backbone".into()])).unwrap();
+    assert_eq!(
+        plan.items,
+        vec![UnbondPlanItem {
+            name: "backbone".into(),
+            amount: Uint128::new(20_000000),
+            next_unbond_time: 123,
+            reason: UnbondPlanReason::WaitingForBatch,
+        }]
+    );
+
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("whitelisted_exec", &[]), unbond.clone())
+            .unwrap_err();
+    assert_eq!(err, ContractError::NothingToUnbond {});
+
+    deps.querier.set_bank_balances(&[
+        coin(100_000000, "utoken"),
+        coin(10_000000, "eriscw"),
+        coin(20_000000, "backbonecw"),
+    ]);
+
+    let plan = query_unbond_plan(deps.as_ref(), _mock_env_at_timestamp(123), None).unwrap();
+    assert_eq!(
+        plan.items.iter().map(|item| item.reason.clone()).collect_vec(),
+        vec![UnbondPlanReason::Ready, UnbondPlanReason::Ready]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        _mock_env_at_timestamp(123),
+        mock_info("whitelisted_exec", &[]),
+        unbond,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+}
+
+#[test]
+fn push_share_updates() {
+    let mut deps = setup_test();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::PushShareUpdates {
+            users: vec!["user".into()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoPushUpdateContracts {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            utilization_method: None,
+            unbond_time_s: None,
+            disable_lsd: None,
+            insert_lsd: None,
+            remove_lsd: None,
+            force_remove_lsd: None,
+            fee_config: None,
+            remove_whitelist: None,
+            set_whitelist: None,
+            set_unbond_schedule: None,
+            set_risk_limits: None,
+            reset_xfactor_high: None,
+            set_push_update_contracts: Some(vec!["farm".into()]),
+        },
+    )
+    .unwrap();
+    // the vault registers itself as before-send hook of the LP denom
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![chain_test()
+            .create_set_before_send_hook_msg(TEST_LP_TOKEN.into(), MOCK_CONTRACT_ADDR.into())
+            .unwrap()]
+    );
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(config.push_update_contracts, vec![Addr::unchecked("farm")]);
+
+    let push_update = |balance: u128, total_supply: u128| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "farm".into(),
+            msg: to_json_binary(&PushExecuteMsg::UpdateShare {
+                user: "user".into(),
+                balance: Uint128::new(balance),
+                total_supply: Uint128::new(total_supply),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+
+    // user already holds 10 shares, minted shares are added
+    deps.querier.set_bank_balance(100_000000);
+    deps.querier.set_cw20_balance(TEST_LP_TOKEN, "user", 10_000000u128);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(100_000000, "utoken")]),
+        ExecuteMsg::Deposit {
+            asset: native_asset("utoken".to_string(), Uint128::new(100_000000)),
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.last().unwrap().msg, push_update(110_000000, 100_000000));
+
+    // unbonded shares are already sent to the vault
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(40_000000, TEST_LP_TOKEN)]),
+        ExecuteMsg::Unbond {
+            immediate: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.last().unwrap().msg, push_update(10_000000, 60_000000));
+
+    // e.g. after a transfer
+    deps.querier.set_cw20_balance(TEST_LP_TOKEN, "user", 5_000000u128);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::PushShareUpdates {
+            users: vec!["user".into()],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![push_update(5_000000, 60_000000)]
+    );
+
+    // transfers are pushed by the before-send hook
+    deps.querier.set_cw20_balance(TEST_LP_TOKEN, "user2", 1_000000u128);
+    let transfer = |from: &str, to: &str, amount: u128| SudoMsg::TrackBeforeSend {
+        from: from.into(),
+        to: to.into(),
+        amount: coin(amount, TEST_LP_TOKEN),
+    };
+    let res = sudo(deps.as_mut(), mock_env(), transfer("user", "user2", 2_000000)).unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        vec![
+            push_update(3_000000, 60_000000),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "farm".into(),
+                msg: to_json_binary(&PushExecuteMsg::UpdateShare {
+                    user: "user2".into(),
+                    balance: Uint128::new(3_000000),
+                    total_supply: Uint128::new(60_000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        ]
+    );
+
+    // the vault pushes its own transfers
+    let res =
+        sudo(deps.as_mut(), mock_env(), transfer("user", MOCK_CONTRACT_ADDR, 2_000000)).unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let err = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::TrackBeforeSend {
+            from: "user".into(),
+            to: "user2".into(),
+            amount: coin(1, "utoken"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ExpectingLPToken("1utoken".into()));
+}
+
+#[test]
+fn crystallize_fees() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let crystallize = ExecuteMsg::CrystallizeFees {};
+    let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), crystallize.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::FeesNotCrystallized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            utilization_method: None,
+            unbond_time_s: None,
+            disable_lsd: None,
+            insert_lsd: None,
+            remove_lsd: None,
+            force_remove_lsd: None,
+            fee_config: Some(FeeConfig {
+                protocol_fee_contract: "fee".into(),
+                protocol_performance_fee: Decimal::from_str("0.1").unwrap(),
+                protocol_withdraw_fee: Decimal::from_str("0.02").unwrap(),
+                immediate_withdraw_fee: Decimal::from_str("0.05").unwrap(),
+                flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+                performance_fee_mode: eris::arb_vault::PerformanceFeeMode::Crystallized,
+            }),
+            remove_whitelist: None,
+            set_whitelist: None,
+            set_unbond_schedule: None,
+            set_risk_limits: None,
+            reset_xfactor_high: None,
+            set_push_update_contracts: None,
+        },
+    )
+    .unwrap();
+
+    // high-water mark set to the exchange rate when switching
+    let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), crystallize.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::NothingToCrystallize {});
+
+    // vault made 10 utoken profit
+    deps.querier.set_bank_balance(110_000000);
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), crystallize.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "arb/execute_crystallize_fees"),
+            attr("old_high_water_mark", "1"),
+            attr("exchange_rate", "1.1"),
+            // 10 * 0.1
+            attr("fee_amount", "1000000"),
+            // 1 * 100 / 109
+            attr("fee_shares", "917431"),
+            attr("high_water_mark", "1.090000002080909094"),
+        ]
+    );
+    assert_eq!(
+        res.messages.into_iter().map(|a| a.msg).collect_vec(),
+        chain_test().create_mint_msgs(
+            TEST_LP_TOKEN.into(),
+            Uint128::new(917431),
+            Addr::unchecked("fee")
+        )
+    );
+
+    let state = query_state(deps.as_ref(), mock_env(), None).unwrap();
+    assert_eq!(state.total_lp_supply, Uint128::new(100_917431));
+    assert_eq!(state.exchange_rate, Decimal::from_str("1.090000002080909094").unwrap());
+
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), crystallize).unwrap_err();
+    assert_eq!(err, ContractError::NothingToCrystallize {});
+}
+
+#[test]
+fn risk_limits() {
+    let (mut deps, _res) = _provide_liquidity();
+
+    let set_limits = |risk_limits: RiskLimits| ExecuteMsg::UpdateConfig {
+        utilization_method: None,
+        unbond_time_s: None,
+        disable_lsd: None,
+        insert_lsd: None,
+        remove_lsd: None,
+        force_remove_lsd: None,
+        fee_config: None,
+        remove_whitelist: None,
+        set_whitelist: None,
+        set_unbond_schedule: None,
+        set_risk_limits: Some(("eris".into(), Some(risk_limits))),
+        reset_xfactor_high: None,
+        set_push_update_contracts: None,
+    };
+    let arbitrage = |amount: u128| ExecuteMsg::ExecuteArbitrage {
+        msg: ExecuteSubMsg {
+            contract_addr: None,
+            funds_amount: Uint128::new(amount),
+            msg: to_json_binary("exec_any_swap").unwrap(),
+        },
+        result_token: native_asset_info("eriscw".into()),
+        wanted_profit: Decimal::from_str("0.01").unwrap(),
+    };
+    let whitelist_info = mock_info("whitelisted_exec", &[]);
+    let owner_info = mock_info("owner", &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        set_limits(RiskLimits {
+            max_exposure_bps: Some(10001),
+            ..RiskLimits::default()
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Std(StdError::generic_err("Risk limit bps too high")));
+
+    // the unbonding batch of eris ends at 100
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        set_limits(RiskLimits {
+            max_unbonding_delay_s: Some(10),
+            ..RiskLimits::default()
+        }),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        _mock_env_at_timestamp(110),
+        whitelist_info.clone(),
+        arbitrage(1_000000),
+    )
+    .unwrap();
+    State::default().balance_checkpoint.remove(deps.as_mut().storage);
+
+    let err = execute(
+        deps.as_mut(),
+        _mock_env_at_timestamp(111),
+        whitelist_info.clone(),
+        arbitrage(1_000000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CircuitBreakerTriggered("eris".into(), "UnbondingStalled".into())
+    );
+
+    // exchange rate dropped from 1.2 to 1.1
+    State::default()
+        .xfactor_high
+        .save(deps.as_mut().storage, "eris".into(), &Decimal::from_str("1.2").unwrap())
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        set_limits(RiskLimits {
+            max_xfactor_drop_bps: Some(500),
+            ..RiskLimits::default()
+        }),
+    )
+    .unwrap();
+
+    let state = query_state(deps.as_ref(), mock_env(), Some(true)).unwrap();
+    assert_eq!(
+        state.details.unwrap().lsd_risks[0],
+        LsdRisk {
+            name: "eris".into(),
+            exposure: Uint128::zero(),
+            max_exposure: None,
+            xfactor: Decimal::from_str("1.1").unwrap(),
+            xfactor_high: Decimal::from_str("1.2").unwrap(),
+            oldest_unbonding_end: Some(100),
+            circuit_breaker: Some(CircuitBreaker::Depeg),
+        }
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), whitelist_info.clone(), arbitrage(1_000000))
+        .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTriggered("eris".into(), "Depeg".into()));
+
+    // swaps into the LSD are halted as well
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(1_000000, "eriscw")]),
+        ExecuteMsg::Swap {
+            offer_asset: native_asset("eriscw".into(), Uint128::new(1_000000u128)),
+            min_receive: None,
+            to: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTriggered("eris".into(), "Depeg".into()));
+
+    let err = query_simulation(
+        deps.as_ref(),
+        mock_env(),
+        native_asset("eriscw".into(), Uint128::new(1_000000u128)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTriggered("eris".into(), "Depeg".into()));
+
+    let reset_xfactor_high = |name: &str| ExecuteMsg::UpdateConfig {
+        utilization_method: None,
+        unbond_time_s: None,
+        disable_lsd: None,
+        insert_lsd: None,
+        remove_lsd: None,
+        force_remove_lsd: None,
+        fee_config: None,
+        remove_whitelist: None,
+        set_whitelist: None,
+        set_unbond_schedule: None,
+        set_risk_limits: None,
+        reset_xfactor_high: Some(name.into()),
+        set_push_update_contracts: None,
+    };
+
+    let err =
+        execute(deps.as_mut(), mock_env(), whitelist_info.clone(), reset_xfactor_high("eris"))
+            .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), mock_env(), owner_info.clone(), reset_xfactor_high("unknown"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::AdapterNotFound("unknown".into()));
+
+    // the owner accepts the lower exchange rate
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), reset_xfactor_high("eris")).unwrap();
+
+    let state = query_state(deps.as_ref(), mock_env(), Some(true)).unwrap();
+    let risk = state.details.unwrap().lsd_risks[0].clone();
+    assert_eq!(risk.xfactor_high, Decimal::from_str("1.1").unwrap());
+    assert_eq!(risk.circuit_breaker, None);
+
+    // 10% of the tvl
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        set_limits(RiskLimits {
+            max_exposure_bps: Some(1000),
+            ..RiskLimits::default()
+        }),
+    )
+    .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), whitelist_info.clone(), arbitrage(10_000001))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ExposureLimitReached("eris".into()));
+
+    execute(deps.as_mut(), mock_env(), whitelist_info, arbitrage(10_000000)).unwrap();
+}
+
 fn calc_takeable(total_value: Uint128, pool_takeable: Uint128, share: &str) -> Uint128 {
     // total value * share = total pool that can be used for that share
     // + takeable - total value
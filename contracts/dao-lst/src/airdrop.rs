@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use astroport::asset::{native_asset_info, Asset, AssetInfo, AssetInfoExt};
+use cosmwasm_std::{
+    attr, Addr, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+use eris::adapters::asset::AssetEx;
+use eris::adapters::ustake_snapshot::UstakeSnapshot;
+use eris::hub::Airdrop;
+use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+
+use crate::error::{ContractError, ContractResult};
+use crate::state::State;
+
+/// Locks the current balance of the provided assets for a new airdrop to ustake holders
+pub fn create_airdrop(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    snapshot_height: u64,
+    merkle_root: String,
+    total_ustake: Uint128,
+    assets: Vec<AssetInfo>,
+) -> ContractResult {
+    let state = State::default();
+    state.assert_owner(deps.storage, &info.sender)?;
+
+    if total_ustake.is_zero() {
+        return Err(ContractError::CantBeZero("total_ustake".into()));
+    }
+    if assets.is_empty() {
+        return Err(ContractError::CantBeZero("assets".into()));
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    let ustake_snapshot = assert_snapshot_total(&deps, &env, snapshot_height, total_ustake)?;
+
+    let stake = state.stake_token.load(deps.storage)?;
+    let stake_denom = native_asset_info(stake.denom.clone());
+
+    let mut locked_assets: Vec<Asset> = vec![];
+    for token in assets.into_iter() {
+        if stake.utoken == token || stake_denom == token {
+            return Err(ContractError::SwapFromNotAllowed(token.to_string()));
+        }
+        if locked_assets.iter().any(|asset| asset.info == token) {
+            return Err(ContractError::DuplicateAsset(token.to_string()));
+        }
+
+        let balance = token.query_pool(&deps.querier, env.contract.address.clone())?;
+        let available = balance.saturating_sub(locked_amount(deps.storage, &token)?);
+        if available.is_zero() {
+            return Err(ContractError::NoTokensAvailable(token.to_string()));
+        }
+
+        state.airdrop_locked.update(deps.storage, token.to_string(), |locked| -> StdResult<_> {
+            Ok(locked.unwrap_or_default().checked_add(available)?)
+        })?;
+        locked_assets.push(token.with_balance(available));
+    }
+
+    let id = state.airdrop_id.may_load(deps.storage)?.unwrap_or_default() + 1;
+    state.airdrop_id.save(deps.storage, &id)?;
+
+    let airdrop = Airdrop {
+        id,
+        snapshot_height,
+        merkle_root,
+        total_ustake,
+        claimed: locked_assets.iter().map(|asset| asset.info.with_balance(0u128)).collect(),
+        assets: locked_assets,
+        ustake_snapshot: ustake_snapshot.map(|snapshot| snapshot.0),
+    };
+    state.airdrops.save(deps.storage, id, &airdrop)?;
+
+    Ok(Response::new().add_attribute("action", "erishub/create_airdrop").add_attributes(vec![
+        attr("id", id.to_string()),
+        attr("snapshot_height", snapshot_height.to_string()),
+        attr("assets", airdrop.assets.iter().join(",")),
+    ]))
+}
+
+/// Pays out the sender's pro-rata share of the airdrop based on its ustake in the snapshot
+pub fn claim_airdrop(
+    deps: DepsMut<CustomQueryType>,
+    info: MessageInfo,
+    id: u64,
+    ustake: Uint128,
+    proof: Vec<String>,
+) -> ContractResult {
+    let state = State::default();
+    let mut airdrop = state.airdrops.load(deps.storage, id)?;
+
+    if state.airdrop_claims.has(deps.storage, (id, &info.sender)) {
+        return Err(ContractError::AirdropAlreadyClaimed(id));
+    }
+
+    verify_proof(&airdrop.merkle_root, &info.sender, ustake, proof)?;
+    state.airdrop_claims.save(deps.storage, (id, &info.sender), &Empty {})?;
+
+    let ustake = capped_by_snapshot(
+        &deps,
+        airdrop.ustake_snapshot.clone(),
+        &info.sender,
+        airdrop.snapshot_height,
+        ustake,
+    )?;
+
+    let mut msgs: Vec<CosmosMsg<CustomMsgType>> = vec![];
+    let mut received = vec![];
+    for (asset, claimed) in airdrop.assets.iter().zip(airdrop.claimed.iter_mut()) {
+        // rounding can't pay out more than was locked for the airdrop
+        let share = asset
+            .amount
+            .multiply_ratio(ustake, airdrop.total_ustake)
+            .min(asset.amount.checked_sub(claimed.amount)?);
+
+        if share.is_zero() {
+            continue;
+        }
+
+        claimed.amount = claimed.amount.checked_add(share)?;
+        unlock(deps.storage, &asset.info, share)?;
+
+        let share = asset.info.with_balance(share);
+        msgs.push(share.transfer_msg(&info.sender)?);
+        received.push(share);
+    }
+
+    state.airdrops.save(deps.storage, id, &airdrop)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "erishub/claim_airdrop")
+        .add_attributes(vec![
+            attr("id", id.to_string()),
+            attr("user", info.sender),
+            attr("ustake", ustake),
+            attr("received", received.iter().join(",")),
+        ]))
+}
+
+/// Removes the airdrop and releases the unclaimed assets back to the contract
+pub fn close_airdrop(deps: DepsMut<CustomQueryType>, info: MessageInfo, id: u64) -> ContractResult {
+    let state = State::default();
+    state.assert_owner(deps.storage, &info.sender)?;
+
+    let airdrop = state.airdrops.load(deps.storage, id)?;
+    for (asset, claimed) in airdrop.assets.iter().zip(airdrop.claimed.iter()) {
+        unlock(deps.storage, &asset.info, asset.amount.checked_sub(claimed.amount)?)?;
+    }
+    state.airdrops.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "erishub/close_airdrop")
+        .add_attribute("id", id.to_string()))
+}
+
+/// Amount of the contract's balance that is locked for airdrops
+pub fn locked_amount(storage: &dyn Storage, token: &AssetInfo) -> StdResult<Uint128> {
+    Ok(State::default().airdrop_locked.may_load(storage, token.to_string())?.unwrap_or_default())
+}
+
+/// Removes the amounts locked for airdrops from the queried balances, so they are not swapped
+pub fn remove_locked_balances(
+    storage: &dyn Storage,
+    balances: &mut HashMap<String, Uint128>,
+) -> StdResult<()> {
+    let state = State::default();
+    for (key, balance) in balances.iter_mut() {
+        if let Some(locked) = state.airdrop_locked.may_load(storage, key.clone())? {
+            *balance = balance.saturating_sub(locked);
+        }
+    }
+    Ok(())
+}
+
+fn unlock(storage: &mut dyn Storage, token: &AssetInfo, amount: Uint128) -> StdResult<()> {
+    let state = State::default();
+    let locked = locked_amount(storage, token)?.checked_sub(amount)?;
+    if locked.is_zero() {
+        state.airdrop_locked.remove(storage, token.to_string());
+    } else {
+        state.airdrop_locked.save(storage, token.to_string(), &locked)?;
+    }
+    Ok(())
+}
+
+/// Checks the total ustake of a merkle snapshot against the ustake snapshot contract, if one is
+/// configured
+pub(crate) fn assert_snapshot_total(
+    deps: &DepsMut<CustomQueryType>,
+    env: &Env,
+    snapshot_height: u64,
+    total_ustake: Uint128,
+) -> Result<Option<UstakeSnapshot>, ContractError> {
+    let ustake_snapshot = match State::default().ustake_snapshot.may_load(deps.storage)? {
+        Some(addr) => UstakeSnapshot(addr),
+        None => return Ok(None),
+    };
+
+    if snapshot_height > env.block.height {
+        return Err(ContractError::SnapshotHeightInFuture(snapshot_height));
+    }
+
+    let supply = ustake_snapshot.query_total_supply_at(&deps.querier, snapshot_height)?;
+    if supply != total_ustake {
+        return Err(ContractError::UnexpectedTotalUstake(supply, total_ustake));
+    }
+
+    Ok(Some(ustake_snapshot))
+}
+
+/// Caps the ustake proven by a merkle proof by the balance in the ustake snapshot contract
+pub(crate) fn capped_by_snapshot(
+    deps: &DepsMut<CustomQueryType>,
+    ustake_snapshot: Option<Addr>,
+    address: &Addr,
+    snapshot_height: u64,
+    ustake: Uint128,
+) -> StdResult<Uint128> {
+    Ok(match ustake_snapshot {
+        Some(addr) => ustake.min(UstakeSnapshot(addr).query_balance_at(
+            &deps.querier,
+            address,
+            snapshot_height,
+        )?),
+        None => ustake,
+    })
+}
+
+/// Verifies a proof of a `{address}{ustake}` leaf against the hex encoded sha256 merkle root
+pub(crate) fn verify_proof(
+    merkle_root: &str,
+    address: &Addr,
+    ustake: Uint128,
+    proof: Vec<String>,
+) -> Result<(), ContractError> {
+    let leaf = format!("{}{}", address, ustake);
+    let hash: [u8; 32] = Sha256::digest(leaf.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| ContractError::InvalidMerkleProof {})?;
+
+    let hash = proof.into_iter().try_fold(hash, |hash, p| -> Result<[u8; 32], ContractError> {
+        let mut proof_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        Sha256::digest(hashes.concat())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::InvalidMerkleProof {})
+    })?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    if root_buf != hash {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use astroport::asset::{AssetInfo, AssetInfoExt};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, WasmMsg,
 };
 use eris::{adapters::asset::AssetEx, hub::ClaimType};
 use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
 
 use crate::{
+    airdrop::locked_amount,
     error::{ContractError, ContractResult},
     state::State,
 };
@@ -26,6 +27,25 @@ impl ClaimExecuteMsg {
     }
 }
 
+#[cw_serde]
+pub enum ClaimExecuteMerkleMsg {
+    Claim {
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
+}
+
+impl ClaimExecuteMerkleMsg {
+    pub fn into_msg(&self, contract_addr: String) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_json_binary(&self)?,
+            funds: vec![],
+        }))
+    }
+}
+
 #[cw_serde]
 pub enum ClaimExecuteGenieMsg {
     Claim {
@@ -88,11 +108,28 @@ pub fn exec_claim(
                         return Err(ContractError::SwapFromNotAllowed(token.to_string()));
                     }
 
-                    let balance = token.query_pool(&deps.querier, env.contract.address.clone())?;
+                    // assets locked for airdrops to holders can't be transferred
+                    let balance = token
+                        .query_pool(&deps.querier, env.contract.address.clone())?
+                        .saturating_sub(locked_amount(deps.storage, &token)?);
                     let recipient = deps.api.addr_validate(&recipient)?;
 
                     token.with_balance(balance).transfer_msg(&recipient)?
                 },
+                ClaimType::Merkle {
+                    contract,
+                    stage,
+                    amount,
+                    proof,
+                } => {
+                    deps.api.addr_validate(&contract)?;
+                    ClaimExecuteMerkleMsg::Claim {
+                        stage,
+                        amount,
+                        proof,
+                    }
+                    .into_msg(contract)?
+                },
             })
         })
         .collect::<Result<Vec<CosmosMsg<CustomMsgType>>, ContractError>>()?;
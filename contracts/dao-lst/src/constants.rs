@@ -3,6 +3,9 @@ use cosmwasm_std::Decimal;
 pub const CONTRACT_NAME: &str = "eris-staking-hub-tokenfactory";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DAY: u64 = 24 * 60 * 60;
+/// ustake minted for an NFT while none are bonded, so that the shares of a single NFT keep their
+/// precision when harvested rewards are burned
+pub const NFT_SHARE_PRECISION: u128 = 1_000_000;
 
 pub fn get_reward_fee_cap() -> Decimal {
     // 10% max reward fee
@@ -6,6 +6,7 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 
 use cw20::Cw20ReceiveMsg;
+use eris::adapters::nft::Cw721ReceiveMsg;
 use eris::helper::validate_received_funds;
 use eris::hub::{CallbackMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg};
 use eris_chain_adapter::types::CustomQueryType;
@@ -14,7 +15,7 @@ use crate::claim::exec_claim;
 use crate::constants::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::error::{ContractError, ContractResult};
 use crate::state::State;
-use crate::{execute, gov, queries};
+use crate::{airdrop, execute, gov, queries};
 
 #[entry_point]
 pub fn instantiate(
@@ -36,6 +37,7 @@ pub fn execute(
     let api = deps.api;
     match msg {
         ExecuteMsg::Receive(cw20_msg) => receive(deps, env, info, cw20_msg),
+        ExecuteMsg::ReceiveNft(nft_msg) => receive_nft(deps, env, info, nft_msg),
         ExecuteMsg::Bond {
             receiver,
             donate,
@@ -89,6 +91,33 @@ pub fn execute(
             proposal_id,
             vote,
         } => gov::vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::VoteMultiple {
+            proposal_id,
+            option_id,
+        } => gov::vote_multiple(deps, env, info, proposal_id, option_id),
+        ExecuteMsg::OpenHolderVote {
+            proposal_id,
+            snapshot_height,
+            merkle_root,
+            end_time,
+        } => gov::open_holder_vote(
+            deps,
+            env,
+            info,
+            proposal_id,
+            snapshot_height,
+            merkle_root,
+            end_time,
+        ),
+        ExecuteMsg::HolderVote {
+            proposal_id,
+            ustake,
+            proof,
+            votes,
+        } => gov::holder_vote(deps, env, info, proposal_id, ustake, proof, votes),
+        ExecuteMsg::ForwardHolderVote {
+            proposal_id,
+        } => gov::forward_holder_vote(deps, env, info, proposal_id),
 
         ExecuteMsg::Callback(callback_msg) => callback(deps, env, info, callback_msg),
 
@@ -105,6 +134,9 @@ pub fn execute(
             epoch_period,
             unbond_period,
             dao_interface,
+            reward_sources,
+            reward_vesting_period,
+            ustake_snapshot,
         } => execute::update_config(
             env,
             deps,
@@ -120,6 +152,9 @@ pub fn execute(
             epoch_period,
             unbond_period,
             dao_interface,
+            reward_sources,
+            reward_vesting_period,
+            ustake_snapshot,
         ),
         ExecuteMsg::QueueUnbond {
             receiver,
@@ -146,6 +181,28 @@ pub fn execute(
         ExecuteMsg::Claim {
             claims,
         } => exec_claim(deps, env, info, claims),
+        ExecuteMsg::CreateAirdrop {
+            snapshot_height,
+            merkle_root,
+            total_ustake,
+            assets,
+        } => airdrop::create_airdrop(
+            deps,
+            env,
+            info,
+            snapshot_height,
+            merkle_root,
+            total_ustake,
+            assets,
+        ),
+        ExecuteMsg::ClaimAirdrop {
+            id,
+            ustake,
+            proof,
+        } => airdrop::claim_airdrop(deps, info, id, ustake, proof),
+        ExecuteMsg::CloseAirdrop {
+            id,
+        } => airdrop::close_airdrop(deps, info, id),
     }
 }
 
@@ -176,6 +233,43 @@ fn receive(deps: DepsMut, env: Env, info: MessageInfo, cw20_msg: Cw20ReceiveMsg)
     }
 }
 
+fn receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nft_msg: Cw721ReceiveMsg,
+) -> ContractResult {
+    let api = deps.api;
+    match from_json(&nft_msg.msg)? {
+        ReceiveMsg::Bond {
+            receiver,
+            donate,
+        } => {
+            let state = State::default();
+            let stake_token = state.stake_token.load(deps.storage)?;
+
+            if !stake_token.dao_interface.is_nft()
+                || token_asset_info(info.sender.clone()) != stake_token.utoken
+            {
+                return Err(ContractError::ExpectingStakeToken(info.sender.into()));
+            }
+
+            if donate.unwrap_or(false) {
+                return Err(ContractError::NotSupportedForNft("donating".into()));
+            }
+
+            execute::bond_nft(
+                deps,
+                env,
+                state,
+                stake_token,
+                nft_msg.token_id,
+                api.addr_validate(&receiver.unwrap_or(nft_msg.sender))?,
+            )
+        },
+    }
+}
+
 fn callback(
     deps: DepsMut<CustomQueryType>,
     env: Env,
@@ -245,6 +339,38 @@ pub fn query(deps: Deps<CustomQueryType>, env: Env, msg: QueryMsg) -> StdResult<
             start_after,
             limit,
         } => to_json_binary(&queries::query_exchange_rates(deps, env, start_after, limit)?),
+        QueryMsg::Health {} => to_json_binary(&queries::health(deps, env)?),
+        QueryMsg::BatchTokenIds(id) => to_json_binary(&queries::batch_token_ids(deps, id)?),
+        QueryMsg::RewardVesting {} => to_json_binary(&queries::reward_vesting(deps, env)?),
+        QueryMsg::Vote {
+            proposal_id,
+            multiple,
+        } => to_json_binary(&queries::vote(deps, proposal_id, multiple.unwrap_or_default())?),
+        QueryMsg::Votes {
+            start_after,
+            limit,
+            multiple,
+        } => {
+            to_json_binary(&queries::votes(deps, start_after, limit, multiple.unwrap_or_default())?)
+        },
+        QueryMsg::HolderProposal {
+            proposal_id,
+        } => to_json_binary(&queries::holder_proposal(deps, proposal_id)?),
+        QueryMsg::HolderVote {
+            proposal_id,
+            voter,
+        } => to_json_binary(&queries::holder_vote(deps, proposal_id, voter)?),
+        QueryMsg::Airdrop {
+            id,
+        } => to_json_binary(&queries::airdrop(deps, id)?),
+        QueryMsg::Airdrops {
+            start_after,
+            limit,
+        } => to_json_binary(&queries::airdrops(deps, start_after, limit)?),
+        QueryMsg::AirdropClaimed {
+            id,
+            address,
+        } => to_json_binary(&queries::airdrop_claimed(deps, id, address)?),
     }
 }
 
@@ -271,12 +397,14 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> ContractResult {
                 attrs.push(attr("disabled", "true"));
             },
             eris::hub::MigrateAction::Unstake => {
-                let unstake_msg = stake_token
-                    .dao_interface
-                    .unbond_msg(&stake_token.utoken, stake_token.total_utoken_bonded)?;
+                // rewards that are still vesting are staked as well
+                let vesting = state.reward_vesting.may_load(deps.storage)?.unwrap_or_default();
+                let total_staked = stake_token.total_utoken_bonded.checked_add(vesting.pending)?;
+                let unstake_msg =
+                    stake_token.dao_interface.unbond_msg(&stake_token.utoken, total_staked)?;
                 msgs.push(unstake_msg);
                 attrs.push(attr("action", "unstake"));
-                attrs.push(attr("unstake", stake_token.total_utoken_bonded));
+                attrs.push(attr("unstake", total_staked));
             },
             eris::hub::MigrateAction::Claim => {
                 let claim_msg = stake_token.dao_interface.claim_unbonded_msg()?;
@@ -73,6 +73,9 @@ pub enum ContractError {
     #[error("No vote operator set")]
     NoVoteOperatorSet {},
 
+    #[error("Vote weights must not be empty and need to sum up to 1")]
+    InvalidVoteWeights {},
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 
@@ -87,4 +90,49 @@ pub enum ContractError {
 
     #[error("Action disabled for maintenance")]
     DisabledMaintenance {},
+
+    #[error("{0} is not supported when staking nfts")]
+    NotSupportedForNft(String),
+
+    #[error("Switching between staking nfts and tokens is not allowed")]
+    NftModeChangeNotAllowed {},
+
+    #[error("{0}")]
+    Hex(#[from] hex::FromHexError),
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Airdrop {0} already claimed")]
+    AirdropAlreadyClaimed(u64),
+
+    #[error("Asset {0} provided multiple times")]
+    DuplicateAsset(String),
+
+    #[error("Snapshot height {0} is in the future")]
+    SnapshotHeightInFuture(u64),
+
+    #[error("An ustake snapshot contract has to be configured")]
+    UstakeSnapshotRequired {},
+
+    #[error("Expected total_ustake to be {0} as in the ustake snapshot, but it is {1}")]
+    UnexpectedTotalUstake(Uint128, Uint128),
+
+    #[error("Proposal {0} is open for holder votes")]
+    HolderVoteOpen(u64),
+
+    #[error("Holder voting on proposal {0} has ended")]
+    HolderVoteEnded(u64),
+
+    #[error("Holder voting on proposal {0} has to end before the proposal ends at {1}")]
+    HolderVoteEndsAfterProposal(u64, u64),
+
+    #[error("Holder voting on proposal {0} has not ended yet")]
+    HolderVoteNotEnded(u64),
+
+    #[error("Holder votes on proposal {0} were already forwarded")]
+    HolderVoteForwarded(u64),
+
+    #[error("No holder votes on proposal {0}")]
+    NoHolderVotes(u64),
 }
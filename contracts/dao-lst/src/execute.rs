@@ -1,27 +1,30 @@
 use std::cmp;
 
-use astroport::asset::{native_asset, native_asset_info, Asset, AssetInfoExt};
+use astroport::asset::{native_asset, native_asset_info, Asset, AssetInfo, AssetInfoExt};
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Attribute, CosmosMsg, Decimal, DepsMut, Env, Event, Order,
-    Response, StdResult, Uint128, WasmMsg,
+    attr, to_json_binary, Addr, Attribute, CosmosMsg, Decimal, DepsMut, Empty, Env, Event, Order,
+    Response, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use eris::adapters::asset::AssetEx;
+use eris::adapters::nft::Nft;
+use eris::ustake_snapshot::ExecuteMsg as SnapshotExecuteMsg;
 use eris::{CustomEvent, CustomResponse, DecimalCheckedOps};
 
 use eris::hub::{
     Batch, CallbackMsg, DaoInterface, ExecuteMsg, FeeConfig, InstantiateMsg, MultiSwapRouter,
-    PendingBatch, SingleSwapConfig, StakeToken, UnbondRequest,
+    PendingBatch, RewardSource, RewardVesting, SingleSwapConfig, StakeToken, UnbondRequest,
 };
 use eris_chain_adapter::types::{
     chain, get_balances_hashmap, CoinType, CustomMsgType, CustomQueryType, DenomType, WithdrawType,
 };
 use itertools::Itertools;
 
-use crate::constants::get_reward_fee_cap;
+use crate::airdrop::remove_locked_balances;
+use crate::constants::{get_reward_fee_cap, NFT_SHARE_PRECISION};
 use crate::error::{ContractError, ContractResult};
 
-use crate::math::{compute_mint_amount, compute_unbond_amount};
+use crate::math::{compute_mint_amount, compute_unbond_amount, release_vested_amount};
 use crate::state::State;
 use crate::types::Assets;
 
@@ -88,18 +91,20 @@ pub fn instantiate(
     let sub_denom = msg.denom;
     let full_denom = chain.get_token_denom(env.contract.address, sub_denom.clone());
 
+    let stake = StakeToken {
+        dao_interface: msg.dao_interface.validate(deps.api)?,
+        utoken: msg.utoken,
+        denom: full_denom.clone(),
+        total_utoken_bonded: Uint128::zero(),
+        total_supply: Uint128::zero(),
+        disabled: false,
+    };
+    if stake.dao_interface.is_nft() {
+        nft_collection(&stake)?;
+    }
+
     state.unlocked_coins.save(deps.storage, &vec![])?;
-    state.stake_token.save(
-        deps.storage,
-        &StakeToken {
-            dao_interface: msg.dao_interface.validate(deps.api)?,
-            utoken: msg.utoken,
-            denom: full_denom.clone(),
-            total_utoken_bonded: Uint128::zero(),
-            total_supply: Uint128::zero(),
-            disabled: false,
-        },
-    )?;
+    state.stake_token.save(deps.storage, &stake)?;
 
     Ok(Response::new().add_message(chain.create_denom_msg(full_denom, sub_denom)))
 }
@@ -127,6 +132,7 @@ pub fn bond(
     donate: bool,
 ) -> ContractResult {
     assert_not_disabled(&stake)?;
+    release_vested_rewards(deps.storage, &state, &mut stake, env.block.time.seconds())?;
     // Query the current supply of Staking Token and compute the amount to mint
     let ustake_supply = stake.total_supply;
     let ustake_to_mint = if donate {
@@ -152,7 +158,7 @@ pub fn bond(
         // create mint message and add to stored total supply
         stake.total_supply = stake.total_supply.checked_add(ustake_to_mint)?;
 
-        Some(chain(&env).create_mint_msgs(stake.denom.clone(), ustake_to_mint, receiver))
+        Some(create_mint_msgs(deps.storage, &env, stake.denom.clone(), ustake_to_mint, receiver)?)
     };
     stake.total_utoken_bonded = stake.total_utoken_bonded.checked_add(token_to_bond)?;
     state.stake_token.save(deps.storage, &stake)?;
@@ -168,6 +174,126 @@ pub fn bond(
         .add_attribute("action", "erishub/bond"))
 }
 
+/// Bonds a single NFT of the collection, which counts as one utoken. The first NFT mints
+/// `NFT_SHARE_PRECISION` ustake, later ones mint based on the current ratio, which increases
+/// whenever harvested rewards are burned.
+pub fn bond_nft(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    state: State,
+    mut stake: StakeToken,
+    token_id: String,
+    receiver: Addr,
+) -> ContractResult {
+    assert_not_disabled(&stake)?;
+    release_vested_rewards(deps.storage, &state, &mut stake, env.block.time.seconds())?;
+    let collection = nft_collection(&stake)?;
+
+    let token_to_bond = Uint128::one();
+    let ustake_to_mint = compute_mint_amount(
+        stake.total_supply,
+        token_to_bond.checked_mul(Uint128::new(NFT_SHARE_PRECISION))?,
+        stake.total_utoken_bonded.checked_mul(Uint128::new(NFT_SHARE_PRECISION))?,
+    );
+    if ustake_to_mint.is_zero() {
+        return Err(ContractError::CantBeZero("ustake to mint".into()));
+    }
+
+    let event = Event::new("erishub/bonded")
+        .add_attribute("receiver", receiver.clone())
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("token_bonded", token_to_bond)
+        .add_attribute("ustake_minted", ustake_to_mint);
+
+    stake.total_supply = stake.total_supply.checked_add(ustake_to_mint)?;
+    stake.total_utoken_bonded = stake.total_utoken_bonded.checked_add(token_to_bond)?;
+    state.stake_token.save(deps.storage, &stake)?;
+    state.staked_nfts.save(deps.storage, &token_id, &Empty {})?;
+
+    Ok(Response::new()
+        .add_message(stake.dao_interface.deposit_nft_msg(&collection.0, token_id)?)
+        .add_messages(create_mint_msgs(
+            deps.storage,
+            &env,
+            stake.denom.clone(),
+            ustake_to_mint,
+            receiver,
+        )?)
+        .add_event(event)
+        .add_attribute("action", "erishub/bond"))
+}
+
+/// Mints ustake and reports the mint to the ustake snapshot contract, if one is set.
+fn create_mint_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    denom: String,
+    amount: Uint128,
+    recipient: Addr,
+) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+    let mut msgs = chain(env).create_mint_msgs(denom, amount, recipient.clone());
+    if let Some(ustake_snapshot) = State::default().ustake_snapshot.may_load(storage)? {
+        msgs.push(
+            SnapshotExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount,
+            }
+            .into_msg(&ustake_snapshot)?,
+        );
+    }
+    Ok(msgs)
+}
+
+/// Burns ustake of the hub and reports the burn to the ustake snapshot contract, if one is set.
+fn create_burn_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    denom: String,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+    let mut msgs = vec![chain(env).create_burn_msg(denom, amount)];
+    if let Some(ustake_snapshot) = State::default().ustake_snapshot.may_load(storage)? {
+        msgs.push(
+            SnapshotExecuteMsg::Burn {
+                amount,
+            }
+            .into_msg(&ustake_snapshot)?,
+        );
+    }
+    Ok(msgs)
+}
+
+/// Returns the cw721 collection, which is stored as the cw20 utoken when staking NFTs.
+fn nft_collection(stake: &StakeToken) -> Result<Nft, ContractError> {
+    match &stake.utoken {
+        AssetInfo::Token {
+            contract_addr,
+        } => Ok(Nft(contract_addr.clone())),
+        AssetInfo::NativeToken {
+            ..
+        } => Err(ContractError::NotSupportedForNft("native utoken".into())),
+    }
+}
+
+/// Releases the harvested rewards that vested until `current_time` into `total_utoken_bonded`, so
+/// that they are part of the exchange rate used for minting and unbonding.
+fn release_vested_rewards(
+    storage: &mut dyn Storage,
+    state: &State,
+    stake: &mut StakeToken,
+    current_time: u64,
+) -> StdResult<RewardVesting> {
+    let mut vesting = state.reward_vesting.may_load(storage)?.unwrap_or_default();
+    if vesting.pending.is_zero() {
+        return Ok(vesting);
+    }
+
+    let vested = release_vested_amount(&mut vesting, current_time);
+    stake.total_utoken_bonded = stake.total_utoken_bonded.checked_add(vested)?;
+    state.reward_vesting.save(storage, &vesting)?;
+    Ok(vesting)
+}
+
 fn assert_not_disabled(stake: &StakeToken) -> Result<(), ContractError> {
     if stake.disabled {
         return Err(ContractError::DisabledMaintenance {});
@@ -190,13 +316,18 @@ pub fn harvest(
     let stake = state.stake_token.load(deps.storage)?;
     assert_not_disabled(&stake)?;
 
-    // 1. Withdraw rewards
-    let claim_msgs = stake.dao_interface.claim_rewards_msgs(
+    // 1. Withdraw rewards from the DAO and all additional reward sources
+    let native_denoms = native_denoms.unwrap_or_default();
+    let cw20_assets = cw20_assets.unwrap_or_default();
+    let mut claim_msgs = stake.dao_interface.claim_rewards_msgs(
         &env,
         &stake.utoken,
-        native_denoms.unwrap_or_default(),
-        cw20_assets.unwrap_or_default(),
+        native_denoms.clone(),
+        cw20_assets.clone(),
     )?;
+    for source in state.reward_sources.may_load(deps.storage)?.unwrap_or_default() {
+        claim_msgs.push(source.claim_msg(&env, native_denoms.clone(), cw20_assets.clone())?);
+    }
 
     // 2. Prepare LP withdrawals / deconstruction
     let withdrawals =
@@ -286,7 +417,8 @@ pub fn single_stage_swap(
     let chain = chain(&env);
     let default_max_spread = state.get_default_max_spread(deps.storage);
     let get_denoms = || stage.iter().map(|a| a.1.clone()).collect_vec();
-    let balances = get_balances_hashmap(&deps, env, get_denoms)?;
+    let mut balances = get_balances_hashmap(&deps, env, get_denoms)?;
+    remove_locked_balances(deps.storage, &mut balances)?;
 
     let mut response = Response::new().add_attribute("action", "erishub/single_stage_swap");
     // iterate all specified swaps of the stage
@@ -334,7 +466,8 @@ pub fn multi_swap_router(
     let chain = chain(&env);
 
     let get_denoms = || router.1.clone();
-    let balances = get_balances_hashmap(&deps, env, get_denoms)?;
+    let mut balances = get_balances_hashmap(&deps, env, get_denoms)?;
+    remove_locked_balances(deps.storage, &mut balances)?;
 
     let mut response = Response::new().add_attribute("action", "erishub/multi_swap_router");
 
@@ -421,7 +554,12 @@ fn check_received_coin_msg(
     // offset to account for funds being sent that should be ignored
     negative_offset: Option<Uint128>,
 ) -> StdResult<CosmosMsg<CustomMsgType>> {
-    let mut amount = stake.utoken.query_pool(&deps.querier, env.contract.address.to_string())?;
+    // rewards can't be compounded into NFTs, only swapped into ustake that is burned on reinvest
+    let mut amount = if stake.dao_interface.is_nft() {
+        Uint128::zero()
+    } else {
+        stake.utoken.query_pool(&deps.querier, env.contract.address.to_string())?
+    };
 
     if let Some(negative_offset) = negative_offset {
         amount = amount.checked_sub(negative_offset)?;
@@ -473,6 +611,8 @@ pub fn reinvest(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
     let fee_config = state.fee_config.load(deps.storage)?;
     let mut unlocked_coins = state.unlocked_coins.load(deps.storage)?;
     let mut stake = state.stake_token.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let mut vesting = release_vested_rewards(deps.storage, &state, &mut stake, current_time)?;
 
     if unlocked_coins.is_empty() {
         return Err(ContractError::NoTokensAvailable(format!(
@@ -494,7 +634,15 @@ pub fn reinvest(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
         let send_fee = if asset.info == stake.utoken {
             let to_bond = remaining;
 
-            stake.total_utoken_bonded += to_bond;
+            if vesting.period == 0 {
+                stake.total_utoken_bonded += to_bond;
+            } else {
+                // the rewards are staked right away, but only released into the exchange rate
+                // linearly over the vesting period together with the ones still pending
+                vesting.pending += to_bond;
+                vesting.start_time = current_time;
+                vesting.end_time = current_time + vesting.period;
+            }
 
             event = event
                 .add_attribute("utoken_bonded", to_bond)
@@ -513,7 +661,7 @@ pub fn reinvest(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
                 .add_attribute("ustake_protocol_fee", protocol_fee);
 
             stake.total_supply = stake.total_supply.checked_sub(remaining)?;
-            msgs.push(chain(&env).create_burn_msg(stake.denom.clone(), remaining));
+            msgs.extend(create_burn_msgs(deps.storage, &env, stake.denom.clone(), remaining)?);
             true
         } else {
             // we can ignore other coins as we will only store utoken and ustake there
@@ -530,6 +678,7 @@ pub fn reinvest(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
     }
 
     state.stake_token.save(deps.storage, &stake)?;
+    state.reward_vesting.save(deps.storage, &vesting)?;
 
     // remove the converted coins. Unlocked_coins track utoken ([TOKEN]) and ustake (amp[TOKEN]).
     unlocked_coins
@@ -538,7 +687,7 @@ pub fn reinvest(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
 
     // update exchange_rate history
     let exchange_rate = calc_current_exchange_rate(stake)?;
-    state.exchange_history.save(deps.storage, env.block.time.seconds(), &exchange_rate)?;
+    state.exchange_history.save(deps.storage, current_time, &exchange_rate)?;
 
     Ok(Response::new()
         .add_messages(msgs)
@@ -583,12 +732,15 @@ pub fn callback_received_coins(
     let mut received_coins = Assets(vec![]);
     let mut event = Event::new("erishub/received");
 
-    event = event.add_optional_attribute(add_to_received_coins(
-        &deps,
-        env.contract.address.clone(),
-        snapshot,
-        &mut received_coins,
-    )?);
+    // NFTs are only received through unbonding and can't be queried as a balance
+    if !state.stake_token.load(deps.storage)?.dao_interface.is_nft() {
+        event = event.add_optional_attribute(add_to_received_coins(
+            &deps,
+            env.contract.address.clone(),
+            snapshot,
+            &mut received_coins,
+        )?);
+    }
 
     event = event.add_optional_attribute(add_to_received_coins(
         &deps,
@@ -696,6 +848,7 @@ pub fn submit_batch(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult
     if current_time < pending_batch.est_unbond_start_time {
         return Err(ContractError::SubmitBatchAfter(pending_batch.est_unbond_start_time));
     }
+    release_vested_rewards(deps.storage, &state, &mut stake, current_time)?;
 
     let ustake_supply = stake.total_supply;
 
@@ -727,15 +880,31 @@ pub fn submit_batch(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult
         },
     )?;
 
-    let unbond_msg = stake.dao_interface.unbond_msg(&stake.utoken, utoken_to_unbond)?;
+    let unbond_msg = if stake.dao_interface.is_nft() {
+        // unbond NFTs in ascending order of their token id, the batch keeps track of them until
+        // they are withdrawn
+        let token_ids = state
+            .staked_nfts
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(utoken_to_unbond.u128() as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+        for token_id in &token_ids {
+            state.staked_nfts.remove(deps.storage, token_id);
+        }
+        state.batch_token_ids.save(deps.storage, pending_batch.id, &token_ids)?;
+
+        stake.dao_interface.unbond_nft_msg(token_ids)?
+    } else {
+        stake.dao_interface.unbond_msg(&stake.utoken, utoken_to_unbond)?
+    };
 
     // apply burn to the stored total supply and save state
     stake.total_utoken_bonded = stake.total_utoken_bonded.checked_sub(utoken_to_unbond)?;
     stake.total_supply = stake.total_supply.checked_sub(pending_batch.ustake_to_burn)?;
     state.stake_token.save(deps.storage, &stake)?;
 
-    let burn_msg: CosmosMsg<CustomMsgType> =
-        chain(&env).create_burn_msg(stake.denom.clone(), pending_batch.ustake_to_burn);
+    let burn_msgs =
+        create_burn_msgs(deps.storage, &env, stake.denom.clone(), pending_batch.ustake_to_burn)?;
 
     let event = Event::new("erishub/unbond_submitted")
         .add_attribute("id", pending_batch.id.to_string())
@@ -744,7 +913,7 @@ pub fn submit_batch(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult
 
     Ok(Response::new()
         .add_message(unbond_msg)
-        .add_message(burn_msg)
+        .add_messages(burn_msgs)
         // .add_message(check_received_coin_msg(&deps, &env, stake, None)?)
         .add_event(event)
         .add_attribute("action", "erishub/unbond"))
@@ -792,15 +961,17 @@ pub fn reconcile(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
         .add_attribute("ids", ids)
         .add_attribute("utoken_deducted", "0");
 
+    // validate that the amount received is the one expected - otherwise dont allow reconciliation.
+    // NFTs can't be checked through a balance query.
+    let assert_msg = if stake.dao_interface.is_nft() {
+        None
+    } else {
+        assert_received_amount_msg(&deps, &env, &stake, utoken_expected_received)?
+    };
+
     Ok(Response::new()
         .add_message(stake.dao_interface.claim_unbonded_msg()?)
-        // validate that the amount received is the one expected - otherwise dont allow reconciliation
-        .add_optional_message(assert_received_amount_msg(
-            &deps,
-            &env,
-            &stake,
-            utoken_expected_received,
-        )?)
+        .add_optional_message(assert_msg)
         .add_event(event)
         .add_attribute("action", "erishub/reconcile"))
 }
@@ -838,7 +1009,9 @@ pub fn withdraw_unbonded(
     // - has finished unbonding
     // If not sure whether the batches have been reconciled, the user should first invoke `ExecuteMsg::Reconcile`
     // before withdrawing.
+    let is_nft = stake.dao_interface.is_nft();
     let mut total_utoken_to_refund = Uint128::zero();
+    let mut token_ids_to_refund: Vec<String> = vec![];
     let mut ids: Vec<String> = vec![];
     for request in &requests {
         if let Ok(mut batch) = state.previous_batches.load(deps.storage, request.id) {
@@ -846,6 +1019,20 @@ pub fn withdraw_unbonded(
                 let utoken_to_refund =
                     batch.utoken_unclaimed.multiply_ratio(request.shares, batch.total_shares);
 
+                if is_nft {
+                    // NFTs are given out rounded down by share, the last withdrawer of the batch
+                    // receives the remaining ones
+                    let mut token_ids = state.batch_token_ids.load(deps.storage, request.id)?;
+                    let count = if request.shares == batch.total_shares {
+                        token_ids.len()
+                    } else {
+                        cmp::min(utoken_to_refund.u128() as usize, token_ids.len())
+                    };
+                    let remaining = token_ids.split_off(count);
+                    token_ids_to_refund.extend(token_ids);
+                    state.batch_token_ids.save(deps.storage, request.id, &remaining)?;
+                }
+
                 ids.push(request.id.to_string());
 
                 total_utoken_to_refund += utoken_to_refund;
@@ -854,6 +1041,7 @@ pub fn withdraw_unbonded(
 
                 if batch.total_shares.is_zero() {
                     state.previous_batches.remove(deps.storage, request.id)?;
+                    state.batch_token_ids.remove(deps.storage, request.id);
                 } else {
                     state.previous_batches.save(deps.storage, batch.id, &batch)?;
                 }
@@ -867,7 +1055,15 @@ pub fn withdraw_unbonded(
         return Err(ContractError::CantBeZero("withdrawable amount".into()));
     }
 
-    let refund_msg = stake.utoken.with_balance(total_utoken_to_refund).transfer_msg(&receiver)?;
+    let refund_msgs = if is_nft {
+        let collection = nft_collection(&stake)?;
+        token_ids_to_refund
+            .into_iter()
+            .map(|token_id| collection.transfer_msg(&receiver, token_id))
+            .collect::<StdResult<Vec<_>>>()?
+    } else {
+        vec![stake.utoken.with_balance(total_utoken_to_refund).transfer_msg(&receiver)?]
+    };
 
     let event = Event::new("erishub/unbonded_withdrawn")
         .add_attribute("ids", ids.join(","))
@@ -876,7 +1072,7 @@ pub fn withdraw_unbonded(
         .add_attribute("utoken_refunded", total_utoken_to_refund);
 
     Ok(Response::new()
-        .add_message(refund_msg)
+        .add_messages(refund_msgs)
         .add_event(event)
         .add_attribute("action", "erishub/withdraw_unbonded"))
 }
@@ -943,6 +1139,9 @@ pub fn update_config(
     epoch_period: Option<u64>,
     unbond_period: Option<u64>,
     dao_interface: Option<DaoInterface<String>>,
+    reward_sources: Option<Vec<RewardSource<String>>>,
+    reward_vesting_period: Option<u64>,
+    ustake_snapshot: Option<String>,
 ) -> ContractResult {
     let state = State::default();
 
@@ -985,10 +1184,36 @@ pub fn update_config(
 
     if let Some(dao_interface) = dao_interface {
         let mut stake = state.stake_token.load(deps.storage)?;
+        if dao_interface.is_nft() != stake.dao_interface.is_nft() {
+            return Err(ContractError::NftModeChangeNotAllowed {});
+        }
         stake.dao_interface = dao_interface.validate(deps.api)?;
         state.stake_token.save(deps.storage, &stake)?;
     }
 
+    if let Some(reward_sources) = reward_sources {
+        let reward_sources = reward_sources
+            .iter()
+            .map(|source| source.validate(deps.api))
+            .collect::<StdResult<Vec<_>>>()?;
+        state.reward_sources.save(deps.storage, &reward_sources)?;
+    }
+
+    if let Some(reward_vesting_period) = reward_vesting_period {
+        // rewards that are already pending keep their end time
+        let mut vesting = state.reward_vesting.may_load(deps.storage)?.unwrap_or_default();
+        vesting.period = reward_vesting_period;
+        state.reward_vesting.save(deps.storage, &vesting)?;
+    }
+
+    if let Some(ustake_snapshot) = ustake_snapshot {
+        if ustake_snapshot.is_empty() {
+            state.ustake_snapshot.remove(deps.storage);
+        } else {
+            state.ustake_snapshot.save(deps.storage, &deps.api.addr_validate(&ustake_snapshot)?)?;
+        }
+    }
+
     if stages_preset.is_some() {
         validate_no_utoken_or_ustake_swap(
             &env,
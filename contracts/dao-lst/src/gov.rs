@@ -1,7 +1,16 @@
-use cosmwasm_std::{DepsMut, Env, Event, MessageInfo, Response};
+use cosmwasm_std::{
+    attr, Addr, Decimal, DepsMut, Env, Event, MessageInfo, Response, StdResult, Storage, Uint128,
+    VoteOption,
+};
+use eris::hub::{HolderBallot, HolderProposal, HubVote, HubVoteOption};
 use eris_chain_adapter::types::CustomQueryType;
+use itertools::Itertools;
 
-use crate::{error::ContractResult, state::State};
+use crate::{
+    airdrop::{capped_by_snapshot, verify_proof},
+    error::{ContractError, ContractResult},
+    state::State,
+};
 
 pub fn vote(
     deps: DepsMut<CustomQueryType>,
@@ -12,16 +21,298 @@ pub fn vote(
 ) -> ContractResult {
     let state = State::default();
     state.assert_vote_operator(deps.storage, &info.sender)?;
+    assert_not_open_for_holders(deps.storage, proposal_id)?;
     let stake = state.stake_token.load(deps.storage)?;
 
     let event = Event::new("erishub/voted").add_attribute("prop", proposal_id.to_string());
 
-    let vote = stake.dao_interface.vote_msg(
+    let vote_msg = stake.dao_interface.vote_msg(
         proposal_id,
-        vote,
-        env.contract.address,
+        vote.clone(),
+        env.contract.address.clone(),
         stake.total_utoken_bonded,
     )?;
 
-    Ok(Response::new().add_message(vote).add_event(event).add_attribute("action", "erishub/vote"))
+    save_vote(
+        deps.storage,
+        &env,
+        info.sender,
+        proposal_id,
+        HubVoteOption::Single(vote),
+        stake.total_utoken_bonded,
+    )?;
+
+    Ok(Response::new()
+        .add_message(vote_msg)
+        .add_event(event)
+        .add_attribute("action", "erishub/vote"))
+}
+
+pub fn vote_multiple(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    option_id: u32,
+) -> ContractResult {
+    let state = State::default();
+    state.assert_vote_operator(deps.storage, &info.sender)?;
+    let stake = state.stake_token.load(deps.storage)?;
+
+    let event = Event::new("erishub/voted_multiple")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("option", option_id.to_string());
+
+    let vote_msg = stake.dao_interface.vote_multiple_msg(
+        proposal_id,
+        option_id,
+        env.contract.address.clone(),
+        stake.total_utoken_bonded,
+    )?;
+
+    save_vote(
+        deps.storage,
+        &env,
+        info.sender,
+        proposal_id,
+        HubVoteOption::Multiple(option_id),
+        stake.total_utoken_bonded,
+    )?;
+
+    Ok(Response::new()
+        .add_message(vote_msg)
+        .add_event(event)
+        .add_attribute("action", "erishub/vote_multiple"))
+}
+
+pub fn open_holder_vote(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    snapshot_height: u64,
+    merkle_root: String,
+    end_time: u64,
+) -> ContractResult {
+    let state = State::default();
+    state.assert_vote_operator(deps.storage, &info.sender)?;
+    assert_not_open_for_holders(deps.storage, proposal_id)?;
+
+    if end_time <= env.block.time.seconds() {
+        return Err(ContractError::HolderVoteEnded(proposal_id));
+    }
+
+    // the tally has to be forwarded while the proposal is still open
+    let stake = state.stake_token.load(deps.storage)?;
+    let proposal_end = stake.dao_interface.query_proposal(&deps.querier, proposal_id)?.end_time_s;
+    if end_time >= proposal_end {
+        return Err(ContractError::HolderVoteEndsAfterProposal(proposal_id, proposal_end));
+    }
+
+    // without the snapshot contract, the merkle root alone would decide the voting power
+    let ustake_snapshot = state
+        .ustake_snapshot
+        .may_load(deps.storage)?
+        .ok_or(ContractError::UstakeSnapshotRequired {})?;
+    if snapshot_height > env.block.height {
+        return Err(ContractError::SnapshotHeightInFuture(snapshot_height));
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    state.holder_proposals.save(
+        deps.storage,
+        proposal_id,
+        &HolderProposal {
+            proposal_id,
+            snapshot_height,
+            merkle_root,
+            end_time,
+            tally: vec![],
+            forwarded: false,
+            ustake_snapshot,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "erishub/open_holder_vote").add_attributes(vec![
+        attr("prop", proposal_id.to_string()),
+        attr("snapshot_height", snapshot_height.to_string()),
+        attr("end_time", end_time.to_string()),
+    ]))
+}
+
+pub fn holder_vote(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    ustake: Uint128,
+    proof: Vec<String>,
+    votes: Vec<(Decimal, VoteOption)>,
+) -> ContractResult {
+    let state = State::default();
+    let mut proposal = state.holder_proposals.load(deps.storage, proposal_id)?;
+
+    if proposal.forwarded || env.block.time.seconds() > proposal.end_time {
+        return Err(ContractError::HolderVoteEnded(proposal_id));
+    }
+
+    let total: Decimal = votes.iter().map(|(weight, _)| *weight).sum();
+    if votes.is_empty() || total != Decimal::one() {
+        return Err(ContractError::InvalidVoteWeights {});
+    }
+
+    verify_proof(&proposal.merkle_root, &info.sender, ustake, proof)?;
+    let ustake = capped_by_snapshot(
+        &deps,
+        Some(proposal.ustake_snapshot.clone()),
+        &info.sender,
+        proposal.snapshot_height,
+        ustake,
+    )?;
+
+    // a previous vote of the holder is replaced
+    if let Some(previous) =
+        state.holder_ballots.may_load(deps.storage, (proposal_id, &info.sender))?
+    {
+        for (weight, option) in previous.votes {
+            if let Some(entry) = proposal.tally.iter_mut().find(|(tallied, _)| *tallied == option) {
+                entry.1 = entry.1.checked_sub(previous.ustake * weight)?;
+            }
+        }
+    }
+
+    for (weight, option) in votes.iter() {
+        let amount = ustake * *weight;
+        match proposal.tally.iter_mut().find(|(tallied, _)| tallied == option) {
+            Some(entry) => entry.1 = entry.1.checked_add(amount)?,
+            None => proposal.tally.push((option.clone(), amount)),
+        }
+    }
+
+    let event = Event::new("erishub/holder_voted")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("voter", info.sender.to_string())
+        .add_attribute("ustake", ustake)
+        .add_attribute(
+            "votes",
+            votes.iter().map(|(weight, option)| format!("{0:?}={1}", option, weight)).join(","),
+        );
+
+    state.holder_ballots.save(
+        deps.storage,
+        (proposal_id, &info.sender),
+        &HolderBallot {
+            ustake,
+            votes,
+        },
+    )?;
+    state.holder_proposals.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new().add_event(event).add_attribute("action", "erishub/holder_vote"))
+}
+
+pub fn forward_holder_vote(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> ContractResult {
+    let state = State::default();
+    let mut proposal = state.holder_proposals.load(deps.storage, proposal_id)?;
+    let stake = state.stake_token.load(deps.storage)?;
+
+    if proposal.forwarded {
+        return Err(ContractError::HolderVoteForwarded(proposal_id));
+    }
+    if env.block.time.seconds() <= proposal.end_time {
+        return Err(ContractError::HolderVoteNotEnded(proposal_id));
+    }
+
+    let tally = proposal.tally.iter().filter(|(_, amount)| !amount.is_zero()).collect_vec();
+    let total: Uint128 = tally.iter().map(|(_, amount)| *amount).sum();
+    if total.is_zero() {
+        return Err(ContractError::NoHolderVotes(proposal_id));
+    }
+
+    let event = Event::new("erishub/holder_vote_forwarded")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute(
+            "tally",
+            tally.iter().map(|(option, amount)| format!("{0:?}={1}", option, amount)).join(","),
+        );
+
+    // the DAO can't split the voting power, the option with the most ustake is voted for.
+    // On equal ustake the first option voted for wins.
+    let mut winner = tally[0];
+    for entry in tally.iter().skip(1) {
+        if entry.1 > winner.1 {
+            winner = *entry;
+        }
+    }
+
+    let vote_msg = stake.dao_interface.vote_msg(
+        proposal_id,
+        winner.0.clone(),
+        env.contract.address.clone(),
+        stake.total_utoken_bonded,
+    )?;
+
+    save_vote(
+        deps.storage,
+        &env,
+        info.sender,
+        proposal_id,
+        HubVoteOption::Single(winner.0.clone()),
+        stake.total_utoken_bonded,
+    )?;
+
+    proposal.forwarded = true;
+    state.holder_proposals.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_message(vote_msg)
+        .add_event(event)
+        .add_attribute("action", "erishub/forward_holder_vote"))
+}
+
+/// Proposals opened for holders can only be voted on by the holders
+fn assert_not_open_for_holders(
+    storage: &dyn Storage,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    if State::default().holder_proposals.has(storage, proposal_id) {
+        return Err(ContractError::HolderVoteOpen(proposal_id));
+    }
+    Ok(())
+}
+
+/// Records the vote of the hub, a later vote on the same proposal replaces it
+fn save_vote(
+    storage: &mut dyn Storage,
+    env: &Env,
+    operator: Addr,
+    proposal_id: u64,
+    vote: HubVoteOption,
+    voting_power: Uint128,
+) -> StdResult<()> {
+    let state = State::default();
+    let votes = if let HubVoteOption::Multiple(_) = vote {
+        state.multiple_votes
+    } else {
+        state.votes
+    };
+
+    votes.save(
+        storage,
+        proposal_id,
+        &HubVote {
+            proposal_id,
+            vote,
+            voting_power,
+            timestamp: env.block.time.seconds(),
+            operator,
+        },
+    )
 }
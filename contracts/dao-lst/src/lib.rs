@@ -7,6 +7,7 @@ pub mod queries;
 pub mod state;
 pub mod types;
 
+pub mod airdrop;
 pub mod claim;
 mod constants;
 pub mod error;
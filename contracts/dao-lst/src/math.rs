@@ -1,4 +1,7 @@
+use std::cmp;
+
 use cosmwasm_std::Uint128;
+use eris::hub::RewardVesting;
 
 //--------------------------------------------------------------------------------------------------
 // Minting/burning logics
@@ -30,3 +33,30 @@ pub(crate) fn compute_unbond_amount(
 ) -> Uint128 {
     utoken_bonded.multiply_ratio(ustake_to_burn, ustake_supply)
 }
+
+/// Compute the amount of `pending` rewards that is released linearly from `start_time` until
+/// `end_time` at `current_time`
+pub(crate) fn compute_vested_amount(
+    pending: Uint128,
+    start_time: u64,
+    end_time: u64,
+    current_time: u64,
+) -> Uint128 {
+    if current_time >= end_time {
+        pending
+    } else if current_time <= start_time {
+        Uint128::zero()
+    } else {
+        pending.multiply_ratio(current_time - start_time, end_time - start_time)
+    }
+}
+
+/// Release the rewards that vested until `current_time` from the pending amount. Returns the
+/// released amount.
+pub(crate) fn release_vested_amount(vesting: &mut RewardVesting, current_time: u64) -> Uint128 {
+    let vested =
+        compute_vested_amount(vesting.pending, vesting.start_time, vesting.end_time, current_time);
+    vesting.pending -= vested;
+    vesting.start_time = cmp::max(vesting.start_time, current_time);
+    vested
+}
@@ -1,16 +1,18 @@
 use std::ops::Div;
 
-use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult, Uint128};
+use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, QuerierWrapper, StdResult, Uint128};
 use cw_storage_plus::Bound;
 
 use eris::hub::{
-    Batch, ConfigResponse, ExchangeRatesResponse, PendingBatch, StateResponse,
+    Airdrop, Batch, ConfigResponse, ExchangeRatesResponse, HealthResponse, HolderBallot,
+    HolderProposal, HubVote, PendingBatch, RewardVesting, StateResponse,
     UnbondRequestsByBatchResponseItem, UnbondRequestsByUserResponseItem,
     UnbondRequestsByUserResponseItemDetails,
 };
 use eris_chain_adapter::types::CustomQueryType;
 
 use crate::constants::DAY;
+use crate::math::release_vested_amount;
 use crate::state::State;
 
 const MAX_LIMIT: u32 = 30;
@@ -35,6 +37,8 @@ pub fn config(deps: Deps<CustomQueryType>) -> StdResult<ConfigResponse> {
         allow_donations: state.allow_donations.may_load(deps.storage)?.unwrap_or(false),
         vote_operator: state.vote_operator.may_load(deps.storage)?.map(|addr| addr.into()),
         dao_interface: stake.dao_interface,
+        reward_sources: state.reward_sources.may_load(deps.storage)?.unwrap_or_default(),
+        ustake_snapshot: state.ustake_snapshot.may_load(deps.storage)?.map(|addr| addr.into()),
     })
 }
 
@@ -43,7 +47,11 @@ pub fn state(deps: Deps<CustomQueryType>, env: Env) -> StdResult<StateResponse>
 
     let stake_token = state.stake_token.load(deps.storage)?;
     let total_ustake = stake_token.total_supply;
-    let total_utoken = stake_token.total_utoken_bonded;
+
+    // rewards are part of the exchange rate as soon as they are vested
+    let mut vesting = state.reward_vesting.may_load(deps.storage)?.unwrap_or_default();
+    let vested = release_vested_amount(&mut vesting, env.block.time.seconds());
+    let total_utoken = stake_token.total_utoken_bonded.checked_add(vested)?;
 
     // only not reconciled batches are relevant as they are still unbonding and estimated unbond time in the future.
     let unbonding: u128 = state
@@ -59,7 +67,19 @@ pub fn state(deps: Deps<CustomQueryType>, env: Env) -> StdResult<StateResponse>
         .map(|item| item.utoken_unclaimed.u128())
         .sum();
 
-    let available = stake_token.utoken.query_pool(&deps.querier, env.contract.address)?;
+    let available = if stake_token.dao_interface.is_nft() {
+        // NFTs are only held by the contract after being reconciled until they are withdrawn
+        state
+            .previous_batches
+            .idx
+            .reconciled
+            .prefix(true.into())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| Ok(item?.1.utoken_unclaimed))
+            .sum::<StdResult<Uint128>>()?
+    } else {
+        stake_token.utoken.query_pool(&deps.querier, env.contract.address)?
+    };
 
     let exchange_rate = if total_ustake.is_zero() {
         Decimal::one()
@@ -74,7 +94,38 @@ pub fn state(deps: Deps<CustomQueryType>, env: Env) -> StdResult<StateResponse>
         unlocked_coins: state.unlocked_coins.load(deps.storage)?,
         unbonding: Uint128::from(unbonding),
         available,
-        tvl_utoken: total_utoken.checked_add(Uint128::from(unbonding))?.checked_add(available)?,
+        tvl_utoken: total_utoken
+            .checked_add(Uint128::from(unbonding))?
+            .checked_add(available)?
+            .checked_add(vesting.pending)?,
+    })
+}
+
+pub fn health(deps: Deps<CustomQueryType>, env: Env) -> StdResult<HealthResponse> {
+    let state = State::default();
+
+    let stake_token = state.stake_token.load(deps.storage)?;
+    // rewards that are still vesting are staked as well
+    let vesting = state.reward_vesting.may_load(deps.storage)?.unwrap_or_default();
+    let total_utoken_bonded = stake_token.total_utoken_bonded.checked_add(vesting.pending)?;
+    let dao_staked = stake_token.dao_interface.query_staked_balance(
+        &QuerierWrapper::new(&*deps.querier),
+        &stake_token.utoken,
+        &env.contract.address,
+    )?;
+
+    let deficit = dao_staked < total_utoken_bonded;
+    let delta = if deficit {
+        total_utoken_bonded - dao_staked
+    } else {
+        dao_staked - total_utoken_bonded
+    };
+
+    Ok(HealthResponse {
+        total_utoken_bonded,
+        dao_staked,
+        delta,
+        deficit,
     })
 }
 
@@ -256,3 +307,91 @@ pub fn query_exchange_rates(
         apr,
     })
 }
+
+pub fn batch_token_ids(deps: Deps<CustomQueryType>, id: u64) -> StdResult<Vec<String>> {
+    Ok(State::default().batch_token_ids.may_load(deps.storage, id)?.unwrap_or_default())
+}
+
+pub fn reward_vesting(deps: Deps<CustomQueryType>, env: Env) -> StdResult<RewardVesting> {
+    let mut vesting = State::default().reward_vesting.may_load(deps.storage)?.unwrap_or_default();
+    release_vested_amount(&mut vesting, env.block.time.seconds());
+    Ok(vesting)
+}
+
+pub fn airdrop(deps: Deps<CustomQueryType>, id: u64) -> StdResult<Airdrop> {
+    State::default().airdrops.load(deps.storage, id)
+}
+
+pub fn airdrops(
+    deps: Deps<CustomQueryType>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Airdrop>> {
+    let state = State::default();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    state
+        .airdrops
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
+}
+
+pub fn airdrop_claimed(deps: Deps<CustomQueryType>, id: u64, address: String) -> StdResult<bool> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(State::default().airdrop_claims.has(deps.storage, (id, &address)))
+}
+
+pub fn vote(deps: Deps<CustomQueryType>, proposal_id: u64, multiple: bool) -> StdResult<HubVote> {
+    let state = State::default();
+    if multiple {
+        state.multiple_votes.load(deps.storage, proposal_id)
+    } else {
+        state.votes.load(deps.storage, proposal_id)
+    }
+}
+
+pub fn votes(
+    deps: Deps<CustomQueryType>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    multiple: bool,
+) -> StdResult<Vec<HubVote>> {
+    let state = State::default();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let votes = if multiple {
+        state.multiple_votes
+    } else {
+        state.votes
+    };
+    votes
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
+}
+
+pub fn holder_proposal(deps: Deps<CustomQueryType>, proposal_id: u64) -> StdResult<HolderProposal> {
+    State::default().holder_proposals.load(deps.storage, proposal_id)
+}
+
+pub fn holder_vote(
+    deps: Deps<CustomQueryType>,
+    proposal_id: u64,
+    voter: String,
+) -> StdResult<HolderBallot> {
+    let voter = deps.api.addr_validate(&voter)?;
+    State::default().holder_ballots.load(deps.storage, (proposal_id, &voter))
+}
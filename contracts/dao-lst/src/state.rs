@@ -1,8 +1,11 @@
 use astroport::asset::Asset;
-use cosmwasm_std::{Addr, Decimal, Storage};
+use cosmwasm_std::{Addr, Decimal, Empty, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
-use eris::hub::{Batch, FeeConfig, PendingBatch, SingleSwapConfig, StakeToken, UnbondRequest};
+use eris::hub::{
+    Airdrop, Batch, FeeConfig, HolderBallot, HolderProposal, HubVote, PendingBatch, RewardSource,
+    RewardVesting, SingleSwapConfig, StakeToken, UnbondRequest,
+};
 use eris_chain_adapter::types::{DenomType, WithdrawType};
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -36,6 +39,11 @@ pub struct State<'a> {
     /// Users' shares in unbonding batches
     pub unbond_requests: IndexedMap<'a, (u64, &'a Addr), UnbondRequest, UnbondRequestsIndexes<'a>>,
 
+    /// Token ids of the NFTs currently staked, only used when staking NFTs
+    pub staked_nfts: Map<'a, &'a str, Empty>,
+    /// Token ids of the NFTs unbonded in a previous batch that have not been withdrawn
+    pub batch_token_ids: Map<'a, u64, Vec<String>>,
+
     /// Fee Config
     pub fee_config: Item<'a, FeeConfig>,
     /// Operator who is allowed to vote on props
@@ -43,6 +51,33 @@ pub struct State<'a> {
     /// Specifies wether the contract allows donations
     pub allow_donations: Item<'a, bool>,
 
+    /// Additional contracts rewards are claimed from during harvest
+    pub reward_sources: Item<'a, Vec<RewardSource<Addr>>>,
+    /// Harvested rewards that are released into the exchange rate over time
+    pub reward_vesting: Item<'a, RewardVesting>,
+    /// Contract mints and burns of ustake are reported to
+    pub ustake_snapshot: Item<'a, Addr>,
+
+    /// Votes cast by the hub, by proposal id
+    pub votes: Map<'a, u64, HubVote>,
+    /// Votes cast by the hub on multiple choice proposals, by proposal id. Kept apart from `votes`,
+    /// as the ids of the multiple choice module can overlap with the ones of the main module.
+    pub multiple_votes: Map<'a, u64, HubVote>,
+
+    /// Proposals opened for ustake holders to vote on directly
+    pub holder_proposals: Map<'a, u64, HolderProposal>,
+    /// Votes of ustake holders on proposals opened for holders
+    pub holder_ballots: Map<'a, (u64, &'a Addr), HolderBallot>,
+
+    /// Airdrops distributed to ustake holders
+    pub airdrops: Map<'a, u64, Airdrop>,
+    /// Id of the last created airdrop
+    pub airdrop_id: Item<'a, u64>,
+    /// Holders that already claimed their share of an airdrop
+    pub airdrop_claims: Map<'a, (u64, &'a Addr), Empty>,
+    /// Balances locked for airdrops, which must not be swapped or transferred, by asset
+    pub airdrop_locked: Map<'a, String, Uint128>,
+
     // history of the exchange_rate
     pub exchange_history: Map<'a, u64, Decimal>,
 
@@ -78,9 +113,22 @@ impl Default for State<'static> {
             pending_batch: Item::new("pending_batch"),
             previous_batches: IndexedMap::new("previous_batches", pb_indexes),
             unbond_requests: IndexedMap::new("unbond_requests", ubr_indexes),
+            staked_nfts: Map::new("staked_nfts"),
+            batch_token_ids: Map::new("batch_token_ids"),
             fee_config: Item::new("fee_config"),
             vote_operator: Item::new("vote_operator"),
             allow_donations: Item::new("allow_donations"),
+            reward_sources: Item::new("reward_sources"),
+            reward_vesting: Item::new("reward_vesting"),
+            ustake_snapshot: Item::new("ustake_snapshot"),
+            votes: Map::new("votes"),
+            multiple_votes: Map::new("multiple_votes"),
+            holder_proposals: Map::new("holder_proposals"),
+            holder_ballots: Map::new("holder_ballots"),
+            airdrops: Map::new("airdrops"),
+            airdrop_id: Item::new("airdrop_id"),
+            airdrop_claims: Map::new("airdrop_claims"),
+            airdrop_locked: Map::new("airdrop_locked"),
             exchange_history: Map::new("exchange_history"),
             default_max_spread: Item::new("default_max_spread"),
         }
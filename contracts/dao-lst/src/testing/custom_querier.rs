@@ -1,7 +1,12 @@
 use cosmwasm_std::testing::{BankQuerier, StakingQuerier, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_json, Coin, Empty, Querier, QuerierResult, QueryRequest, SystemError, WasmQuery,
+    from_json, to_json_binary, Coin, Empty, Querier, QuerierResult, QueryRequest, SystemError,
+    SystemResult, Timestamp, Uint128, WasmQuery,
 };
+use cw20::{BalanceResponse, Expiration};
+use eris::adapters::dao::{Cw3ProposalResponse, Cw3QueryMsg, Cw4StakeQueryMsg, Cw4StakedResponse};
+use eris::ustake_snapshot::QueryMsg as SnapshotQueryMsg;
+use std::collections::HashMap;
 
 use super::helpers::err_unsupported_query;
 
@@ -9,6 +14,10 @@ use super::helpers::err_unsupported_query;
 pub(super) struct CustomQuerier {
     pub bank_querier: BankQuerier,
     pub staking_querier: StakingQuerier,
+    pub dao_staked: Uint128,
+    pub snapshot_balances: HashMap<String, Uint128>,
+    pub snapshot_supply: Uint128,
+    pub proposal_end: u64,
 }
 
 impl Querier for CustomQuerier {
@@ -32,12 +41,87 @@ impl CustomQuerier {
         self.bank_querier = BankQuerier::new(&[(MOCK_CONTRACT_ADDR, balances)])
     }
 
+    pub fn set_dao_staked(&mut self, amount: u128) {
+        self.dao_staked = Uint128::new(amount);
+    }
+
+    /// End time of every proposal of the "gov" contract
+    pub fn set_proposal_end(&mut self, seconds: u64) {
+        self.proposal_end = seconds;
+    }
+
+    /// Balances reported by the "snapshot" contract at every height
+    pub fn set_snapshot(&mut self, balances: &[(&str, u128)]) {
+        self.snapshot_balances = balances
+            .iter()
+            .map(|(addr, amount)| (addr.to_string(), Uint128::new(*amount)))
+            .collect();
+        self.snapshot_supply = self.snapshot_balances.values().sum();
+    }
+
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match request {
             QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr,
                 msg,
-                ..
             }) => {
+                if contract_addr == "cw4" {
+                    if let Ok(Cw4StakeQueryMsg::Staked {
+                        ..
+                    }) = from_json(msg)
+                    {
+                        return SystemResult::Ok(
+                            to_json_binary(&Cw4StakedResponse {
+                                stake: self.dao_staked,
+                            })
+                            .into(),
+                        );
+                    }
+                }
+
+                if contract_addr == "gov" {
+                    if let Ok(Cw3QueryMsg::Proposal {
+                        proposal_id,
+                    }) = from_json(msg)
+                    {
+                        return SystemResult::Ok(
+                            to_json_binary(&Cw3ProposalResponse {
+                                id: proposal_id,
+                                expires: Expiration::AtTime(Timestamp::from_seconds(
+                                    self.proposal_end,
+                                )),
+                            })
+                            .into(),
+                        );
+                    }
+                }
+
+                if contract_addr == "snapshot" {
+                    match from_json(msg) {
+                        Ok(SnapshotQueryMsg::BalanceAt {
+                            address,
+                            ..
+                        }) => {
+                            return SystemResult::Ok(
+                                to_json_binary(&BalanceResponse {
+                                    balance: self
+                                        .snapshot_balances
+                                        .get(&address)
+                                        .copied()
+                                        .unwrap_or_default(),
+                                })
+                                .into(),
+                            );
+                        },
+                        Ok(SnapshotQueryMsg::TotalSupplyAt {
+                            ..
+                        }) => {
+                            return SystemResult::Ok(to_json_binary(&self.snapshot_supply).into());
+                        },
+                        _ => (),
+                    }
+                }
+
                 // if let Ok(query) = from_json::<Cw20QueryMsg>(msg) {
                 //     return self.cw20_querier.handle_query(contract_addr, query);
                 // }
@@ -10,6 +10,8 @@ use eris_chain_adapter::types::{
     WithdrawType,
 };
 use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
 
 use eris::hub::{CallbackMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakeToken};
 
@@ -38,6 +40,18 @@ pub(super) fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, CustomQueri
     }
 }
 
+/// sha256 leaf of a `{address}{ustake}` entry of a ustake snapshot
+pub(super) fn merkle_leaf(address: &str, ustake: u128) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", address, ustake).as_bytes()).as_slice().try_into().unwrap()
+}
+
+/// Hex encoded root of a merkle tree with two leaves
+pub(super) fn merkle_root(a: [u8; 32], b: [u8; 32]) -> String {
+    let mut hashes = [a, b];
+    hashes.sort_unstable();
+    hex::encode(Sha256::digest(hashes.concat()))
+}
+
 pub(super) fn mock_env_at_timestamp(timestamp: u64) -> Env {
     Env {
         block: BlockInfo {
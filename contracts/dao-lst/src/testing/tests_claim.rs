@@ -1,14 +1,17 @@
-use cosmwasm_std::attr;
+use astroport::asset::{native_asset, native_asset_info};
 use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{attr, coin, Addr, Uint128};
 
-use eris::hub::{ClaimType, ExecuteMsg};
+use eris::adapters::asset::AssetEx;
+use eris::hub::{Airdrop, ClaimType, ExecuteMsg, QueryMsg};
 
-use crate::claim::ClaimExecuteMsg;
+use crate::claim::{ClaimExecuteMerkleMsg, ClaimExecuteMsg};
 use crate::contract::execute;
 use crate::error::ContractError;
-use crate::testing::helpers::setup_test;
+use crate::state::State;
+use crate::testing::helpers::{mock_utoken, setup_test};
 
-use super::helpers::mock_env_at_timestamp;
+use super::helpers::{merkle_leaf, merkle_root, mock_env_at_timestamp, query_helper};
 
 //--------------------------------------------------------------------------------------------------
 // Execution
@@ -65,3 +68,321 @@ fn check_claim() {
 
     assert_eq!(res.attributes, vec![attr("action", "erishub/exec_claim")]);
 }
+
+#[test]
+fn check_claim_merkle() {
+    let (mut deps, _) = setup_test();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: vec![ClaimType::Merkle {
+                contract: "merkle".to_string(),
+                stage: 2,
+                amount: Uint128::new(1000),
+                proof: vec!["abcd".to_string()],
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    assert_eq!(
+        res.messages[0].msg,
+        ClaimExecuteMerkleMsg::Claim {
+            stage: 2,
+            amount: Uint128::new(1000),
+            proof: vec!["abcd".to_string()],
+        }
+        .into_msg("merkle".to_string())
+        .unwrap()
+    );
+}
+
+#[test]
+fn distributing_airdrops() {
+    let (mut deps, _) = setup_test();
+
+    // two holders with 300 and 100 ustake in the snapshot
+    let user1 = merkle_leaf("user1", 300);
+    let user2 = merkle_leaf("user2", 100);
+    let merkle_root = merkle_root(user1, user2);
+
+    deps.querier.set_bank_balances(&[coin(1001, "uairdrop"), coin(500, "uother")]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("anyone", &[]),
+        ExecuteMsg::CreateAirdrop {
+            snapshot_height: 12345,
+            merkle_root: merkle_root.clone(),
+            total_ustake: Uint128::new(400),
+            assets: vec![native_asset_info("uairdrop".to_string())],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateAirdrop {
+            snapshot_height: 12345,
+            merkle_root: merkle_root.clone(),
+            total_ustake: Uint128::new(400),
+            assets: vec![mock_utoken()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::SwapFromNotAllowed("utoken".to_string()));
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateAirdrop {
+            snapshot_height: 12345,
+            merkle_root: merkle_root.clone(),
+            total_ustake: Uint128::new(400),
+            assets: vec![native_asset_info("uairdrop".to_string())],
+        },
+    )
+    .unwrap();
+
+    let airdrop: Airdrop = query_helper(
+        deps.as_ref(),
+        QueryMsg::Airdrop {
+            id: 1,
+        },
+    );
+    assert_eq!(
+        airdrop,
+        Airdrop {
+            id: 1,
+            snapshot_height: 12345,
+            merkle_root,
+            total_ustake: Uint128::new(400),
+            assets: vec![native_asset("uairdrop".to_string(), Uint128::new(1001))],
+            claimed: vec![native_asset("uairdrop".to_string(), Uint128::zero())],
+            ustake_snapshot: None,
+        }
+    );
+
+    // the locked balance can't be transferred, but the remaining assets can
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: vec![ClaimType::Transfer {
+                token: native_asset_info("uairdrop".to_string()),
+                recipient: "receiver".to_string(),
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        native_asset("uairdrop".to_string(), Uint128::zero())
+            .transfer_msg(&deps.api.addr_validate("receiver").unwrap())
+            .unwrap()
+    );
+
+    // a wrong amount doesn't match the proof
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("user1", &[]),
+        ExecuteMsg::ClaimAirdrop {
+            id: 1,
+            ustake: Uint128::new(400),
+            proof: vec![hex::encode(user2)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::InvalidMerkleProof {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("user1", &[]),
+        ExecuteMsg::ClaimAirdrop {
+            id: 1,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        native_asset("uairdrop".to_string(), Uint128::new(750))
+            .transfer_msg(&deps.api.addr_validate("user1").unwrap())
+            .unwrap()
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("user1", &[]),
+        ExecuteMsg::ClaimAirdrop {
+            id: 1,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::AirdropAlreadyClaimed(1));
+
+    let claimed: bool = query_helper(
+        deps.as_ref(),
+        QueryMsg::AirdropClaimed {
+            id: 1,
+            address: "user1".to_string(),
+        },
+    );
+    assert!(claimed);
+
+    let claimed: bool = query_helper(
+        deps.as_ref(),
+        QueryMsg::AirdropClaimed {
+            id: 1,
+            address: "user2".to_string(),
+        },
+    );
+    assert!(!claimed);
+
+    // closing releases the unclaimed share
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::CloseAirdrop {
+            id: 1,
+        },
+    )
+    .unwrap();
+
+    let airdrops: Vec<Airdrop> = query_helper(
+        deps.as_ref(),
+        QueryMsg::Airdrops {
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(airdrops, vec![]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: vec![ClaimType::Transfer {
+                token: native_asset_info("uairdrop".to_string()),
+                recipient: "receiver".to_string(),
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        native_asset("uairdrop".to_string(), Uint128::new(1001))
+            .transfer_msg(&deps.api.addr_validate("receiver").unwrap())
+            .unwrap()
+    );
+}
+
+#[test]
+fn airdrops_checked_against_snapshot() {
+    let (mut deps, _) = setup_test();
+
+    // the merkle snapshot overstates the ustake of user1
+    let user1 = merkle_leaf("user1", 300);
+    let user2 = merkle_leaf("user2", 100);
+    let merkle_root = merkle_root(user1, user2);
+
+    State::default()
+        .ustake_snapshot
+        .save(deps.as_mut().storage, &Addr::unchecked("snapshot"))
+        .unwrap();
+    deps.querier.set_snapshot(&[("user1", 200), ("user2", 100)]);
+    deps.querier.set_bank_balances(&[coin(1001, "uairdrop")]);
+
+    let create = |snapshot_height: u64, total_ustake: u128| ExecuteMsg::CreateAirdrop {
+        snapshot_height,
+        merkle_root: merkle_root.clone(),
+        total_ustake: Uint128::new(total_ustake),
+        assets: vec![native_asset_info("uairdrop".to_string())],
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        create(12346, 300),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::SnapshotHeightInFuture(12346));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        create(12345, 400),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::UnexpectedTotalUstake(Uint128::new(300), Uint128::new(400)));
+
+    execute(deps.as_mut(), mock_env_at_timestamp(0), mock_info("owner", &[]), create(12345, 300))
+        .unwrap();
+
+    let airdrop: Airdrop = query_helper(
+        deps.as_ref(),
+        QueryMsg::Airdrop {
+            id: 1,
+        },
+    );
+    assert_eq!(airdrop.ustake_snapshot, Some(Addr::unchecked("snapshot")));
+
+    // 1001 * 200 / 300
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("user1", &[]),
+        ExecuteMsg::ClaimAirdrop {
+            id: 1,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("ustake", "200"));
+    assert_eq!(
+        res.messages[0].msg,
+        native_asset("uairdrop".to_string(), Uint128::new(667))
+            .transfer_msg(&deps.api.addr_validate("user1").unwrap())
+            .unwrap()
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("user2", &[]),
+        ExecuteMsg::ClaimAirdrop {
+            id: 1,
+            ustake: Uint128::new(100),
+            proof: vec![hex::encode(user1)],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        native_asset("uairdrop".to_string(), Uint128::new(333))
+            .transfer_msg(&deps.api.addr_validate("user2").unwrap())
+            .unwrap()
+    );
+}
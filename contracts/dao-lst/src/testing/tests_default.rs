@@ -1,6 +1,6 @@
 use std::ops::Sub;
 
-use astroport::asset::{native_asset_info, AssetInfoExt};
+use astroport::asset::{native_asset_info, token_asset_info, AssetInfoExt};
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     coin, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Event, Order, StdError,
@@ -8,13 +8,23 @@ use cosmwasm_std::{
 };
 use eris::DecimalCheckedOps;
 
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use eris::adapters::dao::{
+    CapaExecuteMsg, DaoDaoExecuteMsg, DaoDaoNftExecuteMsg, DaoDaoProposalMultipleExecuteMsg,
+    DaoDaoRewardsExecuteMsg, EnterpriseExecuteMsg, EnterpriseExecuteMsgV2, EnterpriseUnstakeMsgV2,
+    MultipleChoiceVote,
+};
+use eris::adapters::nft::{Cw721ExecuteMsg, Cw721ReceiveMsg};
 use eris::helper::validate_received_funds;
 use eris::hub::{
-    Batch, CallbackMsg, ConfigResponse, ExecuteMsg, FeeConfig, PendingBatch, QueryMsg, StakeToken,
-    StateResponse, UnbondRequest, UnbondRequestsByBatchResponseItem,
-    UnbondRequestsByUserResponseItem, UnbondRequestsByUserResponseItemDetails,
+    Batch, CallbackMsg, ConfigResponse, DaoInterface, ExecuteMsg, FeeConfig, HealthResponse,
+    HolderBallot, HolderProposal, HubVote, HubVoteOption, PendingBatch, QueryMsg, ReceiveMsg,
+    RewardSource, RewardVesting, StakeToken, StateResponse, UnbondRequest,
+    UnbondRequestsByBatchResponseItem, UnbondRequestsByUserResponseItem,
+    UnbondRequestsByUserResponseItemDetails,
 };
 
+use eris::ustake_snapshot::ExecuteMsg as SnapshotExecuteMsg;
 use eris_chain_shared::chain_trait::ChainInterface;
 
 use crate::contract::execute;
@@ -26,7 +36,9 @@ use crate::testing::helpers::{
 };
 use crate::testing::WithoutGeneric;
 
-use super::helpers::{mock_dependencies, mock_env_at_timestamp, query_helper};
+use super::helpers::{
+    merkle_leaf, merkle_root, mock_dependencies, mock_env_at_timestamp, query_helper,
+};
 
 //--------------------------------------------------------------------------------------------------
 // Execution
@@ -61,6 +73,8 @@ fn proper_instantiation() {
                 gov: Addr::unchecked("gov"),
                 fund_distributor: Addr::unchecked("fund"),
             },
+            reward_sources: vec![],
+            ustake_snapshot: None,
         }
     );
 
@@ -89,6 +103,59 @@ fn proper_instantiation() {
     );
 }
 
+#[test]
+fn reporting_to_ustake_snapshot() {
+    let (mut deps, _) = setup_test();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: None,
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: Some("snapshot".to_string()),
+        },
+    )
+    .unwrap();
+
+    let config: ConfigResponse = query_helper(deps.as_ref(), QueryMsg::Config {});
+    assert_eq!(config.ustake_snapshot, Some("snapshot".to_string()));
+
+    deps.querier.set_bank_balances(&[coin(1000000, MOCK_UTOKEN)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user_1", &[Coin::new(1000000, MOCK_UTOKEN)]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            donate: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages.last().unwrap().msg,
+        SnapshotExecuteMsg::Mint {
+            recipient: "user_1".to_string(),
+            amount: Uint128::new(1000000),
+        }
+        .into_msg(&Addr::unchecked("snapshot"))
+        .unwrap()
+    );
+}
+
 #[test]
 fn bonding() {
     let (mut deps, stake) = setup_test();
@@ -281,6 +348,9 @@ fn donating() {
             epoch_period: None,
             unbond_period: None,
             dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
         },
     )
     .unwrap();
@@ -324,709 +394,1173 @@ fn donating() {
 }
 
 #[test]
-fn harvesting() {
+fn bonding_dao_token_types() {
     let (mut deps, mut stake) = setup_test();
-    stake.total_supply = Uint128::new(1000000);
-    stake.total_utoken_bonded = Uint128::new(1000000);
+
+    // native token staked in an enterprise denom-staking-membership
+    stake.dao_interface = DaoInterface::EnterpriseV2 {
+        gov: Addr::unchecked("gov"),
+        membership: Addr::unchecked("membership"),
+        distributor: Addr::unchecked("distributor"),
+    };
     State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
     let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info("worker", &[]),
-        ExecuteMsg::Harvest {
-            stages: None,
-            withdrawals: None,
-            cw20_assets: None,
-            native_denoms: None,
-            router: None,
+        mock_info("user_1", &[Coin::new(1000, MOCK_UTOKEN)]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            donate: None,
         },
     )
     .unwrap();
-
-    assert_eq!(res.messages.len(), 3);
     assert_eq!(
-        vec![res.messages[0].msg.clone()],
-        stake.dao_interface.claim_rewards_msgs(&mock_env(), &stake.utoken, vec![], vec![]).unwrap()
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "membership".to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsgV2::Stake {
+                user: Some(MOCK_CONTRACT_ADDR.to_string()),
+            })
+            .unwrap(),
+            funds: vec![coin(1000, MOCK_UTOKEN)],
+        })
     );
-    assert_eq!(res.messages[1], check_received_coin(0, 0));
-
     assert_eq!(
-        res.messages[2],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::Reinvest {})).unwrap(),
-            funds: vec![]
-        }))
+        stake.dao_interface.unbond_msg(&stake.utoken, Uint128::new(100)).unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "membership".to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsgV2::Unstake(EnterpriseUnstakeMsgV2 {
+                amount: Uint128::new(100),
+            }))
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        stake.dao_interface.claim_unbonded_msg().unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "membership".to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsg::Claim {}).unwrap(),
+            funds: vec![],
+        })
     );
-}
 
-#[test]
-fn harvesting_with_balance() {
-    let (mut deps, mut stake) = setup_test();
-    stake.total_supply = Uint128::new(1000000);
-    stake.total_utoken_bonded = Uint128::new(1000000);
+    // native token staked in capa
+    stake.dao_interface = DaoInterface::Capa {
+        gov: Addr::unchecked("capa"),
+    };
     State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
-    deps.querier.set_bank_balances(&[coin(1000, MOCK_UTOKEN), coin(100, get_stake_full_denom())]);
-
     let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info("worker", &[]),
-        ExecuteMsg::Harvest {
-            stages: None,
-            withdrawals: None,
-            cw20_assets: None,
-            native_denoms: None,
-            router: None,
+        mock_info("user_1", &[Coin::new(1000, MOCK_UTOKEN)]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            donate: None,
         },
     )
     .unwrap();
-
-    assert_eq!(res.messages.len(), 3);
     assert_eq!(
-        vec![res.messages[0].msg.clone()],
-        stake.dao_interface.claim_rewards_msgs(&mock_env(), &stake.utoken, vec![], vec![]).unwrap()
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "capa".to_string(),
+            msg: to_json_binary(&CapaExecuteMsg::StakeVotingTokens {}).unwrap(),
+            funds: vec![coin(1000, MOCK_UTOKEN)],
+        })
     );
-    assert_eq!(res.messages[1], check_received_coin(1000, 100));
-
     assert_eq!(
-        res.messages[2],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::Reinvest {})).unwrap(),
-            funds: vec![]
-        }))
+        stake.dao_interface.unbond_msg(&stake.utoken, Uint128::new(100)).unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "capa".to_string(),
+            msg: to_json_binary(&CapaExecuteMsg::WithdrawVotingTokens {
+                amount: Some(Uint128::new(100)),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    // capa returns the tokens when withdrawing, there is nothing to claim
+    assert_eq!(
+        stake.dao_interface.claim_unbonded_msg().unwrap_err(),
+        StdError::generic_err("claiming not supported for capa")
     );
-}
 
-#[test]
-fn registering_unlocked_coins() {
-    let (mut deps, _) = setup_test();
-    let state = State::default();
-    deps.querier.set_bank_balances(&[coin(100 + 123, MOCK_UTOKEN)]);
+    // cw20 token staked in daodao
+    stake.utoken = token_asset_info(Addr::unchecked("dao_token"));
+    stake.dao_interface = DaoInterface::DaoDao {
+        staking: Addr::unchecked("staking"),
+        gov: Addr::unchecked("gov"),
+        gov_multiple: None,
+        cw_rewards: Addr::unchecked("cw_rewards"),
+    };
+    State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
+
     let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info(MOCK_CONTRACT_ADDR, &[]),
-        ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
-            snapshot: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(100u128),
-            snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
+        mock_info("dao_token", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "user_1".to_string(),
+            amount: Uint128::new(1000),
+            msg: to_json_binary(&ReceiveMsg::Bond {
+                receiver: None,
+                donate: None,
+            })
+            .unwrap(),
         }),
     )
     .unwrap();
     assert_eq!(
-        res.events,
-        vec![Event::new("erishub/received")
-            .add_attribute("received_coin", 123.to_string() + MOCK_UTOKEN)]
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "dao_token".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking".to_string(),
+                amount: Uint128::new(1000),
+                msg: to_json_binary(&DaoDaoExecuteMsg::Stake {}).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
     );
-    assert_eq!(res.messages.len(), 0);
-    // Unlocked coins in contract state should have been updated
-    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
     assert_eq!(
-        unlocked_coins,
-        vec![native_asset_info(MOCK_UTOKEN.to_string()).with_balance(123u128)]
+        stake.dao_interface.unbond_msg(&stake.utoken, Uint128::new(100)).unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking".to_string(),
+            msg: to_json_binary(&DaoDaoExecuteMsg::Unstake {
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        stake.dao_interface.claim_unbonded_msg().unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking".to_string(),
+            msg: to_json_binary(&DaoDaoExecuteMsg::Claim {}).unwrap(),
+            funds: vec![],
+        })
     );
 }
 
 #[test]
-fn registering_unlocked_stake_coins() -> StdResult<()> {
+fn bonding_and_unbonding_nfts() {
     let (mut deps, mut stake) = setup_test();
     let state = State::default();
 
-    stake.total_supply = Uint128::new(1000);
-    stake.total_utoken_bonded = Uint128::new(1000);
+    stake.utoken = token_asset_info(Addr::unchecked("collection"));
+    stake.dao_interface = DaoInterface::DaoDaoNft {
+        staking: Addr::unchecked("staking"),
+        gov: Addr::unchecked("gov"),
+        gov_multiple: None,
+        rewards: vec![],
+    };
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
-    state.stake_token.save(deps.as_mut().storage, &stake)?;
+    let bond_msg = |token_id: &str| {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "user_1".to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&ReceiveMsg::Bond {
+                receiver: None,
+                donate: None,
+            })
+            .unwrap(),
+        })
+    };
 
-    deps.querier
-        .set_bank_balances(&[coin(100 + 123, MOCK_UTOKEN), coin(100, get_stake_full_denom())]);
+    // only NFTs of the collection can be bonded
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("other", &[]), bond_msg("1")).unwrap_err();
+    assert_eq!(err, ContractError::ExpectingStakeToken("other".to_string()));
+
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("collection", &[]), bond_msg("7")).unwrap();
+    let mint_msgs = chain_test().create_mint_msgs(
+        get_stake_full_denom(),
+        Uint128::new(1000000),
+        Addr::unchecked("user_1"),
+    );
+    assert_eq!(res.messages.len(), 1 + mint_msgs.len());
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "collection".to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::SendNft {
+                contract: "staking".to_string(),
+                token_id: "7".to_string(),
+                msg: Default::default(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    execute(deps.as_mut(), mock_env(), mock_info("collection", &[]), bond_msg("3")).unwrap();
+
+    let stake = state.stake_token.load(deps.as_ref().storage).unwrap();
+    assert_eq!(stake.total_utoken_bonded, Uint128::new(2));
+    assert_eq!(stake.total_supply, Uint128::new(2000000));
+
+    // unbonding the shares of one NFT unbonds the NFT with the lowest token id
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(12345),
+        mock_info("user_1", &[Coin::new(1000000, get_stake_full_denom())]),
+        ExecuteMsg::QueueUnbond {
+            receiver: None,
+        },
+    )
+    .unwrap();
 
     let res = execute(
         deps.as_mut(),
-        mock_env(),
+        mock_env_at_timestamp(269201),
         mock_info(MOCK_CONTRACT_ADDR, &[]),
-        ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
-            snapshot: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(100u128),
-            snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
-        }),
+        ExecuteMsg::SubmitBatch {},
     )
     .unwrap();
     assert_eq!(
-        res.events,
-        vec![Event::new("erishub/received")
-            .add_attribute("received_coin", 123.to_string() + MOCK_UTOKEN)
-            .add_attribute("received_coin", 100.to_string() + &get_stake_full_denom())]
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking".to_string(),
+            msg: to_json_binary(&DaoDaoNftExecuteMsg::Unstake {
+                token_ids: vec!["3".to_string()],
+            })
+            .unwrap(),
+            funds: vec![],
+        })
     );
 
-    assert_eq!(res.messages.len(), 0);
+    let token_ids: Vec<String> = query_helper(deps.as_ref(), QueryMsg::BatchTokenIds(1));
+    assert_eq!(token_ids, vec!["3".to_string()]);
 
-    // Unlocked coins in contract state should have been updated
-    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
+    // NFTs can't be checked through a balance query, only claiming them
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(2083602),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Reconcile {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
     assert_eq!(
-        unlocked_coins,
-        vec![
-            native_asset_info(MOCK_UTOKEN.to_string()).with_balance(123u128),
-            native_asset_info(get_stake_full_denom()).with_balance(100u128)
-        ]
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking".to_string(),
+            msg: to_json_binary(&DaoDaoNftExecuteMsg::ClaimNfts {}).unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let res: StateResponse = query_helper_env(deps.as_ref(), QueryMsg::State {}, 2083602);
+    assert_eq!(res.available, Uint128::new(1));
+    assert_eq!(res.tvl_utoken, Uint128::new(2));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(2083602),
+        mock_info("user_1", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "collection".to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: "user_1".to_string(),
+                token_id: "3".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let token_ids: Vec<String> = query_helper(deps.as_ref(), QueryMsg::BatchTokenIds(1));
+    assert!(token_ids.is_empty());
+    assert_eq!(
+        state
+            .staked_nfts
+            .keys(deps.as_ref().storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap(),
+        vec!["7".to_string()]
     );
-    Ok(())
 }
 
 #[test]
-fn reinvesting() {
+fn harvesting_nfts() {
     let (mut deps, mut stake) = setup_test();
     let state = State::default();
 
-    stake.total_supply = Uint128::new(1000000);
-    stake.total_utoken_bonded = Uint128::new(1000000);
-
+    stake.utoken = token_asset_info(Addr::unchecked("collection"));
+    stake.dao_interface = DaoInterface::DaoDaoNft {
+        staking: Addr::unchecked("staking"),
+        gov: Addr::unchecked("gov"),
+        gov_multiple: None,
+        rewards: vec![(Addr::unchecked("distributor"), 2)],
+    };
+    stake.total_supply = Uint128::new(1000000000);
+    stake.total_utoken_bonded = Uint128::new(1000);
     state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
-    // After the swaps, `unlocked_coins` should contain only utoken and unknown denoms
-    state
-        .unlocked_coins
-        .save(
-            deps.as_mut().storage,
-            &vec![
-                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(234u128),
-                native_asset_info(get_stake_full_denom()).with_balance(69420u128),
-                native_asset_info("unknown".to_string()).with_balance(123u128),
-            ],
-        )
-        .unwrap();
-
-    set_total_stake_supply(&state, &mut deps, 100000);
-
-    // Bob has the smallest amount of delegations, so all proceeds go to him
     let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info(MOCK_CONTRACT_ADDR, &[]),
-        ExecuteMsg::Callback(CallbackMsg::Reinvest {}),
+        mock_info("worker", &[]),
+        ExecuteMsg::Harvest {
+            native_denoms: None,
+            cw20_assets: None,
+            withdrawals: None,
+            stages: None,
+            router: None,
+        },
     )
     .unwrap();
 
-    assert_eq!(res.messages.len(), 4);
-
-    let total = Uint128::from(234u128);
-    let fee =
-        Decimal::from_ratio(1u128, 100u128).checked_mul_uint(total).expect("expects fee result");
-    let delegated = total.saturating_sub(fee);
-
+    // rewards are claimed, but the collection is never queried for a balance
+    assert_eq!(res.messages.len(), 3);
     assert_eq!(
         res.messages[0].msg,
-        stake
-            .dao_interface
-            .deposit_msg(&stake.utoken, delegated, MOCK_CONTRACT_ADDR.to_string())
-            .unwrap()
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "distributor".to_string(),
+            msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                id: 2,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
     );
     assert_eq!(
-        res.messages[1].msg.without_generic(),
-        native_asset_info(MOCK_UTOKEN.to_string()).with_balance(fee).into_msg("fee").unwrap()
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
+                snapshot: stake.utoken.with_balance(0u128),
+                snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
+            }))
+            .unwrap(),
+            funds: vec![],
+        }))
     );
 
-    let total = Uint128::from(69420u128);
-    let fee =
-        Decimal::from_ratio(1u128, 100u128).checked_mul_uint(total).expect("expects fee result");
-    let burnt = total.saturating_sub(fee);
-
-    assert_eq!(res.messages[2].msg, chain_test().create_burn_msg(get_stake_full_denom(), burnt));
+    // the rewards were swapped into ustake
+    deps.querier.set_bank_balances(&[coin(100000000, get_stake_full_denom())]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
+            snapshot: stake.utoken.with_balance(0u128),
+            snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
+        }),
+    )
+    .unwrap();
     assert_eq!(
-        res.messages[3].msg.without_generic(),
-        native_asset_info(get_stake_full_denom()).with_balance(fee).into_msg("fee").unwrap()
+        res.events,
+        vec![Event::new("erishub/received")
+            .add_attribute("received_coin", 100000000.to_string() + &get_stake_full_denom())]
     );
 
+    // the ustake is burned instead of bonding utoken, increasing the NFTs per share
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::Reinvest {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
     assert_eq!(
-        state.stake_token.load(deps.as_ref().storage).unwrap().total_supply,
-        Uint128::new(100000 - burnt.u128())
+        res.messages[0].msg,
+        chain_test().create_burn_msg(get_stake_full_denom(), Uint128::new(99000000))
+    );
+    assert_eq!(
+        res.messages[1].msg.without_generic(),
+        native_asset_info(get_stake_full_denom())
+            .with_balance(1000000u128)
+            .into_msg("fee")
+            .unwrap()
     );
 
-    // Storage should have been updated
-    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
+    let mut stake = state.stake_token.load(deps.as_ref().storage).unwrap();
+    assert_eq!(stake.total_supply, Uint128::new(901000000));
+    assert_eq!(stake.total_utoken_bonded, Uint128::new(1000));
+
+    // NFTs bonded afterwards mint fewer shares
+    let bond_msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: "user_1".to_string(),
+        token_id: "1001".to_string(),
+        msg: to_json_binary(&ReceiveMsg::Bond {
+            receiver: None,
+            donate: None,
+        })
+        .unwrap(),
+    });
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("collection", &[]), bond_msg.clone()).unwrap();
     assert_eq!(
-        unlocked_coins,
-        vec![native_asset_info("unknown".to_string()).with_balance(123u128)]
+        res.events[0],
+        Event::new("erishub/bonded")
+            .add_attribute("receiver", "user_1")
+            .add_attribute("token_id", "1001")
+            .add_attribute("token_bonded", "1")
+            .add_attribute("ustake_minted", "901000")
     );
+
+    let stake_after = state.stake_token.load(deps.as_ref().storage).unwrap();
+    assert_eq!(stake_after.total_supply, Uint128::new(901901000));
+    assert_eq!(stake_after.total_utoken_bonded, Uint128::new(1001));
+
+    // an NFT is never taken without minting shares
+    stake.total_supply = Uint128::new(901);
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("collection", &[]), bond_msg).unwrap_err();
+    assert_eq!(err, ContractError::CantBeZero("ustake to mint".into()));
 }
 
 #[test]
-fn queuing_unbond() {
-    let (mut deps, _) = setup_test();
+fn withdrawing_remaining_nfts() {
+    let (mut deps, mut stake) = setup_test();
     let state = State::default();
 
-    // Only Stake token is accepted for unbonding requests
-    let err = execute(
+    stake.utoken = token_asset_info(Addr::unchecked("collection"));
+    stake.dao_interface = DaoInterface::DaoDaoNft {
+        staking: Addr::unchecked("staking"),
+        gov: Addr::unchecked("gov"),
+        gov_multiple: None,
+        rewards: vec![],
+    };
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
+
+    // three NFTs are unbonded for two users holding half of the shares each
+    state
+        .previous_batches
+        .save(
+            deps.as_mut().storage,
+            1,
+            &Batch {
+                id: 1,
+                reconciled: true,
+                total_shares: Uint128::new(3000000),
+                utoken_unclaimed: Uint128::new(3),
+                est_unbond_end_time: 10000,
+            },
+        )
+        .unwrap();
+    state
+        .batch_token_ids
+        .save(deps.as_mut().storage, 1, &vec!["1".to_string(), "2".to_string(), "3".to_string()])
+        .unwrap();
+    for user in ["user_1", "user_2"] {
+        state
+            .unbond_requests
+            .save(
+                deps.as_mut().storage,
+                (1, &Addr::unchecked(user)),
+                &UnbondRequest {
+                    id: 1,
+                    user: Addr::unchecked(user),
+                    shares: Uint128::new(1500000),
+                },
+            )
+            .unwrap();
+    }
+
+    let transfer = |recipient: &str, token_id: &str| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "collection".to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+
+    // the share of the first withdrawer is rounded down
+    let res = execute(
         deps.as_mut(),
-        mock_env(),
-        mock_info("random_sender", &[Coin::new(100, "random_token")]),
-        ExecuteMsg::QueueUnbond {
+        mock_env_at_timestamp(20000),
+        mock_info("user_1", &[]),
+        ExecuteMsg::WithdrawUnbonded {
             receiver: None,
         },
     )
-    .unwrap_err();
-
-    assert_eq!(err, ContractError::ExpectingStakeToken("random_token".into()));
+    .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+        vec![transfer("user_1", "1")]
+    );
 
-    // User 1 creates an unbonding request before `est_unbond_start_time` is reached. The unbond
-    // request is saved, but not the pending batch is not submitted for unbonding
+    // the last withdrawer receives the remaining NFTs
     let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(12345), // est_unbond_start_time = 269200
-        mock_info("user_1", &[Coin::new(23456, get_stake_full_denom())]),
-        ExecuteMsg::QueueUnbond {
+        mock_env_at_timestamp(20000),
+        mock_info("user_2", &[]),
+        ExecuteMsg::WithdrawUnbonded {
             receiver: None,
         },
     )
     .unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+        vec![transfer("user_2", "2"), transfer("user_2", "3")]
+    );
 
-    assert_eq!(res.messages.len(), 0);
+    assert!(!state.previous_batches.has(deps.as_ref().storage, 1));
+    assert!(!state.batch_token_ids.has(deps.as_ref().storage, 1));
+}
+
+#[test]
+fn harvesting() {
+    let (mut deps, mut stake) = setup_test();
+    stake.total_supply = Uint128::new(1000000);
+    stake.total_utoken_bonded = Uint128::new(1000000);
+    State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
-    // User 2 creates an unbonding request after `est_unbond_start_time` is reached. The unbond
-    // request is saved, and the pending is automatically submitted for unbonding
     let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(269201), // est_unbond_start_time = 269200
-        mock_info("user_2", &[Coin::new(69420, get_stake_full_denom())]),
-        ExecuteMsg::QueueUnbond {
-            receiver: Some("user_3".to_string()),
+        mock_env(),
+        mock_info("worker", &[]),
+        ExecuteMsg::Harvest {
+            stages: None,
+            withdrawals: None,
+            cw20_assets: None,
+            native_denoms: None,
+            router: None,
         },
     )
     .unwrap();
 
-    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages.len(), 3);
     assert_eq!(
-        res.messages[0],
+        vec![res.messages[0].msg.clone()],
+        stake.dao_interface.claim_rewards_msgs(&mock_env(), &stake.utoken, vec![], vec![]).unwrap()
+    );
+    assert_eq!(res.messages[1], check_received_coin(0, 0));
+
+    assert_eq!(
+        res.messages[2],
         SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: MOCK_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&ExecuteMsg::SubmitBatch {}).unwrap(),
+            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::Reinvest {})).unwrap(),
             funds: vec![]
         }))
     );
+}
 
-    // The users' unbonding requests should have been saved
-    let ubr1 = state
-        .unbond_requests
-        .load(deps.as_ref().storage, (1u64, &Addr::unchecked("user_1")))
-        .unwrap();
-    let ubr2 = state
-        .unbond_requests
-        .load(deps.as_ref().storage, (1u64, &Addr::unchecked("user_3")))
-        .unwrap();
+#[test]
+fn harvesting_with_balance() {
+    let (mut deps, mut stake) = setup_test();
+    stake.total_supply = Uint128::new(1000000);
+    stake.total_utoken_bonded = Uint128::new(1000000);
+    State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
+    deps.querier.set_bank_balances(&[coin(1000, MOCK_UTOKEN), coin(100, get_stake_full_denom())]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("worker", &[]),
+        ExecuteMsg::Harvest {
+            stages: None,
+            withdrawals: None,
+            cw20_assets: None,
+            native_denoms: None,
+            router: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 3);
     assert_eq!(
-        ubr1,
-        UnbondRequest {
-            id: 1,
-            user: Addr::unchecked("user_1"),
-            shares: Uint128::new(23456)
-        }
-    );
-    assert_eq!(
-        ubr2,
-        UnbondRequest {
-            id: 1,
-            user: Addr::unchecked("user_3"),
-            shares: Uint128::new(69420)
-        }
+        vec![res.messages[0].msg.clone()],
+        stake.dao_interface.claim_rewards_msgs(&mock_env(), &stake.utoken, vec![], vec![]).unwrap()
     );
+    assert_eq!(res.messages[1], check_received_coin(1000, 100));
 
-    // Pending batch should have been updated
-    let pending_batch = state.pending_batch.load(deps.as_ref().storage).unwrap();
     assert_eq!(
-        pending_batch,
-        PendingBatch {
-            id: 1,
-            ustake_to_burn: Uint128::new(92876), // 23,456 + 69,420
-            est_unbond_start_time: 269200
-        }
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::Reinvest {})).unwrap(),
+            funds: vec![]
+        }))
     );
 }
 
 #[test]
-fn submitting_batch() {
-    let (mut deps, mut stake) = setup_test();
-    let state = State::default();
-
-    // utoken bonded: 1,037,345
-    // ustake supply: 1,012,043
-    // utoken per ustake: 1.025
-    stake.total_utoken_bonded = Uint128::new(1037345);
-    stake.total_supply = Uint128::new(1012043);
-    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
+fn harvesting_with_reward_sources() {
+    let (mut deps, stake) = setup_test();
 
-    // We continue from the contract state at the end of the last test
-    let unbond_requests = vec![
-        UnbondRequest {
-            id: 1,
-            user: Addr::unchecked("user_1"),
-            shares: Uint128::new(23456),
+    let reward_sources = vec![
+        RewardSource::DaoRewardsDistributor {
+            contract: "distributor".to_string(),
+            id: 2,
         },
-        UnbondRequest {
-            id: 1,
-            user: Addr::unchecked("user_3"),
-            shares: Uint128::new(69420),
+        RewardSource::Generic {
+            contract: "airdrop".to_string(),
+            msg: to_json_binary(&"claim").unwrap(),
         },
     ];
+    let update_msg = ExecuteMsg::UpdateConfig {
+        protocol_fee_contract: None,
+        protocol_reward_fee: None,
+        operator: None,
+        stages_preset: None,
+        withdrawals_preset: None,
+        allow_donations: None,
+        vote_operator: None,
+        default_max_spread: None,
+        epoch_period: None,
+        unbond_period: None,
+        dao_interface: None,
+        reward_sources: Some(reward_sources.clone()),
+        reward_vesting_period: None,
+        ustake_snapshot: None,
+    };
 
-    for unbond_request in &unbond_requests {
-        state
-            .unbond_requests
-            .save(
-                deps.as_mut().storage,
-                (unbond_request.id, &Addr::unchecked(unbond_request.user.clone())),
-                unbond_request,
-            )
-            .unwrap();
-    }
+    let err =
+        execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), update_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    state
-        .pending_batch
-        .save(
-            deps.as_mut().storage,
-            &PendingBatch {
-                id: 1,
-                ustake_to_burn: Uint128::new(92876), // 23,456 + 69,420
-                est_unbond_start_time: 269200,
-            },
-        )
-        .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), update_msg).unwrap();
+
+    let reward_sources = reward_sources
+        .iter()
+        .map(|source| source.validate(deps.as_ref().api).unwrap())
+        .collect::<Vec<_>>();
+    let res: ConfigResponse = query_helper(deps.as_ref(), QueryMsg::Config {});
+    assert_eq!(res.reward_sources, reward_sources);
 
-    // Anyone can invoke `submit_batch`. Here we continue from the previous test and assume it is
-    // invoked automatically as user 2 submits the unbonding request
-    //
-    // ustake to burn: 23,456 + 69,420 = 92,876
-    // utoken to unbond: 1,037,345 * 92,876 / 1,012,043 = 95,197
-    //
-    // Target: (1,037,345 - 95,197) / 3 = 314,049
-    // Remainer: 1
-    // Alice:   345,782 - (314,049 + 1) = 31,732
-    // Bob:     345,782 - (314,049 + 0) = 31,733
-    // Charlie: 345,781 - (314,049 + 0) = 31,732
     let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(269201),
-        mock_info(MOCK_CONTRACT_ADDR, &[]),
-        ExecuteMsg::SubmitBatch {},
+        mock_env(),
+        mock_info("worker", &[]),
+        ExecuteMsg::Harvest {
+            stages: None,
+            withdrawals: None,
+            cw20_assets: None,
+            native_denoms: None,
+            router: None,
+        },
     )
     .unwrap();
 
-    assert_eq!(res.messages.len(), 2);
-
+    // rewards are claimed from the DAO first and then from each reward source
+    assert_eq!(res.messages.len(), 5);
     assert_eq!(
-        res.messages[0].msg,
-        stake.dao_interface.unbond_msg(&stake.utoken, Uint128::new(95197)).unwrap()
+        vec![res.messages[0].msg.clone()],
+        stake.dao_interface.claim_rewards_msgs(&mock_env(), &stake.utoken, vec![], vec![]).unwrap()
     );
-
     assert_eq!(
         res.messages[1].msg,
-        chain_test().create_burn_msg(get_stake_full_denom(), Uint128::new(92876))
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "distributor".to_string(),
+            msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                id: 2,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
     );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "airdrop".to_string(),
+            msg: to_json_binary(&"claim").unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(res.messages[3], check_received_coin(0, 0));
+}
 
-    // A new pending batch should have been created
-    let pending_batch = state.pending_batch.load(deps.as_ref().storage).unwrap();
+#[test]
+fn registering_unlocked_coins() {
+    let (mut deps, _) = setup_test();
+    let state = State::default();
+    deps.querier.set_bank_balances(&[coin(100 + 123, MOCK_UTOKEN)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
+            snapshot: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(100u128),
+            snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
+        }),
+    )
+    .unwrap();
     assert_eq!(
-        pending_batch,
-        PendingBatch {
-            id: 2,
-            ustake_to_burn: Uint128::zero(),
-            est_unbond_start_time: 528401 // 269,201 + 259,200
-        }
+        res.events,
+        vec![Event::new("erishub/received")
+            .add_attribute("received_coin", 123.to_string() + MOCK_UTOKEN)]
+    );
+    assert_eq!(res.messages.len(), 0);
+    // Unlocked coins in contract state should have been updated
+    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        unlocked_coins,
+        vec![native_asset_info(MOCK_UTOKEN.to_string()).with_balance(123u128)]
     );
+}
 
-    // Previous batch should have been updated
-    let previous_batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
+#[test]
+fn registering_unlocked_stake_coins() -> StdResult<()> {
+    let (mut deps, mut stake) = setup_test();
+    let state = State::default();
+
+    stake.total_supply = Uint128::new(1000);
+    stake.total_utoken_bonded = Uint128::new(1000);
+
+    state.stake_token.save(deps.as_mut().storage, &stake)?;
+
+    deps.querier
+        .set_bank_balances(&[coin(100 + 123, MOCK_UTOKEN), coin(100, get_stake_full_denom())]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::CheckReceivedCoin {
+            snapshot: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(100u128),
+            snapshot_stake: native_asset_info(get_stake_full_denom()).with_balance(0u128),
+        }),
+    )
+    .unwrap();
     assert_eq!(
-        previous_batch,
-        Batch {
-            id: 1,
-            reconciled: false,
-            total_shares: Uint128::new(92876),
-            utoken_unclaimed: Uint128::new(95197),
-            est_unbond_end_time: 2083601 // 269,201 + 1,814,400
-        }
+        res.events,
+        vec![Event::new("erishub/received")
+            .add_attribute("received_coin", 123.to_string() + MOCK_UTOKEN)
+            .add_attribute("received_coin", 100.to_string() + &get_stake_full_denom())]
     );
 
-    let res: StateResponse = query_helper_env(deps.as_ref(), QueryMsg::State {}, 2083600);
+    assert_eq!(res.messages.len(), 0);
 
-    let total_ustake = Uint128::from(1012043u128).sub(Uint128::new(23456)).sub(Uint128::new(69420));
+    // Unlocked coins in contract state should have been updated
+    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
     assert_eq!(
-        res,
-        StateResponse {
-            total_ustake,
-            total_utoken: Uint128::from(1037345u128 - 95197u128),
-            exchange_rate: Decimal::from_ratio(1037345u128 - 95197u128, total_ustake.u128()),
-            unlocked_coins: vec![],
-            unbonding: Uint128::from(95197u128),
-            available: Uint128::zero(),
-            tvl_utoken: Uint128::from(1037345u128),
-        },
+        unlocked_coins,
+        vec![
+            native_asset_info(MOCK_UTOKEN.to_string()).with_balance(123u128),
+            native_asset_info(get_stake_full_denom()).with_balance(100u128)
+        ]
     );
+    Ok(())
 }
 
 #[test]
-fn reconciling() {
-    let (mut deps, stake) = setup_test();
+fn reinvesting() {
+    let (mut deps, mut stake) = setup_test();
     let state = State::default();
 
-    let previous_batches = vec![
-        Batch {
-            id: 1,
-            reconciled: true,
-            total_shares: Uint128::new(92876),
-            utoken_unclaimed: Uint128::new(95197), // 1.025 Token per Stake
-            est_unbond_end_time: 10000,
-        },
-        Batch {
-            id: 2,
-            reconciled: false,
-            total_shares: Uint128::new(1345),
-            utoken_unclaimed: Uint128::new(1385), // 1.030 Token per Stake
-            est_unbond_end_time: 20000,
-        },
-        Batch {
-            id: 3,
-            reconciled: false,
-            total_shares: Uint128::new(1456),
-            utoken_unclaimed: Uint128::new(1506), // 1.035 Token per Stake
-            est_unbond_end_time: 30000,
-        },
-        Batch {
-            id: 4,
-            reconciled: false,
-            total_shares: Uint128::new(1567),
-            utoken_unclaimed: Uint128::new(1629), // 1.040 Token per Stake
-            est_unbond_end_time: 40000,           // not yet finished unbonding, ignored
-        },
-    ];
+    stake.total_supply = Uint128::new(1000000);
+    stake.total_utoken_bonded = Uint128::new(1000000);
 
-    for previous_batch in &previous_batches {
-        state
-            .previous_batches
-            .save(deps.as_mut().storage, previous_batch.id, previous_batch)
-            .unwrap();
-    }
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
+    // After the swaps, `unlocked_coins` should contain only utoken and unknown denoms
     state
         .unlocked_coins
         .save(
             deps.as_mut().storage,
             &vec![
-                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(10000u128),
-                native_asset_info("ukrw".to_string()).with_balance(234u128),
-                native_asset_info("uusd".to_string()).with_balance(345u128),
-                native_asset_info(
-                    "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"
-                        .to_string(),
-                )
-                .with_balance(69420u128),
+                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(234u128),
+                native_asset_info(get_stake_full_denom()).with_balance(69420u128),
+                native_asset_info("unknown".to_string()).with_balance(123u128),
             ],
         )
         .unwrap();
 
-    deps.querier.set_bank_balances(&[
-        Coin::new(10000, MOCK_UTOKEN),
-        Coin::new(234, "ukrw"),
-        Coin::new(345, "uusd"),
-        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
-    ]);
+    set_total_stake_supply(&state, &mut deps, 100000);
 
+    // Bob has the smallest amount of delegations, so all proceeds go to him
     let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(35000),
-        mock_info("worker", &[]),
-        ExecuteMsg::Reconcile {},
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::Reinvest {}),
     )
     .unwrap();
 
-    let assert_balance_msg = ExecuteMsg::Callback(CallbackMsg::AssertBalance {
-        expected: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(12891u128),
-    });
+    assert_eq!(res.messages.len(), 4);
+
+    let total = Uint128::from(234u128);
+    let fee =
+        Decimal::from_ratio(1u128, 100u128).checked_mul_uint(total).expect("expects fee result");
+    let delegated = total.saturating_sub(fee);
 
-    assert_eq!(res.messages.len(), 2);
-    assert_eq!(res.messages[0].msg, stake.dao_interface.claim_unbonded_msg().unwrap());
     assert_eq!(
-        res.messages[1],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&assert_balance_msg).unwrap(),
-            funds: vec![]
-        }))
+        res.messages[0].msg,
+        stake
+            .dao_interface
+            .deposit_msg(&stake.utoken, delegated, MOCK_CONTRACT_ADDR.to_string())
+            .unwrap()
     );
-
-    // Expected received: batch 2 + batch 3 = 1385 + 1506 = 2891
-    // Expected unlocked: 10000
-    // Expected: 12891
-    let batch = state.previous_batches.load(deps.as_ref().storage, 2u64).unwrap();
     assert_eq!(
-        batch,
-        Batch {
-            id: 2,
-            reconciled: true,
-            total_shares: Uint128::new(1345),
-            utoken_unclaimed: Uint128::new(1385),
-            est_unbond_end_time: 20000,
-        }
+        res.messages[1].msg.without_generic(),
+        native_asset_info(MOCK_UTOKEN.to_string()).with_balance(fee).into_msg("fee").unwrap()
     );
 
-    let batch = state.previous_batches.load(deps.as_ref().storage, 3u64).unwrap();
+    let total = Uint128::from(69420u128);
+    let fee =
+        Decimal::from_ratio(1u128, 100u128).checked_mul_uint(total).expect("expects fee result");
+    let burnt = total.saturating_sub(fee);
+
+    assert_eq!(res.messages[2].msg, chain_test().create_burn_msg(get_stake_full_denom(), burnt));
     assert_eq!(
-        batch,
-        Batch {
-            id: 3,
-            reconciled: true,
-            total_shares: Uint128::new(1456),
-            utoken_unclaimed: Uint128::new(1506),
-            est_unbond_end_time: 30000,
-        }
+        res.messages[3].msg.without_generic(),
+        native_asset_info(get_stake_full_denom()).with_balance(fee).into_msg("fee").unwrap()
     );
 
-    // Batches 1 and 4 should not have changed
-    let batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
-    assert_eq!(batch, previous_batches[0]);
+    assert_eq!(
+        state.stake_token.load(deps.as_ref().storage).unwrap().total_supply,
+        Uint128::new(100000 - burnt.u128())
+    );
 
-    let batch = state.previous_batches.load(deps.as_ref().storage, 4u64).unwrap();
-    assert_eq!(batch, previous_batches[3]);
+    // Storage should have been updated
+    let unlocked_coins = state.unlocked_coins.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        unlocked_coins,
+        vec![native_asset_info("unknown".to_string()).with_balance(123u128)]
+    );
+}
 
-    deps.querier.set_bank_balances(&[
-        Coin::new(12345, MOCK_UTOKEN),
-        Coin::new(234, "ukrw"),
-        Coin::new(345, "uusd"),
-        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
-    ]);
+#[test]
+fn reinvesting_with_vesting() {
+    let (mut deps, mut stake) = setup_test();
+    let state = State::default();
+
+    stake.total_supply = Uint128::new(1000000);
+    stake.total_utoken_bonded = Uint128::new(1000000);
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
 
     execute(
         deps.as_mut(),
-        mock_env_at_timestamp(35000),
-        mock_info("worker", &[]),
-        assert_balance_msg.clone(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            allow_donations: None,
+            vote_operator: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: Some(1000),
+            ustake_snapshot: None,
+        },
     )
-    .expect_err("Should be self called");
+    .unwrap();
 
-    execute(
+    state
+        .unlocked_coins
+        .save(
+            deps.as_mut().storage,
+            &vec![native_asset_info(MOCK_UTOKEN.to_string()).with_balance(10000u128)],
+        )
+        .unwrap();
+
+    // 10,000 - 1% fee = 9,900 are staked, but not yet part of the exchange rate
+    let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(35000),
+        mock_env_at_timestamp(10000),
         mock_info(MOCK_CONTRACT_ADDR, &[]),
-        assert_balance_msg.clone(),
+        ExecuteMsg::Callback(CallbackMsg::Reinvest {}),
     )
-    .expect_err("Not enough received");
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        stake
+            .dao_interface
+            .deposit_msg(&stake.utoken, Uint128::new(9900), MOCK_CONTRACT_ADDR.to_string())
+            .unwrap()
+    );
+    assert_eq!(
+        state.stake_token.load(deps.as_ref().storage).unwrap().total_utoken_bonded,
+        Uint128::new(1000000)
+    );
 
-    deps.querier.set_bank_balances(&[
-        Coin::new(12891, MOCK_UTOKEN),
-        Coin::new(234, "ukrw"),
-        Coin::new(345, "uusd"),
-        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
-    ]);
+    let res: RewardVesting = query_helper_env(deps.as_ref(), QueryMsg::RewardVesting {}, 10000);
+    assert_eq!(
+        res,
+        RewardVesting {
+            period: 1000,
+            pending: Uint128::new(9900),
+            start_time: 10000,
+            end_time: 11000,
+        }
+    );
 
-    execute(
+    // half of the rewards are released after half of the period
+    let res: StateResponse = query_helper_env(deps.as_ref(), QueryMsg::State {}, 10500);
+    assert_eq!(res.total_utoken, Uint128::new(1004950));
+    assert_eq!(res.exchange_rate, Decimal::from_ratio(1004950u128, 1000000u128));
+    assert_eq!(res.tvl_utoken, Uint128::new(1009900));
+
+    // bonding uses the exchange rate including the vested rewards
+    let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(35000),
-        mock_info(MOCK_CONTRACT_ADDR, &[]),
-        assert_balance_msg,
+        mock_env_at_timestamp(10500),
+        mock_info("user_1", &[Coin::new(1004950, MOCK_UTOKEN)]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            donate: None,
+        },
     )
-    .expect("Should succeed");
+    .unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        chain_test().create_mint_msgs(
+            get_stake_full_denom(),
+            Uint128::new(1000000),
+            Addr::unchecked("user_1"),
+        )[0]
+    );
+
+    let stake = state.stake_token.load(deps.as_ref().storage).unwrap();
+    assert_eq!(stake.total_utoken_bonded, Uint128::new(2009900));
+    assert_eq!(stake.total_supply, Uint128::new(2000000));
+
+    let res: StateResponse = query_helper_env(deps.as_ref(), QueryMsg::State {}, 11000);
+    assert_eq!(res.total_utoken, Uint128::new(2014850));
+    assert_eq!(res.tvl_utoken, Uint128::new(2014850));
 }
 
 #[test]
-fn reconciling_even_when_everything_ok() {
+fn queuing_unbond() {
     let (mut deps, _) = setup_test();
     let state = State::default();
 
-    let previous_batches = vec![
-        Batch {
-            id: 1,
-            reconciled: true,
-            total_shares: Uint128::new(100000),
-            utoken_unclaimed: Uint128::new(100000),
-            est_unbond_end_time: 10000,
-        },
-        Batch {
-            id: 2,
-            reconciled: false,
-            total_shares: Uint128::new(1000),
-            utoken_unclaimed: Uint128::new(1000),
-            est_unbond_end_time: 20000,
-        },
-        Batch {
-            id: 3,
-            reconciled: false,
-            total_shares: Uint128::new(1500),
-            utoken_unclaimed: Uint128::new(1500),
-            est_unbond_end_time: 30000,
-        },
-        Batch {
-            id: 4,
-            reconciled: false,
-            total_shares: Uint128::new(1500),
-            utoken_unclaimed: Uint128::new(1500),
-            est_unbond_end_time: 40000, // not yet finished unbonding, ignored
+    // Only Stake token is accepted for unbonding requests
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random_sender", &[Coin::new(100, "random_token")]),
+        ExecuteMsg::QueueUnbond {
+            receiver: None,
         },
-    ];
+    )
+    .unwrap_err();
 
-    for previous_batch in &previous_batches {
-        state
-            .previous_batches
-            .save(deps.as_mut().storage, previous_batch.id, previous_batch)
-            .unwrap();
-    }
+    assert_eq!(err, ContractError::ExpectingStakeToken("random_token".into()));
 
-    state
-        .unlocked_coins
-        .save(
-            deps.as_mut().storage,
-            &vec![native_asset_info(MOCK_UTOKEN.to_string()).with_balance(1000u128)],
-        )
-        .unwrap();
+    // User 1 creates an unbonding request before `est_unbond_start_time` is reached. The unbond
+    // request is saved, but not the pending batch is not submitted for unbonding
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(12345), // est_unbond_start_time = 269200
+        mock_info("user_1", &[Coin::new(23456, get_stake_full_denom())]),
+        ExecuteMsg::QueueUnbond {
+            receiver: None,
+        },
+    )
+    .unwrap();
 
-    deps.querier.set_bank_balances(&[Coin::new(3500, MOCK_UTOKEN)]);
+    assert_eq!(res.messages.len(), 0);
 
-    execute(
+    // User 2 creates an unbonding request after `est_unbond_start_time` is reached. The unbond
+    // request is saved, and the pending is automatically submitted for unbonding
+    let res = execute(
         deps.as_mut(),
-        mock_env_at_timestamp(35000),
-        mock_info("worker", &[]),
-        ExecuteMsg::Reconcile {},
+        mock_env_at_timestamp(269201), // est_unbond_start_time = 269200
+        mock_info("user_2", &[Coin::new(69420, get_stake_full_denom())]),
+        ExecuteMsg::QueueUnbond {
+            receiver: Some("user_3".to_string()),
+        },
     )
     .unwrap();
 
-    let batch = state.previous_batches.load(deps.as_ref().storage, 2u64).unwrap();
+    assert_eq!(res.messages.len(), 1);
     assert_eq!(
-        batch,
-        Batch {
-            id: 2,
-            reconciled: true,
-            total_shares: Uint128::new(1000),
-            utoken_unclaimed: Uint128::new(1000),
-            est_unbond_end_time: 20000,
-        }
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&ExecuteMsg::SubmitBatch {}).unwrap(),
+            funds: vec![]
+        }))
     );
 
-    let batch = state.previous_batches.load(deps.as_ref().storage, 3u64).unwrap();
+    // The users' unbonding requests should have been saved
+    let ubr1 = state
+        .unbond_requests
+        .load(deps.as_ref().storage, (1u64, &Addr::unchecked("user_1")))
+        .unwrap();
+    let ubr2 = state
+        .unbond_requests
+        .load(deps.as_ref().storage, (1u64, &Addr::unchecked("user_3")))
+        .unwrap();
+
     assert_eq!(
-        batch,
-        Batch {
-            id: 3,
-            reconciled: true,
-            total_shares: Uint128::new(1500),
-            utoken_unclaimed: Uint128::new(1500),
-            est_unbond_end_time: 30000,
+        ubr1,
+        UnbondRequest {
+            id: 1,
+            user: Addr::unchecked("user_1"),
+            shares: Uint128::new(23456)
+        }
+    );
+    assert_eq!(
+        ubr2,
+        UnbondRequest {
+            id: 1,
+            user: Addr::unchecked("user_3"),
+            shares: Uint128::new(69420)
         }
     );
 
-    // Batches 1 and 4 should not have changed
-    let batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
-    assert_eq!(batch, previous_batches[0]);
-
-    let batch = state.previous_batches.load(deps.as_ref().storage, 4u64).unwrap();
-    assert_eq!(batch, previous_batches[3]);
+    // Pending batch should have been updated
+    let pending_batch = state.pending_batch.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        pending_batch,
+        PendingBatch {
+            id: 1,
+            ustake_to_burn: Uint128::new(92876), // 23,456 + 69,420
+            est_unbond_start_time: 269200
+        }
+    );
 }
 
 #[test]
-fn reconciling_underflow() {
-    let (mut deps, _) = setup_test();
+fn submitting_batch() {
+    let (mut deps, mut stake) = setup_test();
     let state = State::default();
-    let previous_batches = vec![
-        Batch {
+
+    // utoken bonded: 1,037,345
+    // ustake supply: 1,012,043
+    // utoken per ustake: 1.025
+    stake.total_utoken_bonded = Uint128::new(1037345);
+    stake.total_supply = Uint128::new(1012043);
+    state.stake_token.save(deps.as_mut().storage, &stake).unwrap();
+
+    // We continue from the contract state at the end of the last test
+    let unbond_requests = vec![
+        UnbondRequest {
+            id: 1,
+            user: Addr::unchecked("user_1"),
+            shares: Uint128::new(23456),
+        },
+        UnbondRequest {
+            id: 1,
+            user: Addr::unchecked("user_3"),
+            shares: Uint128::new(69420),
+        },
+    ];
+
+    for unbond_request in &unbond_requests {
+        state
+            .unbond_requests
+            .save(
+                deps.as_mut().storage,
+                (unbond_request.id, &Addr::unchecked(unbond_request.user.clone())),
+                unbond_request,
+            )
+            .unwrap();
+    }
+
+    state
+        .pending_batch
+        .save(
+            deps.as_mut().storage,
+            &PendingBatch {
+                id: 1,
+                ustake_to_burn: Uint128::new(92876), // 23,456 + 69,420
+                est_unbond_start_time: 269200,
+            },
+        )
+        .unwrap();
+
+    // Anyone can invoke `submit_batch`. Here we continue from the previous test and assume it is
+    // invoked automatically as user 2 submits the unbonding request
+    //
+    // ustake to burn: 23,456 + 69,420 = 92,876
+    // utoken to unbond: 1,037,345 * 92,876 / 1,012,043 = 95,197
+    //
+    // Target: (1,037,345 - 95,197) / 3 = 314,049
+    // Remainer: 1
+    // Alice:   345,782 - (314,049 + 1) = 31,732
+    // Bob:     345,782 - (314,049 + 0) = 31,733
+    // Charlie: 345,781 - (314,049 + 0) = 31,732
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(269201),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 2);
+
+    assert_eq!(
+        res.messages[0].msg,
+        stake.dao_interface.unbond_msg(&stake.utoken, Uint128::new(95197)).unwrap()
+    );
+
+    assert_eq!(
+        res.messages[1].msg,
+        chain_test().create_burn_msg(get_stake_full_denom(), Uint128::new(92876))
+    );
+
+    // A new pending batch should have been created
+    let pending_batch = state.pending_batch.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        pending_batch,
+        PendingBatch {
+            id: 2,
+            ustake_to_burn: Uint128::zero(),
+            est_unbond_start_time: 528401 // 269,201 + 259,200
+        }
+    );
+
+    // Previous batch should have been updated
+    let previous_batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
+    assert_eq!(
+        previous_batch,
+        Batch {
+            id: 1,
+            reconciled: false,
+            total_shares: Uint128::new(92876),
+            utoken_unclaimed: Uint128::new(95197),
+            est_unbond_end_time: 2083601 // 269,201 + 1,814,400
+        }
+    );
+
+    let res: StateResponse = query_helper_env(deps.as_ref(), QueryMsg::State {}, 2083600);
+
+    let total_ustake = Uint128::from(1012043u128).sub(Uint128::new(23456)).sub(Uint128::new(69420));
+    assert_eq!(
+        res,
+        StateResponse {
+            total_ustake,
+            total_utoken: Uint128::from(1037345u128 - 95197u128),
+            exchange_rate: Decimal::from_ratio(1037345u128 - 95197u128, total_ustake.u128()),
+            unlocked_coins: vec![],
+            unbonding: Uint128::from(95197u128),
+            available: Uint128::zero(),
+            tvl_utoken: Uint128::from(1037345u128),
+        },
+    );
+}
+
+#[test]
+fn reconciling() {
+    let (mut deps, stake) = setup_test();
+    let state = State::default();
+
+    let previous_batches = vec![
+        Batch {
             id: 1,
             reconciled: true,
             total_shares: Uint128::new(92876),
@@ -1050,17 +1584,19 @@ fn reconciling_underflow() {
         Batch {
             id: 4,
             reconciled: false,
-            total_shares: Uint128::new(1),
-            utoken_unclaimed: Uint128::new(1),
-            est_unbond_end_time: 30001,
+            total_shares: Uint128::new(1567),
+            utoken_unclaimed: Uint128::new(1629), // 1.040 Token per Stake
+            est_unbond_end_time: 40000,           // not yet finished unbonding, ignored
         },
     ];
+
     for previous_batch in &previous_batches {
         state
             .previous_batches
             .save(deps.as_mut().storage, previous_batch.id, previous_batch)
             .unwrap();
     }
+
     state
         .unlocked_coins
         .save(
@@ -1077,85 +1613,338 @@ fn reconciling_underflow() {
             ],
         )
         .unwrap();
+
     deps.querier.set_bank_balances(&[
-        Coin::new(12345, MOCK_UTOKEN),
+        Coin::new(10000, MOCK_UTOKEN),
         Coin::new(234, "ukrw"),
         Coin::new(345, "uusd"),
         Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
     ]);
-    execute(
+
+    let res = execute(
         deps.as_mut(),
         mock_env_at_timestamp(35000),
         mock_info("worker", &[]),
         ExecuteMsg::Reconcile {},
     )
     .unwrap();
+
+    let assert_balance_msg = ExecuteMsg::Callback(CallbackMsg::AssertBalance {
+        expected: native_asset_info(MOCK_UTOKEN.to_string()).with_balance(12891u128),
+    });
+
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[0].msg, stake.dao_interface.claim_unbonded_msg().unwrap());
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&assert_balance_msg).unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    // Expected received: batch 2 + batch 3 = 1385 + 1506 = 2891
+    // Expected unlocked: 10000
+    // Expected: 12891
+    let batch = state.previous_batches.load(deps.as_ref().storage, 2u64).unwrap();
+    assert_eq!(
+        batch,
+        Batch {
+            id: 2,
+            reconciled: true,
+            total_shares: Uint128::new(1345),
+            utoken_unclaimed: Uint128::new(1385),
+            est_unbond_end_time: 20000,
+        }
+    );
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 3u64).unwrap();
+    assert_eq!(
+        batch,
+        Batch {
+            id: 3,
+            reconciled: true,
+            total_shares: Uint128::new(1456),
+            utoken_unclaimed: Uint128::new(1506),
+            est_unbond_end_time: 30000,
+        }
+    );
+
+    // Batches 1 and 4 should not have changed
+    let batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
+    assert_eq!(batch, previous_batches[0]);
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 4u64).unwrap();
+    assert_eq!(batch, previous_batches[3]);
+
+    deps.querier.set_bank_balances(&[
+        Coin::new(12345, MOCK_UTOKEN),
+        Coin::new(234, "ukrw"),
+        Coin::new(345, "uusd"),
+        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
+    ]);
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(35000),
+        mock_info("worker", &[]),
+        assert_balance_msg.clone(),
+    )
+    .expect_err("Should be self called");
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(35000),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        assert_balance_msg.clone(),
+    )
+    .expect_err("Not enough received");
+
+    deps.querier.set_bank_balances(&[
+        Coin::new(12891, MOCK_UTOKEN),
+        Coin::new(234, "ukrw"),
+        Coin::new(345, "uusd"),
+        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
+    ]);
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(35000),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        assert_balance_msg,
+    )
+    .expect("Should succeed");
 }
 
 #[test]
-fn reconciling_underflow_second() {
+fn reconciling_even_when_everything_ok() {
     let (mut deps, _) = setup_test();
     let state = State::default();
+
     let previous_batches = vec![
         Batch {
             id: 1,
             reconciled: true,
-            total_shares: Uint128::new(92876),
-            utoken_unclaimed: Uint128::new(95197), // 1.025 Token per Stake
+            total_shares: Uint128::new(100000),
+            utoken_unclaimed: Uint128::new(100000),
             est_unbond_end_time: 10000,
         },
         Batch {
             id: 2,
             reconciled: false,
-            total_shares: Uint128::new(1345),
-            utoken_unclaimed: Uint128::new(1385), // 1.030 Token per Stake
+            total_shares: Uint128::new(1000),
+            utoken_unclaimed: Uint128::new(1000),
             est_unbond_end_time: 20000,
         },
         Batch {
             id: 3,
             reconciled: false,
-            total_shares: Uint128::new(176),
-            utoken_unclaimed: Uint128::new(183), // 1.035 Token per Stake
+            total_shares: Uint128::new(1500),
+            utoken_unclaimed: Uint128::new(1500),
             est_unbond_end_time: 30000,
         },
         Batch {
             id: 4,
             reconciled: false,
-            total_shares: Uint128::new(1),
-            utoken_unclaimed: Uint128::new(1),
-            est_unbond_end_time: 30001,
+            total_shares: Uint128::new(1500),
+            utoken_unclaimed: Uint128::new(1500),
+            est_unbond_end_time: 40000, // not yet finished unbonding, ignored
         },
     ];
+
     for previous_batch in &previous_batches {
         state
             .previous_batches
             .save(deps.as_mut().storage, previous_batch.id, previous_batch)
             .unwrap();
     }
+
     state
         .unlocked_coins
         .save(
             deps.as_mut().storage,
-            &vec![
-                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(10000u128),
-                native_asset_info("ukrw".to_string()).with_balance(234u128),
-                native_asset_info("uusd".to_string()).with_balance(345u128),
-                native_asset_info(
-                    "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"
-                        .to_string(),
-                )
-                .with_balance(69420u128),
-            ],
+            &vec![native_asset_info(MOCK_UTOKEN.to_string()).with_balance(1000u128)],
         )
         .unwrap();
-    deps.querier.set_bank_balances(&[
-        Coin::new(12345 - 1323, MOCK_UTOKEN),
-        Coin::new(234, "ukrw"),
-        Coin::new(345, "uusd"),
-        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
-    ]);
-    execute(
-        deps.as_mut(),
+
+    deps.querier.set_bank_balances(&[Coin::new(3500, MOCK_UTOKEN)]);
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(35000),
+        mock_info("worker", &[]),
+        ExecuteMsg::Reconcile {},
+    )
+    .unwrap();
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 2u64).unwrap();
+    assert_eq!(
+        batch,
+        Batch {
+            id: 2,
+            reconciled: true,
+            total_shares: Uint128::new(1000),
+            utoken_unclaimed: Uint128::new(1000),
+            est_unbond_end_time: 20000,
+        }
+    );
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 3u64).unwrap();
+    assert_eq!(
+        batch,
+        Batch {
+            id: 3,
+            reconciled: true,
+            total_shares: Uint128::new(1500),
+            utoken_unclaimed: Uint128::new(1500),
+            est_unbond_end_time: 30000,
+        }
+    );
+
+    // Batches 1 and 4 should not have changed
+    let batch = state.previous_batches.load(deps.as_ref().storage, 1u64).unwrap();
+    assert_eq!(batch, previous_batches[0]);
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 4u64).unwrap();
+    assert_eq!(batch, previous_batches[3]);
+}
+
+#[test]
+fn reconciling_underflow() {
+    let (mut deps, _) = setup_test();
+    let state = State::default();
+    let previous_batches = vec![
+        Batch {
+            id: 1,
+            reconciled: true,
+            total_shares: Uint128::new(92876),
+            utoken_unclaimed: Uint128::new(95197), // 1.025 Token per Stake
+            est_unbond_end_time: 10000,
+        },
+        Batch {
+            id: 2,
+            reconciled: false,
+            total_shares: Uint128::new(1345),
+            utoken_unclaimed: Uint128::new(1385), // 1.030 Token per Stake
+            est_unbond_end_time: 20000,
+        },
+        Batch {
+            id: 3,
+            reconciled: false,
+            total_shares: Uint128::new(1456),
+            utoken_unclaimed: Uint128::new(1506), // 1.035 Token per Stake
+            est_unbond_end_time: 30000,
+        },
+        Batch {
+            id: 4,
+            reconciled: false,
+            total_shares: Uint128::new(1),
+            utoken_unclaimed: Uint128::new(1),
+            est_unbond_end_time: 30001,
+        },
+    ];
+    for previous_batch in &previous_batches {
+        state
+            .previous_batches
+            .save(deps.as_mut().storage, previous_batch.id, previous_batch)
+            .unwrap();
+    }
+    state
+        .unlocked_coins
+        .save(
+            deps.as_mut().storage,
+            &vec![
+                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(10000u128),
+                native_asset_info("ukrw".to_string()).with_balance(234u128),
+                native_asset_info("uusd".to_string()).with_balance(345u128),
+                native_asset_info(
+                    "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"
+                        .to_string(),
+                )
+                .with_balance(69420u128),
+            ],
+        )
+        .unwrap();
+    deps.querier.set_bank_balances(&[
+        Coin::new(12345, MOCK_UTOKEN),
+        Coin::new(234, "ukrw"),
+        Coin::new(345, "uusd"),
+        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
+    ]);
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(35000),
+        mock_info("worker", &[]),
+        ExecuteMsg::Reconcile {},
+    )
+    .unwrap();
+}
+
+#[test]
+fn reconciling_underflow_second() {
+    let (mut deps, _) = setup_test();
+    let state = State::default();
+    let previous_batches = vec![
+        Batch {
+            id: 1,
+            reconciled: true,
+            total_shares: Uint128::new(92876),
+            utoken_unclaimed: Uint128::new(95197), // 1.025 Token per Stake
+            est_unbond_end_time: 10000,
+        },
+        Batch {
+            id: 2,
+            reconciled: false,
+            total_shares: Uint128::new(1345),
+            utoken_unclaimed: Uint128::new(1385), // 1.030 Token per Stake
+            est_unbond_end_time: 20000,
+        },
+        Batch {
+            id: 3,
+            reconciled: false,
+            total_shares: Uint128::new(176),
+            utoken_unclaimed: Uint128::new(183), // 1.035 Token per Stake
+            est_unbond_end_time: 30000,
+        },
+        Batch {
+            id: 4,
+            reconciled: false,
+            total_shares: Uint128::new(1),
+            utoken_unclaimed: Uint128::new(1),
+            est_unbond_end_time: 30001,
+        },
+    ];
+    for previous_batch in &previous_batches {
+        state
+            .previous_batches
+            .save(deps.as_mut().storage, previous_batch.id, previous_batch)
+            .unwrap();
+    }
+    state
+        .unlocked_coins
+        .save(
+            deps.as_mut().storage,
+            &vec![
+                native_asset_info(MOCK_UTOKEN.to_string()).with_balance(10000u128),
+                native_asset_info("ukrw".to_string()).with_balance(234u128),
+                native_asset_info("uusd".to_string()).with_balance(345u128),
+                native_asset_info(
+                    "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"
+                        .to_string(),
+                )
+                .with_balance(69420u128),
+            ],
+        )
+        .unwrap();
+    deps.querier.set_bank_balances(&[
+        Coin::new(12345 - 1323, MOCK_UTOKEN),
+        Coin::new(234, "ukrw"),
+        Coin::new(345, "uusd"),
+        Coin::new(69420, "ibc/0471F1C4E7AFD3F07702BEF6DC365268D64570F7C1FDC98EA6098DD6DE59817B"),
+    ]);
+    execute(
+        deps.as_mut(),
         mock_env_at_timestamp(35000),
         mock_info("worker", &[]),
         ExecuteMsg::Reconcile {},
@@ -1390,168 +2179,672 @@ fn withdrawing_unbonded() {
     );
 }
 
-#[test]
-fn transferring_ownership() {
-    let (mut deps, _) = setup_test();
-    let state = State::default();
+#[test]
+fn transferring_ownership() {
+    let (mut deps, _) = setup_test();
+    let state = State::default();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::TransferOwnership {
+            new_owner: "jake".to_string(),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::TransferOwnership {
+            new_owner: "jake".to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 0);
+
+    let owner = state.owner.load(deps.as_ref().storage).unwrap();
+    assert_eq!(owner, Addr::unchecked("owner"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pumpkin", &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::UnauthorizedSenderNotNewOwner {});
+
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), ExecuteMsg::AcceptOwnership {})
+            .unwrap();
+
+    assert_eq!(res.messages.len(), 0);
+
+    let owner = state.owner.load(deps.as_ref().storage).unwrap();
+    assert_eq!(owner, Addr::unchecked("jake"));
+}
+
+//--------------------------------------------------------------------------------------------------
+// Fee Config
+//--------------------------------------------------------------------------------------------------
+
+#[test]
+fn update_fee() {
+    let (mut deps, _) = setup_test();
+    let state = State::default();
+
+    let config = state.fee_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config,
+        FeeConfig {
+            protocol_fee_contract: Addr::unchecked("fee"),
+            protocol_reward_fee: Decimal::from_ratio(1u128, 100u128)
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: Some(Decimal::from_ratio(11u128, 100u128)),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            allow_donations: None,
+            vote_operator: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: Some(Decimal::from_ratio(11u128, 100u128)),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            allow_donations: None,
+            vote_operator: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ProtocolRewardFeeTooHigh {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: Some("fee-new".to_string()),
+            protocol_reward_fee: Some(Decimal::from_ratio(10u128, 100u128)),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            allow_donations: None,
+            vote_operator: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 0);
+
+    let config = state.fee_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config,
+        FeeConfig {
+            protocol_fee_contract: Addr::unchecked("fee-new"),
+            protocol_reward_fee: Decimal::from_ratio(10u128, 100u128)
+        }
+    );
+}
+
+//--------------------------------------------------------------------------------------------------
+// Gov
+//--------------------------------------------------------------------------------------------------
+
+#[test]
+fn vote() {
+    let (mut deps, stake) = setup_test();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: cosmwasm_std::VoteOption::Yes,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::NoVoteOperatorSet {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: Some("vote_operator".to_string()),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: cosmwasm_std::VoteOption::Yes,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: cosmwasm_std::VoteOption::Yes,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    assert_eq!(
+        res.messages[0].msg,
+        stake
+            .dao_interface
+            .vote_msg(3, VoteOption::Yes, Addr::unchecked(""), Uint128::zero())
+            .unwrap()
+    );
+
+    let vote: HubVote = query_helper(
+        deps.as_ref(),
+        QueryMsg::Vote {
+            proposal_id: 3,
+            multiple: None,
+        },
+    );
+    assert_eq!(
+        vote,
+        HubVote {
+            proposal_id: 3,
+            vote: HubVoteOption::Single(VoteOption::Yes),
+            voting_power: Uint128::zero(),
+            timestamp: mock_env().block.time.seconds(),
+            operator: Addr::unchecked("vote_operator"),
+        }
+    );
+}
+
+#[test]
+fn vote_multiple() {
+    let (mut deps, _) = setup_test();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: Some("vote_operator".to_string()),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        ExecuteMsg::VoteMultiple {
+            proposal_id: 3,
+            option_id: 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
+
+    // cw4 does not support multiple choice proposals
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::VoteMultiple {
+            proposal_id: 3,
+            option_id: 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: multiple choice voting not supported for cw4");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: None,
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: Some(eris::hub::DaoInterface::DaoDao {
+                staking: "staking".to_string(),
+                gov: "gov".to_string(),
+                gov_multiple: Some("gov_multiple".to_string()),
+                cw_rewards: "cw_rewards".to_string(),
+            }),
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::VoteMultiple {
+            proposal_id: 3,
+            option_id: 1,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "gov_multiple".to_string(),
+            msg: to_json_binary(&DaoDaoProposalMultipleExecuteMsg::Vote {
+                proposal_id: 3,
+                vote: MultipleChoiceVote {
+                    option_id: 1,
+                },
+                rationale: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // proposal 3 of the main module is a different proposal
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: VoteOption::No,
+        },
+    )
+    .unwrap();
+
+    let vote: HubVote = query_helper(
+        deps.as_ref(),
+        QueryMsg::Vote {
+            proposal_id: 3,
+            multiple: Some(true),
+        },
+    );
+    assert_eq!(vote.vote, HubVoteOption::Multiple(1));
+
+    let votes: Vec<HubVote> = query_helper(
+        deps.as_ref(),
+        QueryMsg::Votes {
+            start_after: None,
+            limit: None,
+            multiple: None,
+        },
+    );
+    assert_eq!(votes.len(), 1);
+    assert_eq!(votes[0].vote, HubVoteOption::Single(VoteOption::No));
+}
+
+#[test]
+fn holder_voting() {
+    let (mut deps, stake) = setup_test();
+    let now = mock_env().block.time.seconds();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: Some("vote_operator".to_string()),
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: None,
+        },
+    )
+    .unwrap();
+
+    // two holders with 300 and 100 ustake in the snapshot
+    let user1 = merkle_leaf("user1", 300);
+    let user2 = merkle_leaf("user2", 100);
 
-    let err = execute(
+    let res = execute(
         deps.as_mut(),
         mock_env(),
         mock_info("jake", &[]),
-        ExecuteMsg::TransferOwnership {
-            new_owner: "jake".to_string(),
+        ExecuteMsg::OpenHolderVote {
+            proposal_id: 5,
+            snapshot_height: 12345,
+            merkle_root: merkle_root(user1, user2),
+            end_time: now + 100,
         },
     )
     .unwrap_err();
-
-    assert_eq!(err, ContractError::Unauthorized {});
+    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
 
     let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::OpenHolderVote {
+            proposal_id: 5,
+            snapshot_height: 12345,
+            merkle_root: merkle_root(user1, user2),
+            end_time: now + 100,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::UstakeSnapshotRequired {});
+
+    execute(
         deps.as_mut(),
         mock_env(),
         mock_info("owner", &[]),
-        ExecuteMsg::TransferOwnership {
-            new_owner: "jake".to_string(),
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: None,
+            protocol_reward_fee: None,
+            allow_donations: None,
+            vote_operator: None,
+            operator: None,
+            stages_preset: None,
+            withdrawals_preset: None,
+            default_max_spread: None,
+            epoch_period: None,
+            unbond_period: None,
+            dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: Some("snapshot".to_string()),
         },
     )
     .unwrap();
+    deps.querier.set_snapshot(&[("user1", 300), ("user2", 100)]);
 
-    assert_eq!(res.messages.len(), 0);
-
-    let owner = state.owner.load(deps.as_ref().storage).unwrap();
-    assert_eq!(owner, Addr::unchecked("owner"));
-
-    let err = execute(
+    // the tally can't be forwarded after the proposal ended
+    deps.querier.set_proposal_end(now + 100);
+    let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info("pumpkin", &[]),
-        ExecuteMsg::AcceptOwnership {},
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::OpenHolderVote {
+            proposal_id: 5,
+            snapshot_height: 12345,
+            merkle_root: merkle_root(user1, user2),
+            end_time: now + 100,
+        },
     )
     .unwrap_err();
+    assert_eq!(res, ContractError::HolderVoteEndsAfterProposal(5, now + 100));
 
-    assert_eq!(err, ContractError::UnauthorizedSenderNotNewOwner {});
+    deps.querier.set_proposal_end(now + 200);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::OpenHolderVote {
+            proposal_id: 5,
+            snapshot_height: 12345,
+            merkle_root: merkle_root(user1, user2),
+            end_time: now + 100,
+        },
+    )
+    .unwrap();
 
-    let res =
-        execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), ExecuteMsg::AcceptOwnership {})
-            .unwrap();
+    // the vote operator can't vote on proposals opened for holders
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vote_operator", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 5,
+            vote: VoteOption::No,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::HolderVoteOpen(5));
 
-    assert_eq!(res.messages.len(), 0);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(1000),
+            proof: vec![hex::encode(user2)],
+            votes: vec![(Decimal::one(), VoteOption::Yes)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::InvalidMerkleProof {});
 
-    let owner = state.owner.load(deps.as_ref().storage).unwrap();
-    assert_eq!(owner, Addr::unchecked("jake"));
-}
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+            votes: vec![(Decimal::one(), VoteOption::Yes)],
+        },
+    )
+    .unwrap();
 
-//--------------------------------------------------------------------------------------------------
-// Fee Config
-//--------------------------------------------------------------------------------------------------
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(100),
+            proof: vec![hex::encode(user1)],
+            votes: vec![
+                (Decimal::percent(50), VoteOption::Yes),
+                (Decimal::percent(50), VoteOption::No),
+            ],
+        },
+    )
+    .unwrap();
 
-#[test]
-fn update_fee() {
-    let (mut deps, _) = setup_test();
-    let state = State::default();
+    // voting again replaces the previous vote
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(100),
+            proof: vec![hex::encode(user1)],
+            votes: vec![(Decimal::one(), VoteOption::No)],
+        },
+    )
+    .unwrap();
 
-    let config = state.fee_config.load(deps.as_ref().storage).unwrap();
+    let ballot: HolderBallot = query_helper(
+        deps.as_ref(),
+        QueryMsg::HolderVote {
+            proposal_id: 5,
+            voter: "user2".to_string(),
+        },
+    );
     assert_eq!(
-        config,
-        FeeConfig {
-            protocol_fee_contract: Addr::unchecked("fee"),
-            protocol_reward_fee: Decimal::from_ratio(1u128, 100u128)
+        ballot,
+        HolderBallot {
+            ustake: Uint128::new(100),
+            votes: vec![(Decimal::one(), VoteOption::No)],
         }
     );
 
-    let err = execute(
+    let proposal: HolderProposal = query_helper(
+        deps.as_ref(),
+        QueryMsg::HolderProposal {
+            proposal_id: 5,
+        },
+    );
+    assert_eq!(
+        proposal.tally,
+        vec![(VoteOption::Yes, Uint128::new(300)), (VoteOption::No, Uint128::new(100))]
+    );
+
+    let res = execute(
         deps.as_mut(),
         mock_env(),
-        mock_info("jake", &[]),
-        ExecuteMsg::UpdateConfig {
-            protocol_fee_contract: None,
-            protocol_reward_fee: Some(Decimal::from_ratio(11u128, 100u128)),
-            operator: None,
-            stages_preset: None,
-            withdrawals_preset: None,
-            allow_donations: None,
-            vote_operator: None,
-            default_max_spread: None,
-            epoch_period: None,
-            unbond_period: None,
-            dao_interface: None,
+        mock_info("anyone", &[]),
+        ExecuteMsg::ForwardHolderVote {
+            proposal_id: 5,
         },
     )
     .unwrap_err();
-    assert_eq!(err, ContractError::Unauthorized {});
+    assert_eq!(res, ContractError::HolderVoteNotEnded(5));
 
-    let err = execute(
+    let res = execute(
         deps.as_mut(),
-        mock_env(),
-        mock_info("owner", &[]),
-        ExecuteMsg::UpdateConfig {
-            protocol_fee_contract: None,
-            protocol_reward_fee: Some(Decimal::from_ratio(11u128, 100u128)),
-            operator: None,
-            stages_preset: None,
-            withdrawals_preset: None,
-            allow_donations: None,
-            vote_operator: None,
-            default_max_spread: None,
-            epoch_period: None,
-            unbond_period: None,
-            dao_interface: None,
+        mock_env_at_timestamp(now + 101),
+        mock_info("user1", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+            votes: vec![(Decimal::one(), VoteOption::No)],
         },
     )
     .unwrap_err();
-    assert_eq!(err, ContractError::ProtocolRewardFeeTooHigh {});
+    assert_eq!(res, ContractError::HolderVoteEnded(5));
 
     let res = execute(
         deps.as_mut(),
-        mock_env(),
-        mock_info("owner", &[]),
-        ExecuteMsg::UpdateConfig {
-            protocol_fee_contract: Some("fee-new".to_string()),
-            protocol_reward_fee: Some(Decimal::from_ratio(10u128, 100u128)),
-            operator: None,
-            stages_preset: None,
-            withdrawals_preset: None,
-            allow_donations: None,
-            vote_operator: None,
-            default_max_spread: None,
-            epoch_period: None,
-            unbond_period: None,
-            dao_interface: None,
+        mock_env_at_timestamp(now + 101),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ForwardHolderVote {
+            proposal_id: 5,
         },
     )
     .unwrap();
+    assert_eq!(res.messages.len(), 1);
 
-    assert_eq!(res.messages.len(), 0);
-
-    let config = state.fee_config.load(deps.as_ref().storage).unwrap();
+    // the option with the most ustake is voted for
     assert_eq!(
-        config,
-        FeeConfig {
-            protocol_fee_contract: Addr::unchecked("fee-new"),
-            protocol_reward_fee: Decimal::from_ratio(10u128, 100u128)
-        }
+        res.messages[0].msg,
+        stake
+            .dao_interface
+            .vote_msg(5, VoteOption::Yes, Addr::unchecked(""), Uint128::zero())
+            .unwrap()
     );
-}
 
-//--------------------------------------------------------------------------------------------------
-// Gov
-//--------------------------------------------------------------------------------------------------
+    let vote: HubVote = query_helper(
+        deps.as_ref(),
+        QueryMsg::Vote {
+            proposal_id: 5,
+            multiple: None,
+        },
+    );
+    assert_eq!(vote.vote, HubVoteOption::Single(VoteOption::Yes));
 
-#[test]
-fn vote() {
-    let (mut deps, stake) = setup_test();
     let res = execute(
         deps.as_mut(),
-        mock_env(),
-        mock_info("jake", &[]),
-        ExecuteMsg::Vote {
-            proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+        mock_env_at_timestamp(now + 101),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ForwardHolderVote {
+            proposal_id: 5,
         },
     )
     .unwrap_err();
-    assert_eq!(res, ContractError::NoVoteOperatorSet {});
+    assert_eq!(res, ContractError::HolderVoteForwarded(5));
+}
+
+#[test]
+fn holder_voting_with_snapshot() {
+    let (mut deps, _) = setup_test();
+    let now = mock_env().block.time.seconds();
 
     execute(
         deps.as_mut(),
@@ -1569,41 +2862,69 @@ fn vote() {
             epoch_period: None,
             unbond_period: None,
             dao_interface: None,
+            reward_sources: None,
+            reward_vesting_period: None,
+            ustake_snapshot: Some("snapshot".to_string()),
         },
     )
     .unwrap();
 
-    let res = execute(
-        deps.as_mut(),
-        mock_env(),
-        mock_info("jake", &[]),
-        ExecuteMsg::Vote {
-            proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+    // the merkle snapshot overstates the ustake of user1
+    let user1 = merkle_leaf("user1", 300);
+    let user2 = merkle_leaf("user2", 100);
+    deps.querier.set_snapshot(&[("user1", 200), ("user2", 100)]);
+    deps.querier.set_proposal_end(now + 200);
+
+    let open = |snapshot_height: u64| ExecuteMsg::OpenHolderVote {
+        proposal_id: 5,
+        snapshot_height,
+        merkle_root: merkle_root(user1, user2),
+        end_time: now + 100,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("vote_operator", &[]), open(12346))
+        .unwrap_err();
+    assert_eq!(res, ContractError::SnapshotHeightInFuture(12346));
+
+    execute(deps.as_mut(), mock_env(), mock_info("vote_operator", &[]), open(12345)).unwrap();
+
+    let proposal: HolderProposal = query_helper(
+        deps.as_ref(),
+        QueryMsg::HolderProposal {
+            proposal_id: 5,
         },
-    )
-    .unwrap_err();
-    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
+    );
+    assert_eq!(proposal.ustake_snapshot, Addr::unchecked("snapshot"));
 
-    let res = execute(
+    execute(
         deps.as_mut(),
         mock_env(),
-        mock_info("vote_operator", &[]),
-        ExecuteMsg::Vote {
-            proposal_id: 3,
-            vote: cosmwasm_std::VoteOption::Yes,
+        mock_info("user1", &[]),
+        ExecuteMsg::HolderVote {
+            proposal_id: 5,
+            ustake: Uint128::new(300),
+            proof: vec![hex::encode(user2)],
+            votes: vec![(Decimal::one(), VoteOption::Yes)],
         },
     )
     .unwrap();
-    assert_eq!(res.messages.len(), 1);
 
-    assert_eq!(
-        res.messages[0].msg,
-        stake
-            .dao_interface
-            .vote_msg(3, VoteOption::Yes, Addr::unchecked(""), Uint128::zero())
-            .unwrap()
+    let ballot: HolderBallot = query_helper(
+        deps.as_ref(),
+        QueryMsg::HolderVote {
+            proposal_id: 5,
+            voter: "user1".to_string(),
+        },
+    );
+    assert_eq!(ballot.ustake, Uint128::new(200));
+
+    let proposal: HolderProposal = query_helper(
+        deps.as_ref(),
+        QueryMsg::HolderProposal {
+            proposal_id: 5,
+        },
     );
+    assert_eq!(proposal.tally, vec![(VoteOption::Yes, Uint128::new(200))]);
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -1912,6 +3233,49 @@ fn querying_unbond_requests_details() {
     );
 }
 
+#[test]
+fn querying_health() {
+    let (mut deps, mut stake) = setup_test();
+    stake.total_utoken_bonded = Uint128::new(1000);
+    State::default().stake_token.save(deps.as_mut().storage, &stake).unwrap();
+
+    deps.querier.set_dao_staked(1000);
+    let res: HealthResponse = query_helper(deps.as_ref(), QueryMsg::Health {});
+    assert_eq!(
+        res,
+        HealthResponse {
+            total_utoken_bonded: Uint128::new(1000),
+            dao_staked: Uint128::new(1000),
+            delta: Uint128::zero(),
+            deficit: false,
+        }
+    );
+
+    deps.querier.set_dao_staked(950);
+    let res: HealthResponse = query_helper(deps.as_ref(), QueryMsg::Health {});
+    assert_eq!(
+        res,
+        HealthResponse {
+            total_utoken_bonded: Uint128::new(1000),
+            dao_staked: Uint128::new(950),
+            delta: Uint128::new(50),
+            deficit: true,
+        }
+    );
+
+    deps.querier.set_dao_staked(1020);
+    let res: HealthResponse = query_helper(deps.as_ref(), QueryMsg::Health {});
+    assert_eq!(
+        res,
+        HealthResponse {
+            total_utoken_bonded: Uint128::new(1000),
+            dao_staked: Uint128::new(1020),
+            delta: Uint128::new(20),
+            deficit: false,
+        }
+    );
+}
+
 //--------------------------------------------------------------------------------------------------
 // Coins
 //--------------------------------------------------------------------------------------------------
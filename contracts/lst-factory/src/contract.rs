@@ -4,6 +4,7 @@ const CONTRACT_NAME: &str = "eris-update-scaling-factor";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 use crate::{
+    deploy::{deploy, deregister, handle_reply, query_deployments, register},
     error::{ContractError, ContractResult, CustomResult},
     msg::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state::CONFIG,
@@ -11,8 +12,8 @@ use crate::{
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, Fraction, MessageInfo, Response, StdResult, Storage,
-    Uint128,
+    to_json_binary, Binary, Deps, DepsMut, Env, Fraction, MessageInfo, Reply, Response, StdResult,
+    Storage, Uint128,
 };
 use cw2::set_contract_version;
 use cw_ownable::{get_ownership, update_ownership};
@@ -40,6 +41,7 @@ pub fn instantiate(
             hub: deps.api.addr_validate(&msg.hub)?,
             scale_first: msg.scale_first,
             decimals: Some(msg.decimals),
+            code_ids: msg.code_ids,
         },
     )?;
     Ok(Response::default())
@@ -56,9 +58,23 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
         ExecuteMsg::UpdateConfig {
             ..
         } => update_config(deps, env, info, msg),
+        ExecuteMsg::Deploy(msg) => deploy(deps, env, info, *msg),
+        ExecuteMsg::Register {
+            hub,
+            voting_escrow,
+            prop_gauges,
+        } => register(deps, info, hub, voting_escrow, prop_gauges),
+        ExecuteMsg::Deregister {
+            hub,
+        } => deregister(deps, info, hub),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> ContractResult {
+    handle_reply(deps, env, reply)
+}
+
 fn update_scaling_factor(deps: DepsMut, _env: Env, _info: MessageInfo) -> ContractResult {
     let config = CONFIG.load(deps.storage)?;
 
@@ -109,6 +125,7 @@ fn update_config(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -
             hub,
             scale_first,
             decimals,
+            code_ids,
         } => {
             cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
@@ -131,6 +148,10 @@ fn update_config(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -
                     config.decimals = Some(decimals);
                 };
 
+                if let Some(code_ids) = code_ids {
+                    config.code_ids = Some(code_ids);
+                };
+
                 Ok(config)
             })?;
 
@@ -145,6 +166,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
         QueryMsg::Config {} => to_json_binary(&get_config(deps.storage)?),
+        QueryMsg::Deployments {
+            start_after,
+            limit,
+        } => to_json_binary(&query_deployments(deps, start_after, limit)?),
     }
 }
 
@@ -0,0 +1,537 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, SubMsg, WasmMsg,
+};
+use cw_storage_plus::Bound;
+use eris::hub::{
+    ConfigResponse as HubConfigResponse, ExecuteMsg as HubExecuteMsg, QueryMsg as HubQueryMsg,
+};
+use eris::voting_escrow::ExecuteMsg as VotingEscrowExecuteMsg;
+
+use crate::{
+    error::{ContractError, ContractResult},
+    msg::{DeployMsg, Deployment, DeploymentsResponse},
+    state::{PendingDeployment, CONFIG, DEPLOYMENTS, PENDING_DEPLOYMENT},
+};
+
+const HUB_REPLY_ID: u64 = 1;
+const VOTING_ESCROW_REPLY_ID: u64 = 2;
+const PROP_GAUGES_REPLY_ID: u64 = 3;
+
+/// Time the owner of the voting_escrow has to claim the ownership
+const OWNERSHIP_PROPOSAL_EXPIRY: u64 = 7 * 86400;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Instantiates the hub owned by the factory, the other contracts are instantiated in the replies.
+pub fn deploy(deps: DepsMut, env: Env, info: MessageInfo, msg: DeployMsg) -> ContractResult {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let code_ids = CONFIG.load(deps.storage)?.code_ids.ok_or(ContractError::CodeIdsNotSet)?;
+
+    let mut hub_msg = msg.hub.clone();
+    hub_msg.owner = env.contract.address.to_string();
+    hub_msg.vote_operator = None;
+
+    let instantiate = WasmMsg::Instantiate {
+        admin: Some(msg.hub.owner.clone()),
+        code_id: code_ids.hub,
+        msg: to_json_binary(&hub_msg)?,
+        funds: info.funds,
+        label: format!("{0} hub", msg.label),
+    };
+
+    PENDING_DEPLOYMENT.save(
+        deps.storage,
+        &PendingDeployment {
+            msg,
+            hub: None,
+            voting_escrow: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(instantiate, HUB_REPLY_ID))
+        .add_attribute("action", "deploy"))
+}
+
+pub fn handle_reply(deps: DepsMut, env: Env, reply: Reply) -> ContractResult {
+    let contract_addr = get_instantiated_address(deps.as_ref(), &reply)?;
+    let mut pending = PENDING_DEPLOYMENT.load(deps.storage)?;
+    let code_ids = CONFIG.load(deps.storage)?.code_ids.ok_or(ContractError::CodeIdsNotSet)?;
+
+    match reply.id {
+        HUB_REPLY_ID => {
+            let hub_config: HubConfigResponse =
+                deps.querier.query_wasm_smart(&contract_addr, &HubQueryMsg::Config {})?;
+
+            let mut escrow_msg = pending.msg.voting_escrow.clone();
+            escrow_msg.owner = env.contract.address.to_string();
+            escrow_msg.deposit_denom = hub_config.stake_token;
+
+            let instantiate = WasmMsg::Instantiate {
+                admin: Some(pending.msg.hub.owner.clone()),
+                code_id: code_ids.voting_escrow,
+                msg: to_json_binary(&escrow_msg)?,
+                funds: vec![],
+                label: format!("{0} voting escrow", pending.msg.label),
+            };
+
+            pending.hub = Some(contract_addr.clone());
+            PENDING_DEPLOYMENT.save(deps.storage, &pending)?;
+
+            Ok(Response::new()
+                .add_submessage(SubMsg::reply_on_success(instantiate, VOTING_ESCROW_REPLY_ID))
+                .add_attribute("hub", contract_addr))
+        },
+        VOTING_ESCROW_REPLY_ID => {
+            let hub =
+                pending.hub.clone().ok_or_else(|| StdError::generic_err("hub not deployed"))?;
+
+            let mut prop_gauges_msg = pending.msg.prop_gauges.clone();
+            prop_gauges_msg.hub_addr = hub.to_string();
+            prop_gauges_msg.escrow_addr = contract_addr.to_string();
+
+            let instantiate = WasmMsg::Instantiate {
+                admin: Some(pending.msg.hub.owner.clone()),
+                code_id: code_ids.prop_gauges,
+                msg: to_json_binary(&prop_gauges_msg)?,
+                funds: vec![],
+                label: format!("{0} prop gauges", pending.msg.label),
+            };
+
+            pending.voting_escrow = Some(contract_addr.clone());
+            PENDING_DEPLOYMENT.save(deps.storage, &pending)?;
+
+            Ok(Response::new()
+                .add_submessage(SubMsg::reply_on_success(instantiate, PROP_GAUGES_REPLY_ID))
+                .add_attribute("voting_escrow", contract_addr))
+        },
+        PROP_GAUGES_REPLY_ID => {
+            let deployment = Deployment {
+                hub: pending.hub.ok_or_else(|| StdError::generic_err("hub not deployed"))?,
+                voting_escrow: pending
+                    .voting_escrow
+                    .ok_or_else(|| StdError::generic_err("voting escrow not deployed"))?,
+                prop_gauges: contract_addr,
+            };
+
+            DEPLOYMENTS.save(deps.storage, &deployment.hub, &deployment)?;
+            PENDING_DEPLOYMENT.remove(deps.storage);
+
+            let hub_msgs = vec![
+                HubExecuteMsg::UpdateConfig {
+                    protocol_fee_contract: None,
+                    protocol_reward_fee: None,
+                    operator: None,
+                    stages_preset: None,
+                    withdrawals_preset: None,
+                    allow_donations: None,
+                    vote_operator: Some(deployment.prop_gauges.to_string()),
+                    default_max_spread: None,
+                    epoch_period: None,
+                    unbond_period: None,
+                    dao_interface: None,
+                    reward_sources: None,
+                    reward_vesting_period: None,
+                    ustake_snapshot: None,
+                },
+                HubExecuteMsg::TransferOwnership {
+                    new_owner: pending.msg.hub.owner,
+                },
+            ];
+
+            let escrow_msgs = vec![
+                VotingEscrowExecuteMsg::UpdateConfig {
+                    new_guardian: None,
+                    push_update_contracts: Some(vec![deployment.prop_gauges.to_string()]),
+                    decommissioned: None,
+                },
+                VotingEscrowExecuteMsg::ProposeNewOwner {
+                    new_owner: pending.msg.voting_escrow.owner,
+                    expires_in: OWNERSHIP_PROPOSAL_EXPIRY,
+                },
+            ];
+
+            let mut msgs = vec![];
+            for msg in hub_msgs {
+                msgs.push(wasm_execute(&deployment.hub, &msg)?);
+            }
+            for msg in escrow_msgs {
+                msgs.push(wasm_execute(&deployment.voting_escrow, &msg)?);
+            }
+
+            Ok(Response::new()
+                .add_messages(msgs)
+                .add_attribute("prop_gauges", deployment.prop_gauges))
+        },
+        id => Err(ContractError::UnknownReplyId(id)),
+    }
+}
+
+pub fn register(
+    deps: DepsMut,
+    info: MessageInfo,
+    hub: String,
+    voting_escrow: String,
+    prop_gauges: String,
+) -> ContractResult {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let deployment = Deployment {
+        hub: deps.api.addr_validate(&hub)?,
+        voting_escrow: deps.api.addr_validate(&voting_escrow)?,
+        prop_gauges: deps.api.addr_validate(&prop_gauges)?,
+    };
+    DEPLOYMENTS.save(deps.storage, &deployment.hub, &deployment)?;
+
+    Ok(Response::new().add_attribute("action", "register").add_attribute("hub", hub))
+}
+
+pub fn deregister(deps: DepsMut, info: MessageInfo, hub: String) -> ContractResult {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let hub_addr = deps.api.addr_validate(&hub)?;
+    if !DEPLOYMENTS.has(deps.storage, &hub_addr) {
+        return Err(ContractError::DeploymentNotFound(hub));
+    }
+    DEPLOYMENTS.remove(deps.storage, &hub_addr);
+
+    Ok(Response::new().add_attribute("action", "deregister").add_attribute("hub", hub))
+}
+
+pub fn query_deployments(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DeploymentsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let deployments = DEPLOYMENTS
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, deployment)| deployment))
+        .collect::<StdResult<Vec<Deployment>>>()?;
+
+    Ok(DeploymentsResponse {
+        deployments,
+    })
+}
+
+fn wasm_execute<T: serde::Serialize>(contract: &Addr, msg: &T) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_json_binary(msg)?,
+        funds: vec![],
+    })
+}
+
+/// Reads the address of the instantiated contract from the events of the reply.
+fn get_instantiated_address(deps: Deps, reply: &Reply) -> StdResult<Addr> {
+    let response = reply.result.clone().into_result().map_err(StdError::generic_err)?;
+
+    let contract_addr = response
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "_contract_address")
+        .ok_or_else(|| StdError::generic_err("instantiated contract address not found"))?;
+
+    deps.api.addr_validate(&contract_addr.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use astroport::asset::native_asset_info;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult as CwContractResult, CosmosMsg, Decimal,
+        Event, Reply, SubMsgResponse, SubMsgResult, SystemResult, WasmMsg, WasmQuery,
+    };
+    use eris::hub::{
+        ConfigResponse as HubConfigResponse, DaoInterface, ExecuteMsg as HubExecuteMsg, FeeConfig,
+        InstantiateMsg as HubInstantiateMsg,
+    };
+    use eris::prop_gauges::InstantiateMsg as PropGaugesInstantiateMsg;
+    use eris::voting_escrow::InstantiateMsg as VotingEscrowInstantiateMsg;
+
+    use crate::contract::{execute, instantiate, query, reply};
+    use crate::msg::{
+        CodeIds, DeployMsg, Deployment, DeploymentsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    };
+
+    fn instantiated(id: u64, addr: &str) -> Reply {
+        Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("instantiate").add_attribute("_contract_address", addr)],
+                data: None,
+            }),
+        }
+    }
+
+    fn deploy_msg() -> DeployMsg {
+        DeployMsg {
+            label: "ampDAO".to_string(),
+            hub: HubInstantiateMsg {
+                owner: "dao_owner".to_string(),
+                operator: "operator".to_string(),
+                utoken: native_asset_info("utoken".to_string()),
+                denom: "ampDAO".to_string(),
+                epoch_period: 259200,
+                unbond_period: 1814400,
+                protocol_fee_contract: "fee".to_string(),
+                protocol_reward_fee: Decimal::percent(5),
+                vote_operator: None,
+                dao_interface: DaoInterface::Cw4 {
+                    addr: "cw4".to_string(),
+                    gov: "gov".to_string(),
+                    fund_distributor: "fund".to_string(),
+                },
+            },
+            voting_escrow: VotingEscrowInstantiateMsg {
+                owner: "dao_owner".to_string(),
+                guardian_addr: None,
+                deposit_denom: "".to_string(),
+                marketing: None,
+                logo_urls_whitelist: vec![],
+            },
+            prop_gauges: PropGaugesInstantiateMsg {
+                owner: "dao_owner".to_string(),
+                escrow_addr: "".to_string(),
+                hub_addr: "".to_string(),
+                quorum_bps: 500,
+                default_vote: None,
+                veto_threshold_bps: None,
+                lock_in_s: None,
+                gov_multiple: None,
+            },
+        }
+    }
+
+    #[test]
+    fn deploy_and_register() {
+        let mut deps = mock_dependencies();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart {
+                ..
+            } => SystemResult::Ok(CwContractResult::Ok(
+                to_json_binary(&HubConfigResponse {
+                    owner: "factory".to_string(),
+                    new_owner: None,
+                    utoken: native_asset_info("utoken".to_string()),
+                    stake_token: "factory/hub/ampDAO".to_string(),
+                    epoch_period: 259200,
+                    unbond_period: 1814400,
+                    fee_config: FeeConfig {
+                        protocol_fee_contract: Addr::unchecked("fee"),
+                        protocol_reward_fee: Decimal::percent(5),
+                    },
+                    operator: "operator".to_string(),
+                    stages_preset: vec![],
+                    withdrawals_preset: vec![],
+                    allow_donations: false,
+                    vote_operator: None,
+                    dao_interface: DaoInterface::Cw4 {
+                        addr: Addr::unchecked("cw4"),
+                        gov: Addr::unchecked("gov"),
+                        fund_distributor: Addr::unchecked("fund"),
+                    },
+                    reward_sources: vec![],
+                    ustake_snapshot: None,
+                })
+                .unwrap(),
+            )),
+            _ => unimplemented!(),
+        });
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                pool_id: 1,
+                scale_first: true,
+                hub: "hub".to_string(),
+                owner: "owner".to_string(),
+                decimals: 9,
+                code_ids: None,
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Deploy(Box::new(deploy_msg())),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "code ids not set");
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateConfig {
+                pool_id: None,
+                hub: None,
+                scale_first: None,
+                decimals: None,
+                code_ids: Some(CodeIds {
+                    hub: 10,
+                    voting_escrow: 11,
+                    prop_gauges: 12,
+                }),
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("jake", &[]),
+            ExecuteMsg::Deploy(Box::new(deploy_msg())),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Caller is not the contract's current owner");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Deploy(Box::new(deploy_msg())),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id,
+                msg,
+                ..
+            }) => {
+                assert_eq!(*code_id, 10);
+                let msg: HubInstantiateMsg = from_json(msg).unwrap();
+                assert_eq!(msg.owner, mock_env().contract.address.to_string());
+            },
+            _ => panic!("expected instantiate"),
+        }
+
+        let res = reply(deps.as_mut(), mock_env(), instantiated(1, "hub")).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id,
+                msg,
+                ..
+            }) => {
+                assert_eq!(*code_id, 11);
+                let msg: VotingEscrowInstantiateMsg = from_json(msg).unwrap();
+                assert_eq!(msg.deposit_denom, "factory/hub/ampDAO".to_string());
+            },
+            _ => panic!("expected instantiate"),
+        }
+
+        let res = reply(deps.as_mut(), mock_env(), instantiated(2, "escrow")).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id,
+                msg,
+                ..
+            }) => {
+                assert_eq!(*code_id, 12);
+                let msg: PropGaugesInstantiateMsg = from_json(msg).unwrap();
+                assert_eq!(msg.hub_addr, "hub".to_string());
+                assert_eq!(msg.escrow_addr, "escrow".to_string());
+            },
+            _ => panic!("expected instantiate"),
+        }
+
+        let res = reply(deps.as_mut(), mock_env(), instantiated(3, "gauges")).unwrap();
+        assert_eq!(res.messages.len(), 4);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                ..
+            }) => {
+                assert_eq!(contract_addr, "hub");
+                match from_json(msg).unwrap() {
+                    HubExecuteMsg::UpdateConfig {
+                        vote_operator,
+                        ..
+                    } => assert_eq!(vote_operator, Some("gauges".to_string())),
+                    _ => panic!("expected update config"),
+                }
+            },
+            _ => panic!("expected execute"),
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Register {
+                hub: "hub2".to_string(),
+                voting_escrow: "escrow2".to_string(),
+                prop_gauges: "gauges2".to_string(),
+            },
+        )
+        .unwrap();
+
+        let res: DeploymentsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Deployments {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.deployments,
+            vec![
+                Deployment {
+                    hub: Addr::unchecked("hub"),
+                    voting_escrow: Addr::unchecked("escrow"),
+                    prop_gauges: Addr::unchecked("gauges"),
+                },
+                Deployment {
+                    hub: Addr::unchecked("hub2"),
+                    voting_escrow: Addr::unchecked("escrow2"),
+                    prop_gauges: Addr::unchecked("gauges2"),
+                }
+            ]
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Deregister {
+                hub: "hub".to_string(),
+            },
+        )
+        .unwrap();
+
+        let res: DeploymentsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Deployments {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.deployments.len(), 1);
+    }
+}
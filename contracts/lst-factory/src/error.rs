@@ -24,4 +24,13 @@ pub enum ContractError {
 
     #[error("decimals must be less than 18")]
     DecimalsMustBeLessThan18,
+
+    #[error("code ids not set")]
+    CodeIdsNotSet,
+
+    #[error("deployment for hub {0} not found")]
+    DeploymentNotFound(String),
+
+    #[error("unknown reply id {0}")]
+    UnknownReplyId(u64),
 }
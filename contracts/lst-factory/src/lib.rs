@@ -1,4 +1,5 @@
 pub mod contract;
+pub mod deploy;
 pub mod error;
 pub mod msg;
 pub mod state;
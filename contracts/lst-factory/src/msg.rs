@@ -1,5 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
+use eris::hub::InstantiateMsg as HubInstantiateMsg;
+use eris::prop_gauges::InstantiateMsg as PropGaugesInstantiateMsg;
+use eris::voting_escrow::InstantiateMsg as VotingEscrowInstantiateMsg;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -8,6 +11,7 @@ pub struct InstantiateMsg {
     pub hub: String,
     pub owner: String,
     pub decimals: u32,
+    pub code_ids: Option<CodeIds>,
 }
 
 #[cw_serde]
@@ -18,8 +22,35 @@ pub enum ExecuteMsg {
         hub: Option<String>,
         scale_first: Option<bool>,
         decimals: Option<u32>,
+        code_ids: Option<CodeIds>,
     },
     UpdateOwnership(cw_ownable::Action),
+
+    /// Deploys a hub, a voting_escrow locking its LST and a prop_gauges contract that is set as
+    /// vote_operator of the hub. The owners of the hub and voting_escrow need to accept the
+    /// ownership afterwards.
+    Deploy(Box<DeployMsg>),
+    /// Tracks an already deployed hub, its vote_operator needs to be set by the owner of the hub.
+    Register {
+        hub: String,
+        voting_escrow: String,
+        prop_gauges: String,
+    },
+    Deregister {
+        hub: String,
+    },
+}
+
+#[cw_serde]
+pub struct DeployMsg {
+    /// Label prefix of the instantiated contracts
+    pub label: String,
+    /// The vote_operator is overwritten
+    pub hub: HubInstantiateMsg,
+    /// The deposit_denom is overwritten with the LST of the hub
+    pub voting_escrow: VotingEscrowInstantiateMsg,
+    /// The hub_addr and escrow_addr are overwritten
+    pub prop_gauges: PropGaugesInstantiateMsg,
 }
 
 #[cw_serde]
@@ -30,6 +61,12 @@ pub enum QueryMsg {
 
     #[returns(cw_ownable::Ownership<String> )]
     Ownership {},
+
+    #[returns(DeploymentsResponse)]
+    Deployments {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -41,4 +78,25 @@ pub struct Config {
     pub hub: Addr,
     pub scale_first: bool,
     pub decimals: Option<u32>,
+    #[serde(default)]
+    pub code_ids: Option<CodeIds>,
+}
+
+#[cw_serde]
+pub struct CodeIds {
+    pub hub: u64,
+    pub voting_escrow: u64,
+    pub prop_gauges: u64,
+}
+
+#[cw_serde]
+pub struct Deployment {
+    pub hub: Addr,
+    pub voting_escrow: Addr,
+    pub prop_gauges: Addr,
+}
+
+#[cw_serde]
+pub struct DeploymentsResponse {
+    pub deployments: Vec<Deployment>,
 }
@@ -1,5 +1,20 @@
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
 
-use crate::msg::Config;
+use crate::msg::{Config, DeployMsg, Deployment};
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Deployments by hub address
+pub const DEPLOYMENTS: Map<&Addr, Deployment> = Map::new("deployments");
+
+/// Deployment that is being instantiated through the reply chain
+pub const PENDING_DEPLOYMENT: Item<PendingDeployment> = Item::new("pending_deployment");
+
+#[cw_serde]
+pub struct PendingDeployment {
+    pub msg: DeployMsg,
+    pub hub: Option<Addr>,
+    pub voting_escrow: Option<Addr>,
+}
@@ -0,0 +1,188 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+    Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw20::BalanceResponse;
+
+use eris::ustake_snapshot::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+
+use crate::error::{ContractError, ContractResult};
+use crate::state::{BALANCES, CONFIG, TOTAL_SUPPLY};
+
+const CONTRACT_NAME: &str = "eris-ustake-snapshot";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            hub: deps.api.addr_validate(&msg.hub)?,
+            denom: msg.denom,
+            module_accounts: msg
+                .module_accounts
+                .iter()
+                .map(|addr| deps.api.addr_validate(addr))
+                .collect::<StdResult<Vec<_>>>()?,
+        },
+    )?;
+
+    let mut supply = Uint128::zero();
+    for (addr, balance) in msg.initial_balances {
+        add_balance(deps.storage, &deps.api.addr_validate(&addr)?, balance, env.block.height)?;
+        supply = supply.checked_add(balance)?;
+    }
+    TOTAL_SUPPLY.save(deps.storage, &supply, env.block.height)?;
+
+    Ok(Response::new().add_attribute("action", "erissnapshot/instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ContractResult {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.hub {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let height = env.block.height;
+    match msg {
+        ExecuteMsg::Mint {
+            recipient,
+            amount,
+        } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            add_balance(deps.storage, &recipient, amount, height)?;
+            let supply = TOTAL_SUPPLY.load(deps.storage)?.checked_add(amount)?;
+            TOTAL_SUPPLY.save(deps.storage, &supply, height)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "erissnapshot/mint")
+                .add_attribute("recipient", recipient)
+                .add_attribute("amount", amount))
+        },
+        ExecuteMsg::Burn {
+            amount,
+        } => {
+            let unrecorded = sub_balance(deps.storage, &config.hub, amount, height)?;
+            let supply = TOTAL_SUPPLY.load(deps.storage)?.saturating_sub(amount);
+            TOTAL_SUPPLY.save(deps.storage, &supply, height)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "erissnapshot/burn")
+                .add_attribute("amount", amount)
+                .add_events(unrecorded_event(&config.hub, unrecorded)))
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> ContractResult {
+    match msg {
+        // transfers are never blocked, so a diverging balance can't freeze the token
+        SudoMsg::BlockBeforeSend {
+            ..
+        } => Ok(Response::new()),
+        SudoMsg::TrackBeforeSend {
+            from,
+            to,
+            amount,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            if amount.denom != config.denom {
+                return Err(ContractError::UnexpectedDenom(config.denom, amount.denom));
+            }
+
+            let from = deps.api.addr_validate(&from)?;
+            let to = deps.api.addr_validate(&to)?;
+
+            // mints and burns are reported by the hub
+            if config.module_accounts.contains(&from) || config.module_accounts.contains(&to) {
+                return Ok(Response::new());
+            }
+
+            let unrecorded = sub_balance(deps.storage, &from, amount.amount, env.block.height)?;
+            add_balance(deps.storage, &to, amount.amount, env.block.height)?;
+
+            Ok(Response::new().add_events(unrecorded_event(&from, unrecorded)))
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::BalanceAt {
+            address,
+            height,
+        } => {
+            let address = deps.api.addr_validate(&address)?;
+            let balance = match height {
+                Some(height) => BALANCES.may_load_at_height(deps.storage, &address, height)?,
+                None => BALANCES.may_load(deps.storage, &address)?,
+            };
+            to_json_binary(&BalanceResponse {
+                balance: balance.unwrap_or_default(),
+            })
+        },
+        QueryMsg::TotalSupplyAt {
+            height,
+        } => {
+            let supply = match height {
+                Some(height) => TOTAL_SUPPLY.may_load_at_height(deps.storage, height)?,
+                None => TOTAL_SUPPLY.may_load(deps.storage)?,
+            };
+            to_json_binary(&supply.unwrap_or_default())
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attribute("action", "erissnapshot/migrate"))
+}
+
+fn add_balance(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default().checked_add(amount)?;
+    BALANCES.save(storage, addr, &balance, height)
+}
+
+/// Clamps the balance at zero and returns the part of `amount` that was not recorded
+fn sub_balance(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<Uint128> {
+    let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+    BALANCES.save(storage, addr, &balance.saturating_sub(amount), height)?;
+    Ok(amount.saturating_sub(balance))
+}
+
+/// Reports a balance that diverged from the bank balances, as the transfer can't be blocked
+fn unrecorded_event(addr: &Addr, unrecorded: Uint128) -> Option<Event> {
+    if unrecorded.is_zero() {
+        return None;
+    }
+    Some(
+        Event::new("erissnapshot/unrecorded_balance")
+            .add_attribute("address", addr)
+            .add_attribute("amount", unrecorded),
+    )
+}
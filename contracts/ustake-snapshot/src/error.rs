@@ -0,0 +1,20 @@
+use cosmwasm_std::{OverflowError, Response, StdError};
+use thiserror::Error;
+
+pub type ContractResult = Result<Response, ContractError>;
+
+/// This enum describes snapshot contract errors
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized: sender is not the hub")]
+    Unauthorized {},
+
+    #[error("Expecting {0}, received {1}")]
+    UnexpectedDenom(String, String),
+}
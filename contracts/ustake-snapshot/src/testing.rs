@@ -0,0 +1,205 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coin, from_json, Deps, Env, Event, Uint128};
+use cw20::BalanceResponse;
+
+use eris::ustake_snapshot::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
+
+use crate::contract::{execute, instantiate, query, sudo};
+use crate::error::ContractError;
+
+const DENOM: &str = "factory/hub/stake";
+
+fn mock_env_at_height(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn balance_at(deps: Deps, address: &str, height: Option<u64>) -> Uint128 {
+    let res: BalanceResponse = from_json(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::BalanceAt {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.balance
+}
+
+fn total_supply_at(deps: Deps, height: Option<u64>) -> Uint128 {
+    from_json(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::TotalSupplyAt {
+                height,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn tracking_balances() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env_at_height(100),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            hub: "hub".to_string(),
+            denom: DENOM.to_string(),
+            module_accounts: vec!["tokenfactory".to_string()],
+            initial_balances: vec![],
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_height(101),
+        mock_info("user1", &[]),
+        ExecuteMsg::Mint {
+            recipient: "user1".to_string(),
+            amount: Uint128::new(1000),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_height(101),
+        mock_info("hub", &[]),
+        ExecuteMsg::Mint {
+            recipient: "user1".to_string(),
+            amount: Uint128::new(1000),
+        },
+    )
+    .unwrap();
+
+    // the mint leg of the tokenfactory module is ignored
+    sudo(
+        deps.as_mut(),
+        mock_env_at_height(101),
+        SudoMsg::TrackBeforeSend {
+            from: "tokenfactory".to_string(),
+            to: "user1".to_string(),
+            amount: coin(1000, DENOM),
+        },
+    )
+    .unwrap();
+
+    sudo(
+        deps.as_mut(),
+        mock_env_at_height(105),
+        SudoMsg::TrackBeforeSend {
+            from: "user1".to_string(),
+            to: "hub".to_string(),
+            amount: coin(400, DENOM),
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_height(110),
+        mock_info("hub", &[]),
+        ExecuteMsg::Burn {
+            amount: Uint128::new(400),
+        },
+    )
+    .unwrap();
+
+    let res = sudo(
+        deps.as_mut(),
+        mock_env_at_height(110),
+        SudoMsg::TrackBeforeSend {
+            from: "user1".to_string(),
+            to: "user2".to_string(),
+            amount: coin(400, "other"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::UnexpectedDenom(DENOM.to_string(), "other".to_string()));
+
+    // balances are reported at the start of the block
+    assert_eq!(balance_at(deps.as_ref(), "user1", Some(101)), Uint128::zero());
+    assert_eq!(balance_at(deps.as_ref(), "user1", Some(102)), Uint128::new(1000));
+    assert_eq!(balance_at(deps.as_ref(), "user1", Some(106)), Uint128::new(600));
+    assert_eq!(balance_at(deps.as_ref(), "user1", None), Uint128::new(600));
+    assert_eq!(balance_at(deps.as_ref(), "hub", Some(106)), Uint128::new(400));
+    assert_eq!(balance_at(deps.as_ref(), "hub", None), Uint128::zero());
+
+    assert_eq!(total_supply_at(deps.as_ref(), Some(101)), Uint128::zero());
+    assert_eq!(total_supply_at(deps.as_ref(), Some(106)), Uint128::new(1000));
+    assert_eq!(total_supply_at(deps.as_ref(), None), Uint128::new(600));
+}
+
+#[test]
+fn importing_balances() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env_at_height(100),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            hub: "hub".to_string(),
+            denom: DENOM.to_string(),
+            module_accounts: vec!["tokenfactory".to_string()],
+            initial_balances: vec![
+                ("user1".to_string(), Uint128::new(500)),
+                ("user2".to_string(), Uint128::new(300)),
+            ],
+        },
+    )
+    .unwrap();
+
+    assert_eq!(balance_at(deps.as_ref(), "user1", Some(101)), Uint128::new(500));
+    assert_eq!(total_supply_at(deps.as_ref(), Some(101)), Uint128::new(800));
+
+    let transfer = |from: &str, amount: u128| SudoMsg::TrackBeforeSend {
+        from: from.to_string(),
+        to: "user3".to_string(),
+        amount: coin(amount, DENOM),
+    };
+
+    let res = sudo(deps.as_mut(), mock_env_at_height(105), transfer("user1", 200)).unwrap();
+    assert!(res.events.is_empty());
+
+    assert_eq!(balance_at(deps.as_ref(), "user1", None), Uint128::new(300));
+    assert_eq!(balance_at(deps.as_ref(), "user3", None), Uint128::new(200));
+    assert_eq!(total_supply_at(deps.as_ref(), None), Uint128::new(800));
+
+    // balances that were not imported are clamped at zero and reported
+    let res = sudo(deps.as_mut(), mock_env_at_height(106), transfer("user1", 301)).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("erissnapshot/unrecorded_balance")
+            .add_attribute("address", "user1")
+            .add_attribute("amount", "1")]
+    );
+    assert_eq!(balance_at(deps.as_ref(), "user1", None), Uint128::zero());
+    assert_eq!(balance_at(deps.as_ref(), "user3", None), Uint128::new(501));
+
+    // transfers are never blocked
+    sudo(
+        deps.as_mut(),
+        mock_env_at_height(107),
+        SudoMsg::BlockBeforeSend {
+            from: "user4".to_string(),
+            to: "user3".to_string(),
+            amount: coin(1, DENOM),
+        },
+    )
+    .unwrap();
+    assert_eq!(balance_at(deps.as_ref(), "user3", None), Uint128::new(501));
+}
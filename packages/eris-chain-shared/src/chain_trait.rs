@@ -1,5 +1,5 @@
 use astroport::asset::AssetInfo;
-use cosmwasm_std::{Addr, Api, CosmosMsg, Decimal, StdResult, Uint128};
+use cosmwasm_std::{Addr, Api, CosmosMsg, Decimal, StdError, StdResult, Uint128};
 
 pub trait ChainInterface<
     TCustom,
@@ -28,6 +28,15 @@ pub trait ChainInterface<
 
     fn create_burn_msg(&self, full_denom: String, amount: Uint128) -> CosmosMsg<TCustom>;
 
+    // registers the contract to be called with BlockBeforeSend / TrackBeforeSend on transfers
+    fn create_set_before_send_hook_msg(
+        &self,
+        _full_denom: String,
+        _contract: String,
+    ) -> StdResult<CosmosMsg<TCustom>> {
+        Err(StdError::generic_err("before-send hooks not supported"))
+    }
+
     fn create_withdraw_msg(
         &self,
         withdraw_type: TWithdrawType,
@@ -57,6 +57,19 @@ impl
         ))
     }
 
+    fn create_set_before_send_hook_msg(
+        &self,
+        full_denom: String,
+        contract: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Custom(CustomExecuteMsg::Token(
+            crate::custom_execute_msg::TokenExecuteMsg::SetBeforeSendHook {
+                denom: full_denom,
+                cosmwasm_address: contract,
+            },
+        )))
+    }
+
     fn create_withdraw_msg(
         &self,
         withdraw_type: WithdrawType,
@@ -24,4 +24,8 @@ pub enum TokenExecuteMsg {
         amount: Uint128,
         burn_from_address: String,
     },
+    SetBeforeSendHook {
+        denom: String,
+        cosmwasm_address: String,
+    },
 }
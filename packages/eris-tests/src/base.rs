@@ -386,6 +386,10 @@ impl BaseErisTestPackage {
             hub_addr: self.hub.get_address_string(),
             escrow_addr: self.voting_escrow.get_address_string(),
             quorum_bps: 500,
+            default_vote: None,
+            veto_threshold_bps: None,
+            lock_in_s: None,
+            gov_multiple: None,
         };
 
         let instance = router
@@ -421,6 +425,8 @@ impl BaseErisTestPackage {
                 protocol_performance_fee: Decimal::from_str("0.1").unwrap(),
                 protocol_withdraw_fee: Decimal::from_str("0.01").unwrap(),
                 immediate_withdraw_fee: Decimal::from_str("0.03").unwrap(),
+                flash_loan_fee: Decimal::from_str("0.001").unwrap(),
+                performance_fee_mode: eris::arb_vault::PerformanceFeeMode::PerTrade,
             },
             unbond_time_s: 24 * 24 * 60 * 60,
             utilization_method: eris::arb_vault::UtilizationMethod::Steps(vec![
@@ -429,7 +435,7 @@ impl BaseErisTestPackage {
                 (Decimal::from_ratio(20u128, 1000u128), Decimal::from_ratio(90u128, 100u128)),
                 (Decimal::from_ratio(25u128, 1000u128), Decimal::from_ratio(100u128, 100u128)),
             ]),
-            utoken: "uluna".to_string(),
+            utoken: native_asset_info("uluna".to_string()),
             whitelist: vec!["executor".to_string()],
             lsds: vec![LsdConfig {
                 name: "eris".into(),
@@ -438,6 +444,8 @@ impl BaseErisTestPackage {
                     denom: self.amp_token.get_address_string(),
                 },
                 disabled: false,
+                unbond_schedule: None,
+                risk_limits: None,
             }],
         };
 
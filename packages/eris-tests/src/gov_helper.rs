@@ -420,7 +420,8 @@ impl EscrowHelper {
             router_ref,
             eris::prop_gauges::ExecuteMsg::Vote {
                 proposal_id,
-                vote,
+                vote: Some(vote),
+                option: None,
             },
             user,
         )
@@ -671,6 +672,10 @@ impl EscrowHelper {
                 fee_config: None,
                 set_whitelist: None,
                 remove_whitelist: None,
+                set_unbond_schedule: None,
+                set_risk_limits: None,
+                reset_xfactor_high: None,
+                set_push_update_contracts: None,
             },
         )
     }
@@ -712,6 +717,8 @@ impl EscrowHelper {
                     disabled: false,
                     name: lsd,
                     lsd_type: t,
+                    unbond_schedule: None,
+                    risk_limits: None,
                 }),
                 disable_lsd: None,
                 remove_lsd: None,
@@ -719,6 +726,10 @@ impl EscrowHelper {
                 fee_config: None,
                 set_whitelist: None,
                 remove_whitelist: None,
+                set_unbond_schedule: None,
+                set_risk_limits: None,
+                reset_xfactor_high: None,
+                set_push_update_contracts: None,
             },
         )
     }
@@ -18,6 +18,11 @@ fn integration_update_configs() -> StdResult<()> {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 quorum_bps: Some(100u16),
+                default_vote: None,
+                veto_threshold_bps: None,
+                lock_in_s: None,
+                remove_default_vote: None,
+                remove_veto_threshold: None,
             },
             "user",
         )
@@ -30,6 +35,11 @@ fn integration_update_configs() -> StdResult<()> {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 quorum_bps: Some(100u16),
+                default_vote: None,
+                veto_threshold_bps: None,
+                lock_in_s: None,
+                remove_default_vote: None,
+                remove_veto_threshold: None,
             },
         )
         .unwrap();
@@ -0,0 +1,289 @@
+use super::DaoAdapter;
+use alliance_protocol::alliance_protocol::ExecuteMsg as AllianceExecuteMsg;
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, CosmosMsg, Env, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use eris_chain_adapter::types::CustomMsgType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllianceQueryMsg {
+    StakedBalance(AllianceAssetQuery),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AllianceAssetQuery {
+    pub address: String,
+    pub asset: cw_asset::AssetInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AllianceStakedBalanceResponse {
+    pub balance: Uint128,
+}
+
+pub struct Alliance<'a> {
+    pub addr: &'a Addr,
+}
+
+impl DaoAdapter for Alliance<'_> {
+    fn name(&self) -> &'static str {
+        "alliance"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match utoken {
+            AssetInfo::Token {
+                contract_addr,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.addr.to_string(),
+                    amount,
+                    msg: to_json_binary(&AllianceExecuteMsg::Stake {})?,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken {
+                denom,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.addr.to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::Stake {})?,
+                funds: vec![coin(amount.u128(), denom)],
+            })),
+        }
+    }
+
+    fn unbond_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&AllianceExecuteMsg::Unstake(to_cw_asset(utoken, amount)))?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        _env: &Env,
+        utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&AllianceExecuteMsg::ClaimRewards(to_cw_asset_info(utoken)))?,
+            funds: vec![],
+        })])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: AllianceStakedBalanceResponse = querier.query_wasm_smart(
+            self.addr,
+            &AllianceQueryMsg::StakedBalance(AllianceAssetQuery {
+                address: staker.to_string(),
+                asset: to_cw_asset_info(utoken),
+            }),
+        )?;
+
+        Ok(result.balance)
+    }
+}
+
+fn to_cw_asset(utoken: &AssetInfo, amount: Uint128) -> cw_asset::Asset {
+    match utoken {
+        AssetInfo::Token {
+            contract_addr,
+        } => cw_asset::Asset::cw20(contract_addr.clone(), amount),
+        AssetInfo::NativeToken {
+            denom,
+        } => cw_asset::Asset::native(denom, amount),
+    }
+}
+
+fn to_cw_asset_info(utoken: &AssetInfo) -> cw_asset::AssetInfo {
+    match utoken {
+        AssetInfo::Token {
+            contract_addr,
+        } => cw_asset::AssetInfo::cw20(contract_addr.clone()),
+        AssetInfo::NativeToken {
+            denom,
+        } => cw_asset::AssetInfo::native(denom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{StdError, VoteOption};
+
+    use super::*;
+    use crate::adapters::dao::testing::mock_querier;
+
+    #[test]
+    fn staking() {
+        let addr = Addr::unchecked("alliance");
+        let adapter = Alliance {
+            addr: &addr,
+        };
+        let utoken = AssetInfo::NativeToken {
+            denom: "utoken".to_string(),
+        };
+
+        assert_eq!(
+            adapter.deposit_msg(&utoken, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "alliance".to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::Stake {}).unwrap(),
+                funds: vec![coin(100, "utoken")],
+            })
+        );
+        assert_eq!(
+            adapter.unbond_msg(&utoken, Uint128::new(50)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "alliance".to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::Unstake(cw_asset::Asset::native(
+                    "utoken", 50u128
+                )))
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.claim_rewards_msgs(&mock_env(), &utoken, vec![], vec![]).unwrap(),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "alliance".to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::ClaimRewards(
+                    cw_asset::AssetInfo::native("utoken")
+                ))
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+        assert_eq!(
+            adapter.claim_unbonded_msg().unwrap_err(),
+            StdError::generic_err("claiming not supported for alliance")
+        );
+    }
+
+    #[test]
+    fn staking_cw20() {
+        let addr = Addr::unchecked("alliance");
+        let adapter = Alliance {
+            addr: &addr,
+        };
+        let utoken = AssetInfo::Token {
+            contract_addr: Addr::unchecked("token"),
+        };
+
+        assert_eq!(
+            adapter.deposit_msg(&utoken, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "alliance".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&AllianceExecuteMsg::Stake {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.unbond_msg(&utoken, Uint128::new(50)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "alliance".to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::Unstake(cw_asset::Asset::cw20(
+                    Addr::unchecked("token"),
+                    50u128
+                )))
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.claim_rewards_msgs(&mock_env(), &utoken, vec![], vec![]).unwrap(),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "alliance".to_string(),
+                msg: to_json_binary(&AllianceExecuteMsg::ClaimRewards(cw_asset::AssetInfo::cw20(
+                    Addr::unchecked("token")
+                )))
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+    }
+
+    #[test]
+    fn governance() {
+        let addr = Addr::unchecked("alliance");
+        let adapter = Alliance {
+            addr: &addr,
+        };
+
+        // alliance only stakes, the governance is not part of the protocol
+        assert_eq!(
+            adapter
+                .vote_msg(1, VoteOption::Yes, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap_err(),
+            StdError::generic_err("voting not supported for alliance")
+        );
+        let querier = mock_querier("alliance", &"", &"");
+        assert_eq!(
+            adapter.query_proposal(&QuerierWrapper::new(&querier), 1).unwrap_err(),
+            StdError::generic_err("proposal not supported for alliance")
+        );
+        assert_eq!(
+            adapter.list_proposals(&QuerierWrapper::new(&querier), None, None).unwrap_err(),
+            StdError::generic_err("proposal not supported for alliance")
+        );
+    }
+
+    #[test]
+    fn staked_balance() {
+        let addr = Addr::unchecked("alliance");
+        let adapter = Alliance {
+            addr: &addr,
+        };
+
+        let querier = mock_querier(
+            "alliance",
+            &AllianceQueryMsg::StakedBalance(AllianceAssetQuery {
+                address: "hub".to_string(),
+                asset: cw_asset::AssetInfo::native("utoken"),
+            }),
+            &AllianceStakedBalanceResponse {
+                balance: Uint128::new(100),
+            },
+        );
+        let balance = adapter
+            .query_staked_balance(
+                &QuerierWrapper::new(&querier),
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                &Addr::unchecked("hub"),
+            )
+            .unwrap();
+        assert_eq!(balance, Uint128::new(100));
+    }
+}
@@ -0,0 +1,491 @@
+use super::{DaoAdapter, ProposalListItem, ProposalResponse};
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, CosmosMsg, Env, QuerierWrapper, StdError, StdResult, Uint128,
+    VoteOption, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use eris_chain_adapter::types::CustomMsgType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapaQueryMsg {
+    Staker {
+        address: String,
+    },
+    Poll {
+        poll_id: u64,
+    },
+    Polls {
+        filter: Option<CapaPollStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order_by: Option<CapaOrderBy>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapaPollStatus {
+    InProgress,
+    Passed,
+    Rejected,
+    Executed,
+    Expired,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapaOrderBy {
+    Asc,
+    Desc,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CapaPollResponse {
+    pub id: u64,
+    pub status: CapaPollStatus,
+    /// end of the voting period in seconds
+    pub end_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CapaPollsResponse {
+    pub polls: Vec<CapaPollResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CapaStakerResponse {
+    pub balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapaVoteOption {
+    Yes,
+    No,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapaExecuteMsg {
+    CastVote {
+        poll_id: u64,
+        vote: CapaVoteOption,
+        amount: Uint128,
+    },
+
+    WithdrawVotingTokens {
+        amount: Option<Uint128>,
+    },
+
+    Claim {},
+
+    /// cw20 callback or called directly with native tokens
+    StakeVotingTokens {},
+}
+
+pub struct Capa<'a> {
+    pub gov: &'a Addr,
+}
+
+impl DaoAdapter for Capa<'_> {
+    fn name(&self) -> &'static str {
+        "capa"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match utoken {
+            AssetInfo::Token {
+                contract_addr,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.gov.to_string(),
+                    amount,
+                    msg: to_json_binary(&CapaExecuteMsg::StakeVotingTokens {})?,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken {
+                denom,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.gov.to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::StakeVotingTokens {})?,
+                funds: vec![coin(amount.u128(), denom)],
+            })),
+        }
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.gov.to_string(),
+            msg: to_json_binary(&CapaExecuteMsg::WithdrawVotingTokens {
+                amount: Some(amount),
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        _env: &Env,
+        _utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.gov.to_string(),
+            msg: to_json_binary(&CapaExecuteMsg::Claim {})?,
+            funds: vec![],
+        })])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: CapaStakerResponse = querier.query_wasm_smart(
+            self.gov,
+            &CapaQueryMsg::Staker {
+                address: staker.to_string(),
+            },
+        )?;
+
+        Ok(result.balance)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.gov.to_string(),
+            msg: to_json_binary(&CapaExecuteMsg::CastVote {
+                poll_id: proposal_id,
+                vote: match outcome {
+                    VoteOption::Yes => CapaVoteOption::Yes,
+                    VoteOption::No => CapaVoteOption::No,
+                    VoteOption::Abstain => {
+                        Err(StdError::generic_err("voting abstain not supported for capa"))?
+                    },
+                    VoteOption::NoWithVeto => CapaVoteOption::No,
+                },
+                amount,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        let result: CapaPollResponse = querier.query_wasm_smart(
+            self.gov,
+            &CapaQueryMsg::Poll {
+                poll_id: proposal_id,
+            },
+        )?;
+
+        Ok(ProposalResponse {
+            end_time_s: result.end_time,
+            choices: None,
+        })
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        let result: CapaPollsResponse = querier.query_wasm_smart(
+            self.gov,
+            &CapaQueryMsg::Polls {
+                filter: None,
+                start_after,
+                limit,
+                order_by: Some(CapaOrderBy::Asc),
+            },
+        )?;
+
+        Ok(result
+            .polls
+            .into_iter()
+            .map(|poll| ProposalListItem {
+                id: poll.id,
+                end_time_s: Some(poll.end_time),
+                is_open: poll.status == CapaPollStatus::InProgress,
+                choices: None,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+
+    use super::*;
+    use crate::adapters::dao::testing::mock_querier;
+
+    #[test]
+    fn deposit() {
+        let gov = Addr::unchecked("gov");
+        let adapter = Capa {
+            gov: &gov,
+        };
+
+        assert_eq!(
+            adapter
+                .deposit_msg(
+                    &AssetInfo::NativeToken {
+                        denom: "utoken".to_string(),
+                    },
+                    Uint128::new(100),
+                    "hub".to_string(),
+                )
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::StakeVotingTokens {}).unwrap(),
+                funds: vec![coin(100, "utoken")],
+            })
+        );
+        assert_eq!(
+            adapter
+                .deposit_msg(
+                    &AssetInfo::Token {
+                        contract_addr: Addr::unchecked("token"),
+                    },
+                    Uint128::new(100),
+                    "hub".to_string(),
+                )
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "gov".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&CapaExecuteMsg::StakeVotingTokens {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn unbond_and_claim() {
+        let gov = Addr::unchecked("gov");
+        let adapter = Capa {
+            gov: &gov,
+        };
+        let utoken = AssetInfo::NativeToken {
+            denom: "utoken".to_string(),
+        };
+
+        assert_eq!(
+            adapter.unbond_msg(&utoken, Uint128::new(50)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::WithdrawVotingTokens {
+                    amount: Some(Uint128::new(50)),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.claim_rewards_msgs(&mock_env(), &utoken, vec![], vec![]).unwrap(),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::Claim {}).unwrap(),
+                funds: vec![],
+            })]
+        );
+        // withdrawn voting tokens are sent directly
+        assert_eq!(
+            adapter.claim_unbonded_msg().unwrap_err(),
+            StdError::generic_err("claiming not supported for capa")
+        );
+    }
+
+    #[test]
+    fn vote() {
+        let gov = Addr::unchecked("gov");
+        let adapter = Capa {
+            gov: &gov,
+        };
+
+        assert_eq!(
+            adapter
+                .vote_msg(3, VoteOption::NoWithVeto, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::CastVote {
+                    poll_id: 3,
+                    vote: CapaVoteOption::No,
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter
+                .vote_msg(4, VoteOption::Yes, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::CastVote {
+                    poll_id: 4,
+                    vote: CapaVoteOption::Yes,
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter
+                .vote_msg(5, VoteOption::No, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&CapaExecuteMsg::CastVote {
+                    poll_id: 5,
+                    vote: CapaVoteOption::No,
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter
+                .vote_msg(3, VoteOption::Abstain, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap_err(),
+            StdError::generic_err("voting abstain not supported for capa")
+        );
+    }
+
+    #[test]
+    fn proposals() {
+        let gov = Addr::unchecked("gov");
+        let adapter = Capa {
+            gov: &gov,
+        };
+
+        let querier = mock_querier(
+            "gov",
+            &CapaQueryMsg::Poll {
+                poll_id: 3,
+            },
+            &CapaPollResponse {
+                id: 3,
+                status: CapaPollStatus::InProgress,
+                end_time: 1000,
+            },
+        );
+        assert_eq!(
+            adapter.query_proposal(&QuerierWrapper::new(&querier), 3).unwrap(),
+            ProposalResponse {
+                end_time_s: 1000,
+                choices: None,
+            }
+        );
+
+        let querier = mock_querier(
+            "gov",
+            &CapaQueryMsg::Polls {
+                filter: None,
+                start_after: Some(1),
+                limit: Some(10),
+                order_by: Some(CapaOrderBy::Asc),
+            },
+            &CapaPollsResponse {
+                polls: vec![
+                    CapaPollResponse {
+                        id: 2,
+                        status: CapaPollStatus::Passed,
+                        end_time: 500,
+                    },
+                    CapaPollResponse {
+                        id: 3,
+                        status: CapaPollStatus::InProgress,
+                        end_time: 1000,
+                    },
+                ],
+            },
+        );
+        assert_eq!(
+            adapter.list_proposals(&QuerierWrapper::new(&querier), Some(1), Some(10)).unwrap(),
+            vec![
+                ProposalListItem {
+                    id: 2,
+                    end_time_s: Some(500),
+                    is_open: false,
+                    choices: None,
+                },
+                ProposalListItem {
+                    id: 3,
+                    end_time_s: Some(1000),
+                    is_open: true,
+                    choices: None,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn staked_balance() {
+        let gov = Addr::unchecked("gov");
+        let adapter = Capa {
+            gov: &gov,
+        };
+
+        let querier = mock_querier(
+            "gov",
+            &CapaQueryMsg::Staker {
+                address: "hub".to_string(),
+            },
+            &CapaStakerResponse {
+                balance: Uint128::new(100),
+            },
+        );
+        let balance = adapter
+            .query_staked_balance(
+                &QuerierWrapper::new(&querier),
+                &AssetInfo::Token {
+                    contract_addr: Addr::unchecked("token"),
+                },
+                &Addr::unchecked("hub"),
+            )
+            .unwrap();
+        assert_eq!(balance, Uint128::new(100));
+    }
+}
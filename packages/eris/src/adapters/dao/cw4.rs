@@ -0,0 +1,412 @@
+use super::{
+    expiration_to_end_time, expiration_to_seconds, DaoAdapter, ProposalListItem, ProposalResponse,
+};
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, CosmosMsg, Empty, Env, QuerierWrapper, StdResult, Uint128,
+    VoteOption, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Expiration};
+use eris_chain_adapter::types::CustomMsgType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw4DistributorExecuteMsg {
+    ClaimRewards(Cw4ClaimRewardsMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw4ClaimRewardsMsg {
+    pub user: String,
+    /// Native denominations to be claimed
+    pub native_denoms: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw4StakeQueryMsg {
+    Staked {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw4StakedResponse {
+    pub stake: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw3QueryMsg {
+    Proposal {
+        proposal_id: u64,
+    },
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw3ProposalResponse {
+    pub id: u64,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw3ProposalListResponse {
+    pub proposals: Vec<Cw3ProposalListItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw3ProposalListItem {
+    pub id: u64,
+    pub status: cw3::Status,
+    pub expires: Expiration,
+}
+
+pub struct Cw4<'a> {
+    /// calling bond, unbond, claim (CW4)
+    pub addr: &'a Addr,
+    /// calling vote (CW3)
+    pub gov: &'a Addr,
+    /// calling claimrewards
+    pub fund_distributor: &'a Addr,
+}
+
+impl DaoAdapter for Cw4<'_> {
+    fn name(&self) -> &'static str {
+        "cw4"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match utoken {
+            AssetInfo::Token {
+                contract_addr,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.addr.to_string(),
+                    amount,
+                    msg: to_json_binary(&cw4_stake::msg::ReceiveMsg::Bond {})?,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken {
+                denom,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.addr.to_string(),
+                msg: to_json_binary(&cw4_stake::msg::ExecuteMsg::Bond {})?,
+                funds: vec![coin(amount.u128(), denom)],
+            })),
+        }
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&cw4_stake::msg::ExecuteMsg::Unbond {
+                tokens: amount,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&cw4_stake::msg::ExecuteMsg::Claim {})?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        env: &Env,
+        _utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.fund_distributor.to_string(),
+            msg: to_json_binary(&Cw4DistributorExecuteMsg::ClaimRewards(Cw4ClaimRewardsMsg {
+                user: env.contract.address.to_string(),
+                native_denoms: None,
+            }))?,
+            funds: vec![],
+        })])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: Cw4StakedResponse = querier.query_wasm_smart(
+            self.addr,
+            &Cw4StakeQueryMsg::Staked {
+                address: staker.to_string(),
+            },
+        )?;
+
+        Ok(result.stake)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.gov.to_string(),
+            msg: to_json_binary(&cw3::Cw3ExecuteMsg::Vote::<Empty> {
+                proposal_id,
+                vote: match outcome {
+                    VoteOption::Yes => cw3::Vote::Yes,
+                    VoteOption::No => cw3::Vote::No,
+                    VoteOption::Abstain => cw3::Vote::Abstain,
+                    VoteOption::NoWithVeto => cw3::Vote::Veto,
+                },
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        let result: Cw3ProposalResponse = querier.query_wasm_smart(
+            self.gov,
+            &Cw3QueryMsg::Proposal {
+                proposal_id,
+            },
+        )?;
+
+        Ok(ProposalResponse {
+            end_time_s: expiration_to_end_time(result.expires)?,
+            choices: None,
+        })
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        let result: Cw3ProposalListResponse = querier.query_wasm_smart(
+            self.gov,
+            &Cw3QueryMsg::ListProposals {
+                start_after,
+                limit,
+            },
+        )?;
+
+        Ok(result
+            .proposals
+            .into_iter()
+            .map(|item| ProposalListItem {
+                id: item.id,
+                end_time_s: expiration_to_seconds(item.expires),
+                is_open: item.status == cw3::Status::Open,
+                choices: None,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_json, Timestamp};
+
+    use super::*;
+    use crate::adapters::dao::testing::mock_querier;
+
+    #[test]
+    fn staking() {
+        let (addr, gov, fund_distributor) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = Cw4 {
+            addr: &addr,
+            gov: &gov,
+            fund_distributor: &fund_distributor,
+        };
+        let utoken = AssetInfo::NativeToken {
+            denom: "utoken".to_string(),
+        };
+
+        assert_eq!(
+            adapter.deposit_msg(&utoken, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&cw4_stake::msg::ExecuteMsg::Bond {}).unwrap(),
+                funds: vec![coin(100, "utoken")],
+            })
+        );
+        assert_eq!(
+            adapter.unbond_msg(&utoken, Uint128::new(50)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&cw4_stake::msg::ExecuteMsg::Unbond {
+                    tokens: Uint128::new(50),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.claim_rewards_msgs(&mock_env(), &utoken, vec![], vec![]).unwrap(),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "distributor".to_string(),
+                msg: to_json_binary(&Cw4DistributorExecuteMsg::ClaimRewards(Cw4ClaimRewardsMsg {
+                    user: mock_env().contract.address.to_string(),
+                    native_denoms: None,
+                }))
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+
+        let msg = adapter
+            .vote_msg(3, VoteOption::NoWithVeto, Addr::unchecked("voter"), Uint128::new(100))
+            .unwrap();
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                ..
+            }) => {
+                assert_eq!(contract_addr, "gov");
+                assert_eq!(
+                    from_json::<cw3::Cw3ExecuteMsg<Empty>>(&msg).unwrap(),
+                    cw3::Cw3ExecuteMsg::Vote {
+                        proposal_id: 3,
+                        vote: cw3::Vote::Veto,
+                    }
+                );
+            },
+            _ => panic!("unexpected message"),
+        }
+    }
+
+    #[test]
+    fn proposals() {
+        let (addr, gov, fund_distributor) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = Cw4 {
+            addr: &addr,
+            gov: &gov,
+            fund_distributor: &fund_distributor,
+        };
+
+        let querier = mock_querier(
+            "gov",
+            &Cw3QueryMsg::Proposal {
+                proposal_id: 3,
+            },
+            &Cw3ProposalResponse {
+                id: 3,
+                expires: Expiration::AtTime(Timestamp::from_seconds(1000)),
+            },
+        );
+        assert_eq!(
+            adapter.query_proposal(&QuerierWrapper::new(&querier), 3).unwrap(),
+            ProposalResponse {
+                end_time_s: 1000,
+                choices: None,
+            }
+        );
+
+        let querier = mock_querier(
+            "gov",
+            &Cw3QueryMsg::ListProposals {
+                start_after: None,
+                limit: Some(2),
+            },
+            &Cw3ProposalListResponse {
+                proposals: vec![
+                    Cw3ProposalListItem {
+                        id: 1,
+                        status: cw3::Status::Passed,
+                        expires: Expiration::AtHeight(100),
+                    },
+                    Cw3ProposalListItem {
+                        id: 2,
+                        status: cw3::Status::Open,
+                        expires: Expiration::AtTime(Timestamp::from_seconds(1000)),
+                    },
+                ],
+            },
+        );
+        assert_eq!(
+            adapter.list_proposals(&QuerierWrapper::new(&querier), None, Some(2)).unwrap(),
+            vec![
+                ProposalListItem {
+                    id: 1,
+                    end_time_s: None,
+                    is_open: false,
+                    choices: None,
+                },
+                ProposalListItem {
+                    id: 2,
+                    end_time_s: Some(1000),
+                    is_open: true,
+                    choices: None,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn staked_balance() {
+        let (addr, gov, fund_distributor) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = Cw4 {
+            addr: &addr,
+            gov: &gov,
+            fund_distributor: &fund_distributor,
+        };
+
+        let querier = mock_querier(
+            "staking",
+            &Cw4StakeQueryMsg::Staked {
+                address: "hub".to_string(),
+            },
+            &Cw4StakedResponse {
+                stake: Uint128::new(100),
+            },
+        );
+        let balance = adapter
+            .query_staked_balance(
+                &QuerierWrapper::new(&querier),
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                &Addr::unchecked("hub"),
+            )
+            .unwrap();
+        assert_eq!(balance, Uint128::new(100));
+    }
+}
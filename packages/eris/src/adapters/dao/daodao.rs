@@ -0,0 +1,1134 @@
+use super::{
+    expiration_to_end_time, expiration_to_seconds, DaoAdapter, ProposalListItem, ProposalResponse,
+};
+use astroport::asset::AssetInfo;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, CosmosMsg, Env, QuerierWrapper, StdError, StdResult,
+    Uint128, VoteOption, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Expiration};
+use eris_chain_adapter::types::CustomMsgType;
+
+use crate::adapters::nft::Nft;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cw_serde]
+pub enum DaoDaoExecuteMsg {
+    /// Stakes tokens with the contract to get voting power in the DAO
+    Stake {},
+    /// Unstakes tokens so that they begin unbonding
+    Unstake {
+        amount: Uint128,
+    },
+    Claim {},
+}
+/// dao-voting-cw721-staked, NFTs are staked by sending them to the contract
+#[cw_serde]
+pub enum DaoDaoNftExecuteMsg {
+    Unstake {
+        token_ids: Vec<String>,
+    },
+    ClaimNfts {},
+}
+
+#[cw_serde]
+pub enum DaoDaoRewardsExecuteMsg {
+    Claim {
+        id: u64,
+    },
+}
+
+#[cw_serde]
+pub enum DaoDaoProposalSingleExecuteMsg {
+    Vote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The senders position on the proposal.
+        vote: Vote,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
+}
+
+#[cw_serde]
+pub enum DaoDaoProposalMultipleExecuteMsg {
+    Vote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The senders position on the proposal.
+        vote: MultipleChoiceVote,
+        /// An optional rationale for why this vote was cast.
+        rationale: Option<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub struct MultipleChoiceVote {
+    /// Index of the chosen option
+    pub option_id: u32,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+#[repr(u8)]
+pub enum Vote {
+    /// Marks support for the proposal.
+    Yes,
+    /// Marks opposition to the proposal.
+    No,
+    /// Marks participation but does not count towards the ratio of
+    /// support / opposed.
+    Abstain,
+}
+
+#[cw_serde]
+pub enum DaoDaoQueryMsg {
+    Proposal {
+        proposal_id: u64,
+    },
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub enum DaoDaoStakingQueryMsg {
+    StakedBalanceAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoStakedBalanceAtHeightResponse {
+    pub balance: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub enum DaoDaoNftQueryMsg {
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoVotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: DaoDaoSingleChoiceProposal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoSingleChoiceProposal {
+    pub expiration: Expiration,
+    /// Only set by dao-proposal-multiple
+    #[serde(default)]
+    pub choices: Vec<DaoDaoChoice>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoChoice {
+    pub index: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoProposalListResponse {
+    pub proposals: Vec<DaoDaoProposalListItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoProposalListItem {
+    pub id: u64,
+    pub proposal: DaoDaoProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoDaoProposalStatus {
+    pub expiration: Expiration,
+    /// e.g. open, rejected, passed, executed, closed
+    pub status: String,
+    /// Only set by dao-proposal-multiple
+    #[serde(default)]
+    pub choices: Vec<DaoDaoChoice>,
+}
+
+#[cw_serde]
+pub enum CwRewardsExecuteMsg {
+    ClaimRewards(CwRewardsClaimRewardsMsg),
+}
+#[cw_serde]
+pub struct CwRewardsClaimRewardsMsg {
+    pub callback: Option<KujiraCallbackData>,
+}
+
+#[cw_serde]
+pub struct KujiraCallbackData(pub Binary);
+
+pub struct DaoDao<'a> {
+    /// calling bond, unbond, claim
+    pub staking: &'a Addr,
+    /// calling vote, proposal queries
+    pub gov: &'a Addr,
+    /// calling vote on multiple choice proposals, falls back to `gov`
+    pub gov_multiple: Option<&'a Addr>,
+    /// entropic variant of rewards claimable
+    pub cw_rewards: &'a Addr,
+}
+
+pub struct DaoDaoV2<'a> {
+    /// calling bond, unbond, claim
+    pub staking: &'a Addr,
+    /// calling vote, proposal queries
+    pub gov: &'a Addr,
+    /// calling vote on multiple choice proposals, falls back to `gov`
+    pub gov_multiple: Option<&'a Addr>,
+    /// calling claim with id on each of the contracts
+    pub rewards: &'a [(Addr, u64)],
+}
+
+pub struct DaoDaoNft<'a> {
+    /// calling unstake, claim nfts
+    pub staking: &'a Addr,
+    /// calling vote, proposal queries
+    pub gov: &'a Addr,
+    /// calling vote on multiple choice proposals, falls back to `gov`
+    pub gov_multiple: Option<&'a Addr>,
+    /// calling claim with id on each of the contracts
+    pub rewards: &'a [(Addr, u64)],
+}
+
+impl DaoAdapter for DaoDao<'_> {
+    fn name(&self) -> &'static str {
+        "daodao"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        deposit_msg(self.staking, utoken, amount)
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        unbond_msg(self.staking, amount)
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        claim_unbonded_msg(self.staking)
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        _env: &Env,
+        _utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.cw_rewards.to_string(),
+            msg: to_json_binary(&CwRewardsExecuteMsg::ClaimRewards(CwRewardsClaimRewardsMsg {
+                callback: None,
+            }))?,
+            funds: vec![],
+        })])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        query_staked_balance(self.staking, querier, staker)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_msg(self.gov, proposal_id, outcome)
+    }
+
+    fn vote_multiple_msg(
+        &self,
+        proposal_id: u64,
+        option_id: u32,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_multiple_msg(self.gov_multiple.unwrap_or(self.gov), proposal_id, option_id)
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov, querier, proposal_id)
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov, querier, start_after, limit)
+    }
+
+    fn query_proposal_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov_multiple.unwrap_or(self.gov), querier, proposal_id)
+    }
+
+    fn list_proposals_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov_multiple.unwrap_or(self.gov), querier, start_after, limit)
+    }
+}
+
+impl DaoAdapter for DaoDaoV2<'_> {
+    fn name(&self) -> &'static str {
+        "daodao_v2"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        deposit_msg(self.staking, utoken, amount)
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        unbond_msg(self.staking, amount)
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        claim_unbonded_msg(self.staking)
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        _env: &Env,
+        _utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        claim_rewards_msgs(self.rewards)
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        query_staked_balance(self.staking, querier, staker)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_msg(self.gov, proposal_id, outcome)
+    }
+
+    fn vote_multiple_msg(
+        &self,
+        proposal_id: u64,
+        option_id: u32,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_multiple_msg(self.gov_multiple.unwrap_or(self.gov), proposal_id, option_id)
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov, querier, proposal_id)
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov, querier, start_after, limit)
+    }
+
+    fn query_proposal_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov_multiple.unwrap_or(self.gov), querier, proposal_id)
+    }
+
+    fn list_proposals_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov_multiple.unwrap_or(self.gov), querier, start_after, limit)
+    }
+}
+
+impl DaoAdapter for DaoDaoNft<'_> {
+    fn name(&self) -> &'static str {
+        "daodao_nft"
+    }
+
+    fn deposit_msg(
+        &self,
+        _utoken: &AssetInfo,
+        _amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err("fungible tokens not supported for daodao_nft"))
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err("fungible tokens not supported for daodao_nft"))
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.staking.to_string(),
+            msg: to_json_binary(&DaoDaoNftExecuteMsg::ClaimNfts {})?,
+            funds: vec![],
+        }))
+    }
+
+    fn deposit_nft_msg(
+        &self,
+        collection: &Addr,
+        token_id: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        // the staking contract stakes any NFT it receives, no hook message required
+        Nft(collection.clone()).send_msg(self.staking, token_id, Binary::default())
+    }
+
+    fn unbond_nft_msg(&self, token_ids: Vec<String>) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.staking.to_string(),
+            msg: to_json_binary(&DaoDaoNftExecuteMsg::Unstake {
+                token_ids,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        _env: &Env,
+        _utoken: &AssetInfo,
+        _native_denoms: Vec<String>,
+        _cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        claim_rewards_msgs(self.rewards)
+    }
+
+    /// Returns the number of NFTs staked, which equals the voting power.
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: DaoDaoVotingPowerAtHeightResponse = querier.query_wasm_smart(
+            self.staking,
+            &DaoDaoNftQueryMsg::VotingPowerAtHeight {
+                address: staker.to_string(),
+                height: None,
+            },
+        )?;
+
+        Ok(result.power)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_msg(self.gov, proposal_id, outcome)
+    }
+
+    fn vote_multiple_msg(
+        &self,
+        proposal_id: u64,
+        option_id: u32,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_multiple_msg(self.gov_multiple.unwrap_or(self.gov), proposal_id, option_id)
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov, querier, proposal_id)
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov, querier, start_after, limit)
+    }
+
+    fn query_proposal_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov_multiple.unwrap_or(self.gov), querier, proposal_id)
+    }
+
+    fn list_proposals_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov_multiple.unwrap_or(self.gov), querier, start_after, limit)
+    }
+}
+
+fn deposit_msg(
+    staking: &Addr,
+    utoken: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<CustomMsgType>> {
+    match utoken {
+        AssetInfo::Token {
+            contract_addr,
+        } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: staking.to_string(),
+                amount,
+                msg: to_json_binary(&DaoDaoExecuteMsg::Stake {})?,
+            })?,
+            funds: vec![],
+        })),
+        AssetInfo::NativeToken {
+            denom,
+        } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: staking.to_string(),
+            msg: to_json_binary(&DaoDaoExecuteMsg::Stake {})?,
+            funds: vec![coin(amount.u128(), denom)],
+        })),
+    }
+}
+
+fn unbond_msg(staking: &Addr, amount: Uint128) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: staking.to_string(),
+        msg: to_json_binary(&DaoDaoExecuteMsg::Unstake {
+            amount,
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn claim_unbonded_msg(staking: &Addr) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: staking.to_string(),
+        msg: to_json_binary(&DaoDaoExecuteMsg::Claim {})?,
+        funds: vec![],
+    }))
+}
+
+fn claim_rewards_msgs(rewards: &[(Addr, u64)]) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+    rewards
+        .iter()
+        .map(|(contract, claim_id)| {
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                    id: *claim_id,
+                })?,
+                funds: vec![],
+            }))
+        })
+        .collect()
+}
+
+fn query_staked_balance(
+    staking: &Addr,
+    querier: &QuerierWrapper,
+    staker: &Addr,
+) -> StdResult<Uint128> {
+    let result: DaoDaoStakedBalanceAtHeightResponse = querier.query_wasm_smart(
+        staking,
+        &DaoDaoStakingQueryMsg::StakedBalanceAtHeight {
+            address: staker.to_string(),
+            height: None,
+        },
+    )?;
+
+    Ok(result.balance)
+}
+
+fn vote_msg(
+    gov: &Addr,
+    proposal_id: u64,
+    outcome: VoteOption,
+) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: gov.to_string(),
+        msg: to_json_binary(&DaoDaoProposalSingleExecuteMsg::Vote {
+            rationale: None,
+            proposal_id,
+            vote: match outcome {
+                VoteOption::Yes => Vote::Yes,
+                VoteOption::No => Vote::No,
+                VoteOption::Abstain => Vote::Abstain,
+                VoteOption::NoWithVeto => Vote::No,
+            },
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// `gov` needs to be a dao-proposal-multiple module.
+fn vote_multiple_msg(
+    gov: &Addr,
+    proposal_id: u64,
+    option_id: u32,
+) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: gov.to_string(),
+        msg: to_json_binary(&DaoDaoProposalMultipleExecuteMsg::Vote {
+            rationale: None,
+            proposal_id,
+            vote: MultipleChoiceVote {
+                option_id,
+            },
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn query_proposal(
+    gov: &Addr,
+    querier: &QuerierWrapper,
+    proposal_id: u64,
+) -> StdResult<ProposalResponse> {
+    let result: DaoDaoProposalResponse = querier.query_wasm_smart(
+        gov,
+        &DaoDaoQueryMsg::Proposal {
+            proposal_id,
+        },
+    )?;
+
+    Ok(ProposalResponse {
+        end_time_s: expiration_to_end_time(result.proposal.expiration)?,
+        choices: get_choices(&result.proposal.choices),
+    })
+}
+
+fn list_proposals(
+    gov: &Addr,
+    querier: &QuerierWrapper,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ProposalListItem>> {
+    let result: DaoDaoProposalListResponse = querier.query_wasm_smart(
+        gov,
+        &DaoDaoQueryMsg::ListProposals {
+            start_after,
+            limit: limit.map(u64::from),
+        },
+    )?;
+
+    Ok(result
+        .proposals
+        .into_iter()
+        .map(|item| ProposalListItem {
+            id: item.id,
+            end_time_s: expiration_to_seconds(item.proposal.expiration),
+            is_open: item.proposal.status == "open",
+            choices: get_choices(&item.proposal.choices),
+        })
+        .collect())
+}
+
+/// Returns the number of options of a dao-proposal-multiple proposal.
+fn get_choices(choices: &[DaoDaoChoice]) -> Option<u32> {
+    if choices.is_empty() {
+        None
+    } else {
+        Some(choices.len() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::Timestamp;
+
+    use super::*;
+    use crate::adapters::dao::testing::mock_querier;
+    use crate::adapters::nft::Cw721ExecuteMsg;
+
+    #[test]
+    fn deposit() {
+        let (staking, gov, cw_rewards) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("rewards"));
+        let adapter = DaoDao {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            cw_rewards: &cw_rewards,
+        };
+        let token = AssetInfo::Token {
+            contract_addr: Addr::unchecked("token"),
+        };
+        let native = AssetInfo::NativeToken {
+            denom: "utoken".to_string(),
+        };
+
+        assert_eq!(
+            adapter.deposit_msg(&token, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "staking".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&DaoDaoExecuteMsg::Stake {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.deposit_msg(&native, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&DaoDaoExecuteMsg::Stake {}).unwrap(),
+                funds: vec![coin(100, "utoken")],
+            })
+        );
+
+        let rewards: Vec<(Addr, u64)> = vec![];
+        let adapter = DaoDaoV2 {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            rewards: &rewards,
+        };
+        assert_eq!(
+            adapter.deposit_msg(&token, Uint128::new(100), "hub".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "staking".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&DaoDaoExecuteMsg::Stake {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn nft_staking() {
+        let (staking, gov) = (Addr::unchecked("staking"), Addr::unchecked("gov"));
+        let rewards = vec![(Addr::unchecked("rewards"), 2)];
+        let adapter = DaoDaoNft {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            rewards: &rewards,
+        };
+        let collection = AssetInfo::Token {
+            contract_addr: Addr::unchecked("collection"),
+        };
+
+        assert_eq!(
+            adapter.deposit_msg(&collection, Uint128::new(1), "hub".to_string()).unwrap_err(),
+            StdError::generic_err("fungible tokens not supported for daodao_nft")
+        );
+        assert_eq!(
+            adapter.deposit_nft_msg(&Addr::unchecked("collection"), "42".to_string()).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "collection".to_string(),
+                msg: to_json_binary(&Cw721ExecuteMsg::SendNft {
+                    contract: "staking".to_string(),
+                    token_id: "42".to_string(),
+                    msg: Binary::default(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.unbond_nft_msg(vec!["1".to_string(), "42".to_string()]).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&DaoDaoNftExecuteMsg::Unstake {
+                    token_ids: vec!["1".to_string(), "42".to_string()],
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.claim_unbonded_msg().unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_json_binary(&DaoDaoNftExecuteMsg::ClaimNfts {}).unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let querier = mock_querier(
+            "staking",
+            &DaoDaoNftQueryMsg::VotingPowerAtHeight {
+                address: "hub".to_string(),
+                height: None,
+            },
+            &DaoDaoVotingPowerAtHeightResponse {
+                power: Uint128::new(3),
+                height: 12345,
+            },
+        );
+        let balance = adapter
+            .query_staked_balance(
+                &QuerierWrapper::new(&querier),
+                &collection,
+                &Addr::unchecked("hub"),
+            )
+            .unwrap();
+        assert_eq!(balance, Uint128::new(3));
+    }
+
+    #[test]
+    fn claim_rewards() {
+        let (staking, gov) = (Addr::unchecked("staking"), Addr::unchecked("gov"));
+        let rewards = vec![(Addr::unchecked("rewards1"), 1), (Addr::unchecked("rewards2"), 4)];
+        let adapter = DaoDaoV2 {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            rewards: &rewards,
+        };
+        let utoken = AssetInfo::NativeToken {
+            denom: "utoken".to_string(),
+        };
+
+        assert_eq!(
+            adapter.claim_rewards_msgs(&mock_env(), &utoken, vec![], vec![]).unwrap(),
+            vec![
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "rewards1".to_string(),
+                    msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                        id: 1,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "rewards2".to_string(),
+                    msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                        id: 4,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn vote() {
+        let (staking, gov, cw_rewards) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("rewards"));
+        let adapter = DaoDao {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            cw_rewards: &cw_rewards,
+        };
+
+        assert_eq!(
+            adapter
+                .vote_msg(3, VoteOption::NoWithVeto, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&DaoDaoProposalSingleExecuteMsg::Vote {
+                    proposal_id: 3,
+                    vote: Vote::No,
+                    rationale: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.vote_multiple_msg(3, 2, Addr::unchecked("voter"), Uint128::new(100)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&DaoDaoProposalMultipleExecuteMsg::Vote {
+                    proposal_id: 3,
+                    vote: MultipleChoiceVote {
+                        option_id: 2,
+                    },
+                    rationale: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // single and multiple choice proposals live in separate modules
+        let gov_multiple = Addr::unchecked("gov_multiple");
+        let adapter = DaoDao {
+            gov_multiple: Some(&gov_multiple),
+            ..adapter
+        };
+        assert_eq!(
+            adapter
+                .vote_msg(3, VoteOption::Yes, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov".to_string(),
+                msg: to_json_binary(&DaoDaoProposalSingleExecuteMsg::Vote {
+                    proposal_id: 3,
+                    vote: Vote::Yes,
+                    rationale: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            adapter.vote_multiple_msg(4, 1, Addr::unchecked("voter"), Uint128::new(100)).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gov_multiple".to_string(),
+                msg: to_json_binary(&DaoDaoProposalMultipleExecuteMsg::Vote {
+                    proposal_id: 4,
+                    vote: MultipleChoiceVote {
+                        option_id: 1,
+                    },
+                    rationale: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn proposals() {
+        let (staking, gov, cw_rewards) =
+            (Addr::unchecked("staking"), Addr::unchecked("gov"), Addr::unchecked("rewards"));
+        let adapter = DaoDao {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            cw_rewards: &cw_rewards,
+        };
+
+        let querier = mock_querier(
+            "gov",
+            &DaoDaoQueryMsg::Proposal {
+                proposal_id: 3,
+            },
+            &DaoDaoProposalResponse {
+                id: 3,
+                proposal: DaoDaoSingleChoiceProposal {
+                    expiration: Expiration::AtTime(Timestamp::from_seconds(1000)),
+                    choices: vec![
+                        DaoDaoChoice {
+                            index: 0,
+                        },
+                        DaoDaoChoice {
+                            index: 1,
+                        },
+                    ],
+                },
+            },
+        );
+        assert_eq!(
+            adapter.query_proposal(&QuerierWrapper::new(&querier), 3).unwrap(),
+            ProposalResponse {
+                end_time_s: 1000,
+                choices: Some(2),
+            }
+        );
+
+        let querier = mock_querier(
+            "gov",
+            &DaoDaoQueryMsg::ListProposals {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+            &DaoDaoProposalListResponse {
+                proposals: vec![DaoDaoProposalListItem {
+                    id: 2,
+                    proposal: DaoDaoProposalStatus {
+                        expiration: Expiration::Never {},
+                        status: "open".to_string(),
+                        choices: vec![],
+                    },
+                }],
+            },
+        );
+        assert_eq!(
+            adapter.list_proposals(&QuerierWrapper::new(&querier), Some(1), Some(1)).unwrap(),
+            vec![ProposalListItem {
+                id: 2,
+                end_time_s: None,
+                is_open: true,
+                choices: None,
+            }]
+        );
+
+        // multiple choice proposals are listed from their own module
+        let gov_multiple = Addr::unchecked("gov_multiple");
+        let adapter = DaoDao {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: Some(&gov_multiple),
+            cw_rewards: &cw_rewards,
+        };
+        let querier = mock_querier(
+            "gov_multiple",
+            &DaoDaoQueryMsg::ListProposals {
+                start_after: None,
+                limit: Some(1),
+            },
+            &DaoDaoProposalListResponse {
+                proposals: vec![DaoDaoProposalListItem {
+                    id: 2,
+                    proposal: DaoDaoProposalStatus {
+                        expiration: Expiration::AtTime(Timestamp::from_seconds(1000)),
+                        status: "open".to_string(),
+                        choices: vec![
+                            DaoDaoChoice {
+                                index: 0,
+                            },
+                            DaoDaoChoice {
+                                index: 1,
+                            },
+                            DaoDaoChoice {
+                                index: 2,
+                            },
+                        ],
+                    },
+                }],
+            },
+        );
+        assert_eq!(
+            adapter.list_proposals_multiple(&QuerierWrapper::new(&querier), None, Some(1)).unwrap(),
+            vec![ProposalListItem {
+                id: 2,
+                end_time_s: Some(1000),
+                is_open: true,
+                choices: Some(3),
+            }]
+        );
+        adapter.list_proposals(&QuerierWrapper::new(&querier), None, Some(1)).unwrap_err();
+    }
+
+    #[test]
+    fn staked_balance() {
+        let (staking, gov) = (Addr::unchecked("staking"), Addr::unchecked("gov"));
+        let rewards: Vec<(Addr, u64)> = vec![];
+        let adapter = DaoDaoV2 {
+            staking: &staking,
+            gov: &gov,
+            gov_multiple: None,
+            rewards: &rewards,
+        };
+
+        let querier = mock_querier(
+            "staking",
+            &DaoDaoStakingQueryMsg::StakedBalanceAtHeight {
+                address: "hub".to_string(),
+                height: None,
+            },
+            &DaoDaoStakedBalanceAtHeightResponse {
+                balance: Uint128::new(100),
+                height: 12345,
+            },
+        );
+        let balance = adapter
+            .query_staked_balance(
+                &QuerierWrapper::new(&querier),
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                &Addr::unchecked("hub"),
+            )
+            .unwrap();
+        assert_eq!(balance, Uint128::new(100));
+    }
+}
@@ -0,0 +1,883 @@
+use super::{
+    expiration_to_end_time, expiration_to_seconds, DaoAdapter, ProposalListItem, ProposalResponse,
+};
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, CosmosMsg, Env, QuerierWrapper, StdError, StdResult, Uint128,
+    VoteOption, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Expiration};
+use eris_chain_adapter::types::CustomMsgType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseCw20HookMsg {
+    Stake {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseV2Cw20HookMsg {
+    Stake {
+        user: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseExecuteMsg {
+    CastVote(CastVoteMsg),
+    Unstake(EnterpriseUnstakeMsg),
+    Claim {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseExecuteMsgV2 {
+    /// Stakes the sent native tokens in a denom-staking-membership contract
+    Stake {
+        user: Option<String>,
+    },
+    Unstake(EnterpriseUnstakeMsgV2),
+    // CastVote(CastVoteMsgV2),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CastVoteMsg {
+    pub proposal_id: u64,
+    pub outcome: VoteOutcome,
+}
+
+// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+// #[serde(rename_all = "snake_case")]
+// pub struct CastVoteMsgV2 {
+//     pub poll_id: u64,
+//     pub outcome: VoteOutcome,
+//     pub voter: Addr,
+//     pub amount: Uint128,
+// }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+// TODO: rename to VoteOption?
+pub enum VoteOutcome {
+    Yes = 0,
+    No = 1,
+    Abstain = 2,
+    Veto = 3,
+}
+
+impl From<u8> for VoteOutcome {
+    fn from(v: u8) -> VoteOutcome {
+        match v {
+            0u8 => VoteOutcome::Yes,
+            1u8 => VoteOutcome::No,
+            2u8 => VoteOutcome::Abstain,
+            3u8 => VoteOutcome::Veto,
+            _ => panic!("invalid vote option"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseUnstakeMsg {
+    Cw20(EnterpriseUnstakeCw20Msg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EnterpriseUnstakeMsgV2 {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EnterpriseUnstakeCw20Msg {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseDistributorExecuteMsg {
+    ClaimRewards(EnterpriseClaimRewardsMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EnterpriseClaimRewardsMsg {
+    pub user: String,
+    /// Native denominations to be claimed
+    pub native_denoms: Option<Vec<String>>,
+    /// CW20 asset rewards to be claimed, should be addresses of CW20 tokens
+    pub cw20_assets: Option<Vec<String>>,
+}
+
+// /// Unique identifier for a poll.
+// pub type PollId = u64;
+
+// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+// #[serde(rename_all = "snake_case")]
+// /// Params for querying a poll.
+// pub struct PollParams {
+//     /// ID of the poll to be queried.
+//     pub poll_id: PollId,
+// }
+
+// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+// #[serde(rename_all = "snake_case")]
+// /// Response model for querying a poll.
+// pub struct EnterprisePollResponse {
+//     /// The poll.
+//     pub poll: Poll,
+// }
+// #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+// #[serde(rename_all = "snake_case")]
+// /// A poll.
+// pub struct Poll {
+//     /// Unique identifier for the poll.
+//     pub id: PollId,
+//     /// End-time of poll.
+//     pub ends_at: Timestamp,
+// }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseQueryMsg {
+    Proposal(ProposalParams),
+    Proposals(ProposalsParams),
+    UserStake(UserStakeParams),
+    // Poll(PollParams),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserStakeParams {
+    pub user: String,
+    pub start_after: Option<u16>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserStakeResponse {
+    pub user_stake: UserStake,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStake {
+    None,
+    Token(TokenUserStake),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TokenUserStake {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseMembershipQueryMsg {
+    UserStake(UserStakeParamsV2),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserStakeParamsV2 {
+    pub user: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserStakeResponseV2 {
+    pub staked_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalParams {
+    pub proposal_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalsParams {
+    pub filter: Option<EnterpriseProposalStatusFilter>,
+    pub start_after: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterpriseProposalStatusFilter {
+    InProgress,
+    Passed,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EnterpriseProposalResponse {
+    pub proposal: Proposal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EnterpriseProposalsResponse {
+    pub proposals: Vec<EnterpriseProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Proposal {
+    pub id: u64,
+    pub expires: Expiration,
+}
+
+pub struct Enterprise<'a> {
+    pub addr: &'a Addr,
+    pub fund_distributor: &'a Addr,
+}
+
+pub struct EnterpriseV2<'a> {
+    pub gov: &'a Addr,
+    pub membership: &'a Addr,
+    pub distributor: &'a Addr,
+}
+
+impl DaoAdapter for Enterprise<'_> {
+    fn name(&self) -> &'static str {
+        "enterprise"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        _eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match utoken {
+            AssetInfo::Token {
+                contract_addr,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.addr.to_string(),
+                    amount,
+                    msg: to_json_binary(&EnterpriseCw20HookMsg::Stake {})?,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken {
+                ..
+            } => Err(native_not_supported()),
+        }
+    }
+
+    fn unbond_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        if utoken.is_native_token() {
+            return Err(native_not_supported());
+        }
+
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsg::Unstake(EnterpriseUnstakeMsg::Cw20(
+                EnterpriseUnstakeCw20Msg {
+                    amount,
+                },
+            )))?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsg::Claim {})?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        env: &Env,
+        _utoken: &AssetInfo,
+        native_denoms: Vec<String>,
+        cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![claim_rewards_msg(self.fund_distributor, env, native_denoms, cw20_assets)?])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: UserStakeResponse = querier.query_wasm_smart(
+            self.addr,
+            &EnterpriseQueryMsg::UserStake(UserStakeParams {
+                user: staker.to_string(),
+                start_after: None,
+                limit: None,
+            }),
+        )?;
+
+        Ok(match result.user_stake {
+            UserStake::None => Uint128::zero(),
+            UserStake::Token(stake) => stake.amount,
+        })
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_msg(self.addr, proposal_id, outcome)
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.addr, querier, proposal_id)
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.addr, querier, start_after, limit)
+    }
+}
+
+impl DaoAdapter for EnterpriseV2<'_> {
+    fn name(&self) -> &'static str {
+        "enterprise_v2"
+    }
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match utoken {
+            AssetInfo::Token {
+                contract_addr,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.membership.to_string(),
+                    amount,
+                    msg: to_json_binary(&EnterpriseV2Cw20HookMsg::Stake {
+                        user: eris_contract_addr,
+                    })?,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken {
+                denom,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.membership.to_string(),
+                msg: to_json_binary(&EnterpriseExecuteMsgV2::Stake {
+                    user: Some(eris_contract_addr),
+                })?,
+                funds: vec![coin(amount.u128(), denom)],
+            })),
+        }
+    }
+
+    fn unbond_msg(
+        &self,
+        _utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.membership.to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsgV2::Unstake(EnterpriseUnstakeMsgV2 {
+                amount,
+            }))?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.membership.to_string(),
+            msg: to_json_binary(&EnterpriseExecuteMsg::Claim {})?,
+            funds: vec![],
+        }))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        env: &Env,
+        _utoken: &AssetInfo,
+        native_denoms: Vec<String>,
+        cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        Ok(vec![claim_rewards_msg(self.distributor, env, native_denoms, cw20_assets)?])
+    }
+
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        _utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        let result: UserStakeResponseV2 = querier.query_wasm_smart(
+            self.membership,
+            &EnterpriseMembershipQueryMsg::UserStake(UserStakeParamsV2 {
+                user: staker.to_string(),
+            }),
+        )?;
+
+        Ok(result.staked_amount)
+    }
+
+    fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        vote_msg(self.gov, proposal_id, outcome)
+    }
+
+    fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        query_proposal(self.gov, querier, proposal_id)
+    }
+
+    fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        list_proposals(self.gov, querier, start_after, limit)
+    }
+}
+
+/// Enterprise v1 only stakes cw20 tokens (and NFTs), native denom memberships were introduced with
+/// v2 and are integrated through [`EnterpriseV2`].
+fn native_not_supported() -> StdError {
+    StdError::generic_err("native_token not supported for enterprise v1, use enterprise_v2")
+}
+
+pub(super) fn claim_rewards_msg(
+    distributor: &Addr,
+    env: &Env,
+    native_denoms: Vec<String>,
+    cw20_assets: Vec<String>,
+) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: distributor.to_string(),
+        msg: to_json_binary(&EnterpriseDistributorExecuteMsg::ClaimRewards(
+            EnterpriseClaimRewardsMsg {
+                user: env.contract.address.to_string(),
+                native_denoms: Some(native_denoms),
+                cw20_assets: Some(cw20_assets),
+            },
+        ))?,
+        funds: vec![],
+    }))
+}
+
+fn vote_msg(
+    gov: &Addr,
+    proposal_id: u64,
+    outcome: VoteOption,
+) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: gov.to_string(),
+        msg: to_json_binary(&EnterpriseExecuteMsg::CastVote(CastVoteMsg {
+            proposal_id,
+            outcome: match outcome {
+                VoteOption::Yes => VoteOutcome::Yes,
+                VoteOption::No => VoteOutcome::No,
+                VoteOption::Abstain => VoteOutcome::Abstain,
+                VoteOption::NoWithVeto => VoteOutcome::Veto,
+            },
+        }))?,
+        funds: vec![],
+    }))
+}
+
+fn query_proposal(
+    gov: &Addr,
+    querier: &QuerierWrapper,
+    proposal_id: u64,
+) -> StdResult<ProposalResponse> {
+    let result: EnterpriseProposalResponse = querier.query_wasm_smart(
+        gov,
+        &EnterpriseQueryMsg::Proposal(ProposalParams {
+            proposal_id,
+        }),
+    )?;
+
+    Ok(ProposalResponse {
+        end_time_s: expiration_to_end_time(result.proposal.expires)?,
+        choices: None,
+    })
+}
+
+fn list_proposals(
+    gov: &Addr,
+    querier: &QuerierWrapper,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ProposalListItem>> {
+    let result: EnterpriseProposalsResponse = querier.query_wasm_smart(
+        gov,
+        &EnterpriseQueryMsg::Proposals(ProposalsParams {
+            filter: Some(EnterpriseProposalStatusFilter::InProgress),
+            start_after,
+            limit,
+        }),
+    )?;
+
+    Ok(result
+        .proposals
+        .into_iter()
+        .map(|item| ProposalListItem {
+            id: item.proposal.id,
+            end_time_s: expiration_to_seconds(item.proposal.expires),
+            is_open: true,
+            choices: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_json, Timestamp};
+
+    use super::*;
+    use crate::adapters::dao::testing::mock_querier;
+
+    fn adapter<'a>(addr: &'a Addr, fund_distributor: &'a Addr) -> Enterprise<'a> {
+        Enterprise {
+            addr,
+            fund_distributor,
+        }
+    }
+
+    #[test]
+    fn deposit() {
+        let (gov, distributor) = (Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = adapter(&gov, &distributor);
+
+        let msg = adapter
+            .deposit_msg(
+                &AssetInfo::Token {
+                    contract_addr: Addr::unchecked("token"),
+                },
+                Uint128::new(100),
+                "hub".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "gov".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&EnterpriseCw20HookMsg::Stake {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let err = adapter
+            .deposit_msg(
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                Uint128::new(100),
+                "hub".to_string(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(
+                "native_token not supported for enterprise v1, use enterprise_v2"
+            )
+        );
+
+        let err = adapter
+            .unbond_msg(
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                Uint128::new(100),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(
+                "native_token not supported for enterprise v1, use enterprise_v2"
+            )
+        );
+    }
+
+    #[test]
+    fn deposit_v2_stakes_for_hub() {
+        let (gov, membership, distributor) =
+            (Addr::unchecked("gov"), Addr::unchecked("membership"), Addr::unchecked("distributor"));
+        let adapter = EnterpriseV2 {
+            gov: &gov,
+            membership: &membership,
+            distributor: &distributor,
+        };
+
+        let msg = adapter
+            .deposit_msg(
+                &AssetInfo::Token {
+                    contract_addr: Addr::unchecked("token"),
+                },
+                Uint128::new(100),
+                "hub".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "membership".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_json_binary(&EnterpriseV2Cw20HookMsg::Stake {
+                        user: "hub".to_string(),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let msg = adapter
+            .deposit_msg(
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                Uint128::new(100),
+                "hub".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "membership".to_string(),
+                msg: to_json_binary(&EnterpriseExecuteMsgV2::Stake {
+                    user: Some("hub".to_string()),
+                })
+                .unwrap(),
+                funds: vec![coin(100, "utoken")],
+            })
+        );
+
+        let msgs = adapter
+            .claim_rewards_msgs(
+                &mock_env(),
+                &AssetInfo::NativeToken {
+                    denom: "utoken".to_string(),
+                },
+                vec!["uluna".to_string()],
+                vec![],
+            )
+            .unwrap();
+        assert_eq!(
+            msgs,
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "distributor".to_string(),
+                msg: to_json_binary(&EnterpriseDistributorExecuteMsg::ClaimRewards(
+                    EnterpriseClaimRewardsMsg {
+                        user: mock_env().contract.address.to_string(),
+                        native_denoms: Some(vec!["uluna".to_string()]),
+                        cw20_assets: Some(vec![]),
+                    },
+                ))
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+    }
+
+    #[test]
+    fn vote() {
+        let (gov, distributor) = (Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = adapter(&gov, &distributor);
+
+        let msg = adapter
+            .vote_msg(3, VoteOption::NoWithVeto, Addr::unchecked("voter"), Uint128::new(100))
+            .unwrap();
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                ..
+            }) => {
+                assert_eq!(contract_addr, "gov");
+                assert_eq!(
+                    from_json::<EnterpriseExecuteMsg>(&msg).unwrap(),
+                    EnterpriseExecuteMsg::CastVote(CastVoteMsg {
+                        proposal_id: 3,
+                        outcome: VoteOutcome::Veto,
+                    })
+                );
+            },
+            _ => panic!("unexpected message"),
+        }
+
+        let err = adapter
+            .vote_multiple_msg(3, 1, Addr::unchecked("voter"), Uint128::new(100))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("multiple choice voting not supported for enterprise")
+        );
+    }
+
+    #[test]
+    fn proposals() {
+        let (gov, distributor) = (Addr::unchecked("gov"), Addr::unchecked("distributor"));
+        let adapter = adapter(&gov, &distributor);
+
+        let querier = mock_querier(
+            "gov",
+            &EnterpriseQueryMsg::Proposal(ProposalParams {
+                proposal_id: 3,
+            }),
+            &EnterpriseProposalResponse {
+                proposal: Proposal {
+                    id: 3,
+                    expires: Expiration::AtTime(Timestamp::from_seconds(1000)),
+                },
+            },
+        );
+        let proposal = adapter.query_proposal(&QuerierWrapper::new(&querier), 3).unwrap();
+        assert_eq!(
+            proposal,
+            ProposalResponse {
+                end_time_s: 1000,
+                choices: None,
+            }
+        );
+
+        let querier = mock_querier(
+            "gov",
+            &EnterpriseQueryMsg::Proposal(ProposalParams {
+                proposal_id: 3,
+            }),
+            &EnterpriseProposalResponse {
+                proposal: Proposal {
+                    id: 3,
+                    expires: Expiration::AtHeight(1000),
+                },
+            },
+        );
+        let err = adapter.query_proposal(&QuerierWrapper::new(&querier), 3).unwrap_err();
+        assert_eq!(err, StdError::generic_err("not supported expiry type."));
+
+        let querier = mock_querier(
+            "gov",
+            &EnterpriseQueryMsg::Proposals(ProposalsParams {
+                filter: Some(EnterpriseProposalStatusFilter::InProgress),
+                start_after: Some(1),
+                limit: Some(10),
+            }),
+            &EnterpriseProposalsResponse {
+                proposals: vec![EnterpriseProposalResponse {
+                    proposal: Proposal {
+                        id: 2,
+                        expires: Expiration::AtTime(Timestamp::from_seconds(1000)),
+                    },
+                }],
+            },
+        );
+        let proposals =
+            adapter.list_proposals(&QuerierWrapper::new(&querier), Some(1), Some(10)).unwrap();
+        assert_eq!(
+            proposals,
+            vec![ProposalListItem {
+                id: 2,
+                end_time_s: Some(1000),
+                is_open: true,
+                choices: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn staked_balance() {
+        let (gov, membership, distributor) =
+            (Addr::unchecked("gov"), Addr::unchecked("membership"), Addr::unchecked("distributor"));
+        let hub = Addr::unchecked("hub");
+        let utoken = AssetInfo::Token {
+            contract_addr: Addr::unchecked("token"),
+        };
+
+        let querier = mock_querier(
+            "gov",
+            &EnterpriseQueryMsg::UserStake(UserStakeParams {
+                user: "hub".to_string(),
+                start_after: None,
+                limit: None,
+            }),
+            &UserStakeResponse {
+                user_stake: UserStake::Token(TokenUserStake {
+                    amount: Uint128::new(100),
+                }),
+            },
+        );
+        let balance = adapter(&gov, &distributor)
+            .query_staked_balance(&QuerierWrapper::new(&querier), &utoken, &hub)
+            .unwrap();
+        assert_eq!(balance, Uint128::new(100));
+
+        let querier = mock_querier(
+            "membership",
+            &EnterpriseMembershipQueryMsg::UserStake(UserStakeParamsV2 {
+                user: "hub".to_string(),
+            }),
+            &UserStakeResponseV2 {
+                staked_amount: Uint128::new(200),
+            },
+        );
+        let adapter = EnterpriseV2 {
+            gov: &gov,
+            membership: &membership,
+            distributor: &distributor,
+        };
+        let balance =
+            adapter.query_staked_balance(&QuerierWrapper::new(&querier), &utoken, &hub).unwrap();
+        assert_eq!(balance, Uint128::new(200));
+    }
+}
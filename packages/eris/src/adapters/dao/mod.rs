@@ -0,0 +1,478 @@
+mod alliance;
+mod capa;
+mod cw4;
+mod daodao;
+mod enterprise;
+mod reward_source;
+
+pub use alliance::*;
+pub use capa::*;
+pub use cw4::*;
+pub use daodao::*;
+pub use enterprise::*;
+
+use crate::hub::DaoInterface;
+use astroport::asset::AssetInfo;
+use cosmwasm_std::{
+    Addr, CosmosMsg, Env, QuerierWrapper, StdError, StdResult, Uint128, VoteOption,
+};
+use cw20::Expiration;
+use eris_chain_adapter::types::CustomMsgType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResponse {
+    pub end_time_s: u64,
+    /// Number of options, if it is a multiple choice proposal
+    pub choices: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListItem {
+    pub id: u64,
+    /// None if the proposal does not end at a specific time
+    pub end_time_s: Option<u64>,
+    /// Whether the proposal can still be voted on
+    pub is_open: bool,
+    /// Number of options, if it is a multiple choice proposal
+    pub choices: Option<u32>,
+}
+
+/// Integration of a DAO framework. Operations that are not supported by the framework return an
+/// error by default.
+pub trait DaoAdapter {
+    /// Name of the framework used in error messages
+    fn name(&self) -> &'static str;
+
+    fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>>;
+
+    fn unbond_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>>;
+
+    fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err(format!("claiming not supported for {}", self.name())))
+    }
+
+    /// Stakes a single NFT of the `collection` held by the hub.
+    fn deposit_nft_msg(
+        &self,
+        _collection: &Addr,
+        _token_id: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err(format!("nft staking not supported for {}", self.name())))
+    }
+
+    fn unbond_nft_msg(&self, _token_ids: Vec<String>) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err(format!("nft staking not supported for {}", self.name())))
+    }
+
+    fn claim_rewards_msgs(
+        &self,
+        env: &Env,
+        utoken: &AssetInfo,
+        native_denoms: Vec<String>,
+        cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>;
+
+    /// Queries the amount of utoken the DAO reports as staked by `staker`.
+    fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128>;
+
+    fn vote_msg(
+        &self,
+        _proposal_id: u64,
+        _outcome: VoteOption,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err(format!("voting not supported for {}", self.name())))
+    }
+
+    fn vote_multiple_msg(
+        &self,
+        _proposal_id: u64,
+        _option_id: u32,
+        _voter: Addr,
+        _amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Err(StdError::generic_err(format!(
+            "multiple choice voting not supported for {}",
+            self.name()
+        )))
+    }
+
+    fn query_proposal(
+        &self,
+        _querier: &QuerierWrapper,
+        _proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        Err(StdError::generic_err(format!("proposal not supported for {}", self.name())))
+    }
+
+    /// Lists the proposals of the DAO in ascending order of their id.
+    fn list_proposals(
+        &self,
+        _querier: &QuerierWrapper,
+        _start_after: Option<u64>,
+        _limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        Err(StdError::generic_err(format!("proposal not supported for {}", self.name())))
+    }
+
+    /// Queries a proposal of the module that receives `vote_multiple_msg`, whose ids can overlap
+    /// with the ones of `query_proposal`.
+    fn query_proposal_multiple(
+        &self,
+        _querier: &QuerierWrapper,
+        _proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        Err(StdError::generic_err(format!(
+            "multiple choice proposals not supported for {}",
+            self.name()
+        )))
+    }
+
+    fn list_proposals_multiple(
+        &self,
+        _querier: &QuerierWrapper,
+        _start_after: Option<u64>,
+        _limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        Err(StdError::generic_err(format!(
+            "multiple choice proposals not supported for {}",
+            self.name()
+        )))
+    }
+}
+
+impl DaoInterface<Addr> {
+    /// Returns the adapter of the DAO framework.
+    pub fn adapter(&self) -> Box<dyn DaoAdapter + '_> {
+        match self {
+            DaoInterface::Enterprise {
+                addr,
+                fund_distributor,
+            } => Box::new(Enterprise {
+                addr,
+                fund_distributor,
+            }),
+            DaoInterface::EnterpriseV2 {
+                gov,
+                membership,
+                distributor,
+            } => Box::new(EnterpriseV2 {
+                gov,
+                membership,
+                distributor,
+            }),
+            DaoInterface::Cw4 {
+                addr,
+                gov,
+                fund_distributor,
+            } => Box::new(Cw4 {
+                addr,
+                gov,
+                fund_distributor,
+            }),
+            DaoInterface::DaoDao {
+                staking,
+                gov,
+                gov_multiple,
+                cw_rewards,
+            } => Box::new(DaoDao {
+                staking,
+                gov,
+                gov_multiple: gov_multiple.as_ref(),
+                cw_rewards,
+            }),
+            DaoInterface::DaoDaoV2 {
+                staking,
+                gov,
+                gov_multiple,
+                rewards,
+            } => Box::new(DaoDaoV2 {
+                staking,
+                gov,
+                gov_multiple: gov_multiple.as_ref(),
+                rewards,
+            }),
+            DaoInterface::DaoDaoNft {
+                staking,
+                gov,
+                gov_multiple,
+                rewards,
+            } => Box::new(DaoDaoNft {
+                staking,
+                gov,
+                gov_multiple: gov_multiple.as_ref(),
+                rewards,
+            }),
+            DaoInterface::Alliance {
+                addr,
+            } => Box::new(Alliance {
+                addr,
+            }),
+            DaoInterface::Capa {
+                gov,
+            } => Box::new(Capa {
+                gov,
+            }),
+        }
+    }
+
+    pub fn deposit_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+        eris_contract_addr: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().deposit_msg(utoken, amount, eris_contract_addr)
+    }
+
+    pub fn unbond_msg(
+        &self,
+        utoken: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().unbond_msg(utoken, amount)
+    }
+
+    pub fn claim_unbonded_msg(&self) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().claim_unbonded_msg()
+    }
+
+    pub fn deposit_nft_msg(
+        &self,
+        collection: &Addr,
+        token_id: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().deposit_nft_msg(collection, token_id)
+    }
+
+    pub fn unbond_nft_msg(&self, token_ids: Vec<String>) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().unbond_nft_msg(token_ids)
+    }
+
+    pub fn claim_rewards_msgs(
+        &self,
+        env: &Env,
+        utoken: &AssetInfo,
+        native_denoms: Vec<String>,
+        cw20_assets: Vec<String>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        self.adapter().claim_rewards_msgs(env, utoken, native_denoms, cw20_assets)
+    }
+
+    pub fn query_staked_balance(
+        &self,
+        querier: &QuerierWrapper,
+        utoken: &AssetInfo,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        self.adapter().query_staked_balance(querier, utoken, staker)
+    }
+
+    pub fn vote_msg(
+        &self,
+        proposal_id: u64,
+        outcome: VoteOption,
+        voter: Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().vote_msg(proposal_id, outcome, voter, amount)
+    }
+
+    /// Creates the vote message for an option of a multiple choice proposal. For DaoDao the vote is
+    /// sent to `gov_multiple`, or to `gov` if it is not set.
+    pub fn vote_multiple_msg(
+        &self,
+        proposal_id: u64,
+        option_id: u32,
+        voter: Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        self.adapter().vote_multiple_msg(proposal_id, option_id, voter, amount)
+    }
+
+    pub fn query_proposal(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        self.adapter().query_proposal(querier, proposal_id)
+    }
+
+    /// Lists the proposals of the DAO in ascending order of their id.
+    pub fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        self.adapter().list_proposals(querier, start_after, limit)
+    }
+
+    /// Queries a proposal of the multiple choice module. For DaoDao this is `gov_multiple`, or
+    /// `gov` if it is not set.
+    pub fn query_proposal_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+    ) -> StdResult<ProposalResponse> {
+        self.adapter().query_proposal_multiple(querier, proposal_id)
+    }
+
+    /// Lists the proposals of the multiple choice module in ascending order of their id.
+    pub fn list_proposals_multiple(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProposalListItem>> {
+        self.adapter().list_proposals_multiple(querier, start_after, limit)
+    }
+}
+
+/// Returns the end time of a proposal, which needs to be time based.
+fn expiration_to_end_time(expiration: Expiration) -> StdResult<u64> {
+    match expiration {
+        Expiration::AtTime(time) => Ok(time.seconds()),
+        Expiration::AtHeight(_) | Expiration::Never {} => {
+            Err(StdError::generic_err("not supported expiry type."))
+        },
+    }
+}
+
+/// Returns the expiry in seconds, if the expiration is time based.
+fn expiration_to_seconds(expiration: Expiration) -> Option<u64> {
+    match expiration {
+        Expiration::AtTime(time) => Some(time.seconds()),
+        Expiration::AtHeight(_) | Expiration::Never {} => None,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{to_json_binary, ContractResult, SystemError, SystemResult, WasmQuery};
+    use serde::Serialize;
+
+    /// Returns a querier that answers the smart query `query` to `contract` with `response`.
+    pub fn mock_querier<Q: Serialize, R: Serialize>(
+        contract: &str,
+        query: &Q,
+        response: &R,
+    ) -> MockQuerier {
+        let contract = contract.to_string();
+        let query = to_json_binary(query).unwrap();
+        let response = to_json_binary(response).unwrap();
+
+        let mut querier = MockQuerier::new(&[]);
+        querier.update_wasm(move |request| match request {
+            WasmQuery::Smart {
+                contract_addr,
+                msg,
+            } if *contract_addr == contract && *msg == query => {
+                SystemResult::Ok(ContractResult::Ok(response.clone()))
+            },
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: format!("{:?}", request),
+            }),
+        });
+        querier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{MockApi, MockQuerier};
+    use cosmwasm_std::{from_json, to_json_binary};
+
+    use super::*;
+
+    #[test]
+    fn dao_interface_serde_compatible() {
+        let configs = [
+            r#"{"enterprise":{"addr":"gov","fund_distributor":"distributor"}}"#,
+            r#"{"enterprise_v2":{"gov":"gov","membership":"membership","distributor":"distributor"}}"#,
+            r#"{"cw4":{"addr":"staking","gov":"gov","fund_distributor":"distributor"}}"#,
+            r#"{"dao_dao":{"staking":"staking","gov":"gov","cw_rewards":"rewards"}}"#,
+            r#"{"dao_dao_v2":{"staking":"staking","gov":"gov","rewards":[["rewards",1]]}}"#,
+            r#"{"dao_dao_nft":{"staking":"staking","gov":"gov","rewards":[["rewards",1]]}}"#,
+            r#"{"dao_dao_v2":{"staking":"staking","gov":"gov","gov_multiple":"gov_multiple","rewards":[["rewards",1]]}}"#,
+            r#"{"alliance":{"addr":"alliance"}}"#,
+            r#"{"capa":{"gov":"gov"}}"#,
+        ];
+        let names = [
+            "enterprise",
+            "enterprise_v2",
+            "cw4",
+            "daodao",
+            "daodao_v2",
+            "daodao_nft",
+            "daodao_v2",
+            "alliance",
+            "capa",
+        ];
+
+        let api = MockApi::default();
+        for (config, name) in configs.iter().zip(names.iter()) {
+            let interface: DaoInterface<String> = from_json(config.as_bytes()).unwrap();
+            assert_eq!(to_json_binary(&interface).unwrap().as_slice(), config.as_bytes());
+
+            let interface: DaoInterface<Addr> = interface.validate(&api).unwrap();
+            assert_eq!(to_json_binary(&interface).unwrap().as_slice(), config.as_bytes());
+            assert_eq!(interface.adapter().name(), *name);
+        }
+    }
+
+    #[test]
+    fn unsupported_operations() {
+        let interface = DaoInterface::Alliance {
+            addr: Addr::unchecked("alliance"),
+        };
+        let querier = MockQuerier::new(&[]);
+
+        assert_eq!(
+            interface.claim_unbonded_msg().unwrap_err(),
+            StdError::generic_err("claiming not supported for alliance")
+        );
+        assert_eq!(
+            interface
+                .vote_msg(1, VoteOption::Yes, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap_err(),
+            StdError::generic_err("voting not supported for alliance")
+        );
+        assert_eq!(
+            interface
+                .vote_multiple_msg(1, 0, Addr::unchecked("voter"), Uint128::new(100))
+                .unwrap_err(),
+            StdError::generic_err("multiple choice voting not supported for alliance")
+        );
+        assert_eq!(
+            interface
+                .list_proposals_multiple(&QuerierWrapper::new(&querier), None, None)
+                .unwrap_err(),
+            StdError::generic_err("multiple choice proposals not supported for alliance")
+        );
+        assert_eq!(
+            interface.deposit_nft_msg(&Addr::unchecked("collection"), "1".to_string()).unwrap_err(),
+            StdError::generic_err("nft staking not supported for alliance")
+        );
+    }
+}
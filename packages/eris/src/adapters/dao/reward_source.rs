@@ -0,0 +1,112 @@
+use super::{CwRewardsClaimRewardsMsg, CwRewardsExecuteMsg, DaoDaoRewardsExecuteMsg};
+use crate::hub::RewardSource;
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, Env, StdResult, WasmMsg};
+use eris_chain_adapter::types::CustomMsgType;
+
+impl RewardSource<Addr> {
+    /// Creates the message claiming the rewards of the hub from the source.
+    pub fn claim_msg(
+        &self,
+        env: &Env,
+        native_denoms: Vec<String>,
+        cw20_assets: Vec<String>,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        match self {
+            RewardSource::CwRewards {
+                contract,
+            } => execute_msg(
+                contract,
+                to_json_binary(&CwRewardsExecuteMsg::ClaimRewards(CwRewardsClaimRewardsMsg {
+                    callback: None,
+                }))?,
+            ),
+            RewardSource::DaoRewardsDistributor {
+                contract,
+                id,
+            } => execute_msg(
+                contract,
+                to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                    id: *id,
+                })?,
+            ),
+            RewardSource::EnterpriseDistributor {
+                contract,
+            } => super::enterprise::claim_rewards_msg(contract, env, native_denoms, cw20_assets),
+            RewardSource::Generic {
+                contract,
+                msg,
+            } => execute_msg(contract, msg.clone()),
+        }
+    }
+}
+
+fn execute_msg(contract: &Addr, msg: Binary) -> StdResult<CosmosMsg<CustomMsgType>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg,
+        funds: vec![],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_env, MockApi};
+
+    use super::*;
+    use crate::adapters::dao::{EnterpriseClaimRewardsMsg, EnterpriseDistributorExecuteMsg};
+
+    #[test]
+    fn claim_msgs() {
+        let api = MockApi::default();
+        let env = mock_env();
+
+        let source = RewardSource::DaoRewardsDistributor {
+            contract: "distributor".to_string(),
+            id: 3,
+        };
+        assert_eq!(
+            source.validate(&api).unwrap().claim_msg(&env, vec![], vec![]).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "distributor".to_string(),
+                msg: to_json_binary(&DaoDaoRewardsExecuteMsg::Claim {
+                    id: 3,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let source = RewardSource::EnterpriseDistributor {
+            contract: Addr::unchecked("funds"),
+        };
+        assert_eq!(
+            source.claim_msg(&env, vec!["uluna".to_string()], vec![]).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "funds".to_string(),
+                msg: to_json_binary(&EnterpriseDistributorExecuteMsg::ClaimRewards(
+                    EnterpriseClaimRewardsMsg {
+                        user: env.contract.address.to_string(),
+                        native_denoms: Some(vec!["uluna".to_string()]),
+                        cw20_assets: Some(vec![]),
+                    }
+                ))
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let msg = Binary::from(br#"{"claim":{}}"#);
+        let source = RewardSource::Generic {
+            contract: Addr::unchecked("airdrop"),
+            msg: msg.clone(),
+        };
+        assert_eq!(
+            source.claim_msg(&env, vec![], vec![]).unwrap(),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "airdrop".to_string(),
+                msg,
+                funds: vec![],
+            })
+        );
+    }
+}
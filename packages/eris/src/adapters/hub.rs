@@ -41,6 +41,21 @@ impl Hub {
         }))
     }
 
+    pub fn vote_multiple_msg(
+        &self,
+        proposal_id: u64,
+        option_id: u32,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.0.to_string(),
+            msg: to_json_binary(&ExecuteMsg::VoteMultiple {
+                proposal_id,
+                option_id,
+            })?,
+            funds: vec![],
+        }))
+    }
+
     pub fn query_config(&self, querier: &QuerierWrapper) -> StdResult<ConfigResponse> {
         let config: ConfigResponse =
             querier.query_wasm_smart(self.0.to_string(), &QueryMsg::Config {})?;
@@ -3,5 +3,7 @@ pub mod asset;
 pub mod dao;
 pub mod generator;
 pub mod hub;
+pub mod nft;
 pub mod router;
 pub mod token;
+pub mod ustake_snapshot;
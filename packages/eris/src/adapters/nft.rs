@@ -0,0 +1,61 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, StdResult, WasmMsg};
+use eris_chain_adapter::types::CustomMsgType;
+
+#[cw_serde]
+pub enum Cw721ExecuteMsg {
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+}
+
+/// Cw721ReceiveMsg should be de/serialized under `ReceiveNft()` variant in a ExecuteMsg
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+/// A cw721 collection
+pub struct Nft(pub Addr);
+
+impl Nft {
+    pub fn transfer_msg(
+        &self,
+        recipient: &Addr,
+        token_id: String,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.0.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    pub fn send_msg(
+        &self,
+        contract: &Addr,
+        token_id: String,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.0.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::SendNft {
+                contract: contract.to_string(),
+                token_id,
+                msg,
+            })?,
+            funds: vec![],
+        }))
+    }
+}
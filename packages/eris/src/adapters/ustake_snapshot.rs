@@ -0,0 +1,41 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
+use cw20::BalanceResponse;
+
+use crate::ustake_snapshot::QueryMsg;
+
+#[cw_serde]
+pub struct UstakeSnapshot(pub Addr);
+
+impl UstakeSnapshot {
+    /// Balance of the address at the start of the block `height`
+    pub fn query_balance_at(
+        &self,
+        querier: &QuerierWrapper,
+        address: &Addr,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        let res: BalanceResponse = querier.query_wasm_smart(
+            self.0.to_string(),
+            &QueryMsg::BalanceAt {
+                address: address.to_string(),
+                height: Some(height),
+            },
+        )?;
+        Ok(res.balance)
+    }
+
+    /// Total supply at the start of the block `height`
+    pub fn query_total_supply_at(
+        &self,
+        querier: &QuerierWrapper,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        querier.query_wasm_smart(
+            self.0.to_string(),
+            &QueryMsg::TotalSupplyAt {
+                height: Some(height),
+            },
+        )
+    }
+}
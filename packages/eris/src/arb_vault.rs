@@ -2,8 +2,10 @@ use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 use cosmwasm_std::{
-    to_json_binary, Addr, Api, Binary, CosmosMsg, Decimal, StdError, StdResult, Uint128, WasmMsg,
+    to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult, Uint128,
+    WasmMsg,
 };
+use cw20::Cw20ReceiveMsg;
 use eris_chain_adapter::types::CustomMsgType;
 
 // /// The default swap slippage
@@ -18,8 +20,8 @@ pub struct InstantiateMsg {
 
     pub owner: String,
 
-    // Used base token
-    pub utoken: String,
+    // Used base token (native or cw20)
+    pub utoken: AssetInfo,
     // execution threshold
     pub utilization_method: UtilizationMethod,
     // min unbond time 21+3 * 24 * 60 * 60
@@ -39,13 +41,54 @@ pub struct LsdConfig<T> {
     pub disabled: bool,
     pub name: String,
     pub lsd_type: LsdType<T>,
+    // without a schedule every unbondable xtoken is unbonded on each call
+    #[serde(default)]
+    pub unbond_schedule: Option<UnbondSchedule>,
+    #[serde(default)]
+    pub risk_limits: Option<RiskLimits>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RiskLimits {
+    // max utoken value held in the LSD (xtoken, unbonding and withdrawable) in bps of the tvl
+    pub max_exposure_bps: Option<u16>,
+    // max drop of the exchange rate from the highest seen exchange rate in bps
+    pub max_xfactor_drop_bps: Option<u16>,
+    // max time an unbonding can be overdue without being withdrawable
+    pub max_unbonding_delay_s: Option<u64>,
+}
+
+impl RiskLimits {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.max_exposure_bps.unwrap_or_default() > 10000
+            || self.max_xfactor_drop_bps.unwrap_or_default() > 10000
+        {
+            return Err(StdError::generic_err("Risk limit bps too high"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct UnbondSchedule {
+    // minimum xtoken balance required to start an unbonding. The xtoken is only unbonded once the
+    // pending batch of the hub is due, so that it joins the batch right before its submission.
+    pub min_batch: Uint128,
 }
 
 impl LsdConfig<String> {
     pub fn validate(self, api: &dyn Api) -> StdResult<LsdConfig<Addr>> {
+        if let Some(risk_limits) = &self.risk_limits {
+            risk_limits.validate()?;
+        }
+
         Ok(LsdConfig {
             disabled: self.disabled,
             name: self.name,
+            unbond_schedule: self.unbond_schedule,
+            risk_limits: self.risk_limits,
             lsd_type: match self.lsd_type {
                 LsdType::Eris {
                     addr,
@@ -61,6 +104,13 @@ impl LsdConfig<String> {
                     addr: api.addr_validate(&addr)?,
                     denom,
                 },
+                LsdType::ErisDao {
+                    addr,
+                    denom,
+                } => LsdType::ErisDao {
+                    addr: api.addr_validate(&addr)?,
+                    denom,
+                },
             },
         })
     }
@@ -76,6 +126,11 @@ pub enum LsdType<T> {
         addr: T,
         denom: String,
     },
+    /// DAO LST hub (dao-lst), unbonding through the hub's batches
+    ErisDao {
+        addr: T,
+        denom: String,
+    },
 }
 
 impl LsdType<String> {
@@ -89,13 +144,49 @@ impl LsdType<String> {
                 addr,
                 ..
             } => format!("backbone_{0}", addr),
+            LsdType::ErisDao {
+                addr,
+                ..
+            } => format!("eris_dao_{0}", addr),
         }
     }
 }
 
 #[cw_serde]
 pub enum UtilizationMethod {
+    // (profit, max utilization) only the specified profits are supported
     Steps(Vec<(Decimal, Decimal)>),
+    // (profit, max utilization) linearly interpolated between the points, capped at the last point
+    PiecewiseLinear(Vec<(Decimal, Decimal)>),
+    // utilization rises linearly from 0 at min_profit to kink_utilization at kink_profit
+    // and from there to 100 % at max_profit
+    Kinked {
+        min_profit: Decimal,
+        kink_profit: Decimal,
+        kink_utilization: Decimal,
+        max_profit: Decimal,
+    },
+}
+
+impl UtilizationMethod {
+    /// Returns the (profit, max utilization) points that define the method.
+    pub fn points(&self) -> Vec<(Decimal, Decimal)> {
+        match self {
+            UtilizationMethod::Steps(points) | UtilizationMethod::PiecewiseLinear(points) => {
+                points.clone()
+            },
+            UtilizationMethod::Kinked {
+                min_profit,
+                kink_profit,
+                kink_utilization,
+                max_profit,
+            } => vec![
+                (*min_profit, Decimal::zero()),
+                (*kink_profit, *kink_utilization),
+                (*max_profit, Decimal::one()),
+            ],
+        }
+    }
 }
 
 #[cw_serde]
@@ -119,21 +210,24 @@ pub enum ExecuteMsg {
         receiver: Option<String>,
     },
 
+    // User action: Receive hook for cw20 utokens, see [`ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+
     // User action: Withdraw all unbonded funds
     WithdrawUnbonded {},
     // User action: Withdraw any unbond item immediate if possible
     WithdrawImmediate {
         id: u64,
+        // withdraws only a part of the unbond item, the rest stays unbonding (default full amount)
+        amount: Option<Uint128>,
     },
 
-    // /// Swap is allowed between the [TOKEN] and the arb[TOKEN]
-    // Swap {
-    //     offer_asset: Asset,
-    //     ask_asset_info: Option<AssetInfo>,
-    //     belief_price: Option<Decimal>,
-    //     max_spread: Option<Decimal>,
-    //     to: Option<String>,
-    // },
+    // User action: Swap a supported LSD directly into the utoken at the exchange rate minus the first profit step that can cover it
+    Swap {
+        offer_asset: Asset,
+        min_receive: Option<Uint128>,
+        to: Option<String>,
+    },
 
     // Admin User: Update config
     UpdateConfig {
@@ -153,6 +247,24 @@ pub enum ExecuteMsg {
         set_whitelist: Option<Vec<String>>,
         // opens up executions so anyone can execute.
         remove_whitelist: Option<bool>,
+        // sets or clears the unbond schedule of a LSD
+        set_unbond_schedule: Option<(String, Option<UnbondSchedule>)>,
+        // sets or clears the risk limits of a LSD
+        set_risk_limits: Option<(String, Option<RiskLimits>)>,
+        // resets the highest seen exchange rate of a LSD to its current exchange rate, e.g. after an accepted slashing
+        reset_xfactor_high: Option<String>,
+        // contracts notified with a [`PushExecuteMsg`] when a share balance changes. Transfers are
+        // only pushed if the vault is registered as before-send hook of the LP denom, which is
+        // done when the first contracts are set. Fails on chains without before-send hooks.
+        set_push_update_contracts: Option<Vec<String>>,
+    },
+
+    // Anyone: Mints the performance fee as LP shares to the protocol on exchange rate growth above the high-water mark
+    CrystallizeFees {},
+
+    // Anyone: Pushes the current share balance of the users to the push_update_contracts, e.g. after a transfer
+    PushShareUpdates {
+        users: Vec<String>,
     },
 
     // Bot: Execute arbitrage
@@ -165,6 +277,14 @@ pub enum ExecuteMsg {
         wanted_profit: Decimal,
     },
 
+    // Anyone: Borrow utoken that needs to be repaid with the flash_loan_fee in the same transaction.
+    // The recipient (default sender) is called with the msg and the borrowed amount.
+    FlashLoan {
+        amount: Uint128,
+        msg: Binary,
+        recipient: Option<String>,
+    },
+
     // Bot: Withdraw unbonded liquidity from liquid staking providers
     WithdrawFromLiquidStaking {
         // specify which adapters should be withdrawn
@@ -193,13 +313,51 @@ pub enum ExecuteMsg {
     Callback(CallbackMsg),
 }
 
-/// This structure describes the callback messages of the contract.
+/// This structure describes the cw20 receive hooks of the contract.
+#[cw_serde]
+pub enum ReceiveMsg {
+    // User action: Provide cw20 utoken liquidity to the pool and specify who will receive the pool token.
+    Deposit {
+        receiver: Option<String>,
+    },
+}
+
+/// Message sent to each of the push_update_contracts when the LP share balance of a user changes,
+/// so that gauges and farms can track balances without taking custody of the shares.
+#[cw_serde]
+pub enum PushExecuteMsg {
+    UpdateShare {
+        user: String,
+        // LP balance of the user after the execution
+        balance: Uint128,
+        total_supply: Uint128,
+    },
+}
+
+/// Tokenfactory before-send hook of the LP denom, called by the chain for every transfer
+#[cw_serde]
+pub enum SudoMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+    TrackBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+}
+
 #[cw_serde]
 pub enum CallbackMsg {
     AssertResult {
         result_token: AssetInfo,
         wanted_profit: Decimal,
     },
+    AssertFlashLoanRepaid {
+        fee_amount: Uint128,
+    },
 }
 
 impl CallbackMsg {
@@ -251,6 +409,71 @@ pub enum QueryMsg {
         start_after_d: Option<u64>,
         limit: Option<u32>,
     },
+
+    /// Simulates a swap of a supported LSD into the utoken
+    #[returns(SimulationResponse)]
+    Simulation {
+        offer_asset: Asset,
+    },
+
+    /// Query the fees and received amount when withdrawing (a part of) an unbond item immediately
+    #[returns(WithdrawImmediateQuoteResponse)]
+    WithdrawImmediateQuote {
+        address: String,
+        id: u64,
+        amount: Option<Uint128>,
+    },
+
+    /// Shows what the next UnbondFromLiquidStaking would unbond for each LSD and why
+    #[returns(UnbondPlanResponse)]
+    UnbondPlan {
+        names: Option<Vec<String>>,
+    },
+}
+
+#[cw_serde]
+pub enum UnbondPlanReason {
+    // the xtoken balance will be unbonded
+    Ready,
+    // no xtoken balance
+    NothingToUnbond,
+    // the xtoken balance is below the min_batch of the schedule
+    BelowMinBatch,
+    // the pending batch of the hub is not due yet
+    WaitingForBatch,
+}
+
+#[cw_serde]
+pub struct UnbondPlanItem {
+    pub name: String,
+    // xtoken amount that would be unbonded
+    pub amount: Uint128,
+    // earliest time the next unbonding can be started
+    pub next_unbond_time: u64,
+    pub reason: UnbondPlanReason,
+}
+
+impl UnbondPlanItem {
+    pub fn is_ready(&self) -> bool {
+        self.reason == UnbondPlanReason::Ready
+    }
+}
+
+#[cw_serde]
+pub struct UnbondPlanResponse {
+    pub items: Vec<UnbondPlanItem>,
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    // utoken received by the user
+    pub return_amount: Uint128,
+    // utoken value of the offered asset
+    pub offer_value: Uint128,
+    // profit step used as the discount
+    pub profit_step: Decimal,
+    // profit of the vault
+    pub profit_amount: Uint128,
 }
 
 #[cw_serde]
@@ -269,6 +492,18 @@ pub struct UnbondRequestsResponse {
     pub requests: Vec<UnbondItem>,
 }
 
+#[cw_serde]
+pub struct WithdrawImmediateQuoteResponse {
+    // utoken taken from the unbond item
+    pub withdraw_amount: Uint128,
+    // utoken received by the user
+    pub receive_amount: Uint128,
+    pub withdraw_protocol_fee: Uint128,
+    pub withdraw_pool_fee: Uint128,
+    // utoken that stays in the unbond item
+    pub remaining_amount: Uint128,
+}
+
 #[cw_serde]
 pub struct WithdrawableResponse {
     pub withdrawable: Uint128,
@@ -288,13 +523,14 @@ pub struct ConfigResponse {
     pub owner: Addr,
     pub whitelist: Option<Vec<Addr>>,
     pub lp_token: LpToken,
+    pub push_update_contracts: Vec<Addr>,
 }
 
 /// ## Description
 /// This structure stores the main config parameters for a constant product pair contract.
 #[cw_serde]
 pub struct Config<T> {
-    pub utoken: String,
+    pub utoken: AssetInfo,
     pub utilization_method: UtilizationMethod,
     pub unbond_time_s: u64,
     pub lsds: Vec<LsdConfig<T>>,
@@ -314,9 +550,34 @@ pub struct FeeConfig<T> {
     pub protocol_performance_fee: Decimal,
     pub protocol_withdraw_fee: Decimal,
     pub immediate_withdraw_fee: Decimal,
+    // fee on the amount of a flash loan that needs to be repaid together with the principal
+    #[serde(default)]
+    pub flash_loan_fee: Decimal,
+    #[serde(default)]
+    pub performance_fee_mode: PerformanceFeeMode,
 }
 pub type ValidatedFeeConfig = FeeConfig<Addr>;
 
+#[cw_serde]
+#[derive(Default)]
+pub enum PerformanceFeeMode {
+    // the performance fee is taken from the profit of each arbitrage, swap and flash loan
+    #[default]
+    PerTrade,
+    // the performance fee is minted as LP shares through CrystallizeFees on exchange rate growth above the high-water mark
+    Crystallized,
+}
+
+impl<T> FeeConfig<T> {
+    /// Returns the performance fee taken from each trade, zero when the fees are crystallized.
+    pub fn trade_performance_fee(&self) -> Decimal {
+        match self.performance_fee_mode {
+            PerformanceFeeMode::PerTrade => self.protocol_performance_fee,
+            PerformanceFeeMode::Crystallized => Decimal::zero(),
+        }
+    }
+}
+
 impl FeeConfig<String> {
     pub fn validate(self, api: &dyn Api) -> StdResult<FeeConfig<Addr>> {
         if self.protocol_performance_fee > Decimal::percent(20) {
@@ -331,11 +592,17 @@ impl FeeConfig<String> {
             return Err(StdError::generic_err("Immediate withdraw fee too high"));
         }
 
+        if self.flash_loan_fee > Decimal::percent(5) {
+            return Err(StdError::generic_err("Flash loan fee too high"));
+        }
+
         Ok(FeeConfig {
             protocol_fee_contract: api.addr_validate(&self.protocol_fee_contract)?,
             protocol_performance_fee: self.protocol_performance_fee,
             protocol_withdraw_fee: self.protocol_withdraw_fee,
             immediate_withdraw_fee: self.immediate_withdraw_fee,
+            flash_loan_fee: self.flash_loan_fee,
+            performance_fee_mode: self.performance_fee_mode,
         })
     }
 }
@@ -355,6 +622,31 @@ pub struct UserDetails {}
 #[cw_serde]
 pub struct StateDetails {
     pub takeable_steps: Vec<(Decimal, Uint128)>,
+    #[serde(default)]
+    pub lsd_risks: Vec<LsdRisk>,
+}
+
+#[cw_serde]
+pub struct LsdRisk {
+    pub name: String,
+    // utoken value held in the LSD (xtoken, unbonding and withdrawable)
+    pub exposure: Uint128,
+    pub max_exposure: Option<Uint128>,
+    pub xfactor: Decimal,
+    // highest exchange rate seen during arbitrage
+    pub xfactor_high: Decimal,
+    // expected end of the oldest unbonding that is not withdrawable yet
+    pub oldest_unbonding_end: Option<u64>,
+    // set when arbitrage into the LSD is halted
+    pub circuit_breaker: Option<CircuitBreaker>,
+}
+
+#[cw_serde]
+pub enum CircuitBreaker {
+    // exchange rate dropped more than max_xfactor_drop_bps
+    Depeg,
+    // unbonding overdue more than max_unbonding_delay_s
+    UnbondingStalled,
 }
 
 #[cw_serde]
@@ -1,7 +1,8 @@
 use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, Api, CosmosMsg, Decimal, Empty, StdResult, Uint128, VoteOption, WasmMsg,
+    to_json_binary, Addr, Api, Binary, CosmosMsg, Decimal, Empty, StdResult, Uint128, VoteOption,
+    WasmMsg,
 };
 use cw20::Cw20ReceiveMsg;
 use eris_chain_adapter::types::{
@@ -10,6 +11,8 @@ use eris_chain_adapter::types::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::adapters::nft::Cw721ReceiveMsg;
+
 // StageType = DEX
 // DenomType = Chain specific denom
 // Option<Decimal> = Price
@@ -48,6 +51,7 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum DaoInterface<T> {
+    /// Enterprise v1, only for cw20 utokens. DAOs with a native denom membership use `EnterpriseV2`.
     Enterprise {
         addr: T,
         fund_distributor: T,
@@ -70,6 +74,9 @@ pub enum DaoInterface<T> {
         staking: T,
         // calling vote (CW3)
         gov: T,
+        /// dao-proposal-multiple module for multiple choice votes, if it differs from `gov`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gov_multiple: Option<T>,
         /// entropic variant of rewards claimable
         cw_rewards: T,
     },
@@ -79,6 +86,22 @@ pub enum DaoInterface<T> {
         staking: T,
         // calling vote (CW3)
         gov: T,
+        /// dao-proposal-multiple module for multiple choice votes, if it differs from `gov`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gov_multiple: Option<T>,
+        // calling claim with id on each of the contracts
+        rewards: Vec<(T, u64)>,
+    },
+    /// NFT staking (dao-voting-cw721-staked). The utoken is the cw721 collection and each NFT
+    /// counts as one utoken. Harvested rewards can only be swapped into ustake, which is burned.
+    DaoDaoNft {
+        // calling unstake, claim nfts
+        staking: T,
+        // calling vote (CW3)
+        gov: T,
+        /// dao-proposal-multiple module for multiple choice votes, if it differs from `gov`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gov_multiple: Option<T>,
         // calling claim with id on each of the contracts
         rewards: Vec<(T, u64)>,
     },
@@ -131,33 +154,114 @@ impl DaoInterface<String> {
             DaoInterface::DaoDao {
                 staking: addr,
                 gov,
+                gov_multiple,
                 cw_rewards: fund_distributor,
             } => DaoInterface::DaoDao {
                 staking: api.addr_validate(addr)?,
                 gov: api.addr_validate(gov)?,
+                gov_multiple: gov_multiple.as_ref().map(|a| api.addr_validate(a)).transpose()?,
                 cw_rewards: api.addr_validate(fund_distributor)?,
             },
             DaoInterface::DaoDaoV2 {
                 staking: addr,
                 gov,
+                gov_multiple,
                 rewards: fund_distributor,
             } => DaoInterface::DaoDaoV2 {
                 staking: api.addr_validate(addr)?,
                 gov: api.addr_validate(gov)?,
+                gov_multiple: gov_multiple.as_ref().map(|a| api.addr_validate(a)).transpose()?,
                 rewards: fund_distributor
                     .clone()
                     .into_iter()
                     .map(|(contract, claim_id)| Ok((api.addr_validate(&contract)?, claim_id)))
                     .collect::<StdResult<Vec<_>>>()?,
             },
+            DaoInterface::DaoDaoNft {
+                staking,
+                gov,
+                gov_multiple,
+                rewards,
+            } => DaoInterface::DaoDaoNft {
+                staking: api.addr_validate(staking)?,
+                gov: api.addr_validate(gov)?,
+                gov_multiple: gov_multiple.as_ref().map(|a| api.addr_validate(a)).transpose()?,
+                rewards: rewards
+                    .iter()
+                    .map(|(contract, claim_id)| Ok((api.addr_validate(contract)?, *claim_id)))
+                    .collect::<StdResult<Vec<_>>>()?,
+            },
+        })
+    }
+}
+
+/// Additional contract the hub claims rewards from during harvest, independent of the DAO interface
+#[cw_serde]
+pub enum RewardSource<T> {
+    /// entropic cw-rewards contract
+    CwRewards {
+        contract: T,
+    },
+    /// dao-rewards-distributor, claiming the distribution with the id
+    DaoRewardsDistributor {
+        contract: T,
+        id: u64,
+    },
+    /// enterprise funds distributor, claiming the native_denoms and cw20_assets of the harvest
+    EnterpriseDistributor {
+        contract: T,
+    },
+    /// any contract executed with the msg
+    Generic {
+        contract: T,
+        msg: Binary,
+    },
+}
+
+impl RewardSource<String> {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<RewardSource<Addr>> {
+        Ok(match self {
+            RewardSource::CwRewards {
+                contract,
+            } => RewardSource::CwRewards {
+                contract: api.addr_validate(contract)?,
+            },
+            RewardSource::DaoRewardsDistributor {
+                contract,
+                id,
+            } => RewardSource::DaoRewardsDistributor {
+                contract: api.addr_validate(contract)?,
+                id: *id,
+            },
+            RewardSource::EnterpriseDistributor {
+                contract,
+            } => RewardSource::EnterpriseDistributor {
+                contract: api.addr_validate(contract)?,
+            },
+            RewardSource::Generic {
+                contract,
+                msg,
+            } => RewardSource::Generic {
+                contract: api.addr_validate(contract)?,
+                msg: msg.clone(),
+            },
         })
     }
 }
 
+impl<T> DaoInterface<T> {
+    /// Whether NFTs of a collection are staked instead of a fungible token
+    pub fn is_nft(&self) -> bool {
+        matches!(self, DaoInterface::DaoDaoNft { .. })
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Implements the Cw20 receiver interface
     Receive(Cw20ReceiveMsg),
+    /// Implements the Cw721 receiver interface, used to bond NFTs of the collection
+    ReceiveNft(Cw721ReceiveMsg),
     /// Bond specified amount of Token
     Bond {
         receiver: Option<String>,
@@ -194,6 +298,36 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         vote: VoteOption,
     },
+    /// Vote for an option of a multiple choice proposal (only allowed by the vote_operator)
+    VoteMultiple {
+        proposal_id: u64,
+        option_id: u32,
+    },
+    /// Opens a proposal for ustake holders to vote on directly (only allowed by the vote_operator).
+    /// The merkle root commits to `{address}{ustake}` leaves of the ustake balances at
+    /// `snapshot_height`. Requires an ustake snapshot contract, which caps the ustake of a holder
+    /// by its balance. The vote_operator can no longer vote on the proposal itself.
+    OpenHolderVote {
+        proposal_id: u64,
+        snapshot_height: u64,
+        merkle_root: String,
+        /// Timestamp in seconds after which voting ends and the tally can be forwarded. Has to be
+        /// before the proposal ends in the DAO.
+        end_time: u64,
+    },
+    /// Casts the sender's vote on a proposal opened for holders, `ustake` being the sender's
+    /// balance in the snapshot. Voting again replaces the previous vote.
+    HolderVote {
+        proposal_id: u64,
+        ustake: Uint128,
+        proof: Vec<String>,
+        votes: Vec<(Decimal, VoteOption)>,
+    },
+    /// Forwards the option with the most ustake of the tallied holder votes to the DAO once voting
+    /// ended.
+    ForwardHolderVote {
+        proposal_id: u64,
+    },
 
     /// Callbacks; can only be invoked by the contract itself
     Callback(CallbackMsg),
@@ -225,6 +359,16 @@ pub enum ExecuteMsg {
 
         /// Update the DAO config
         dao_interface: Option<DaoInterface<String>>,
+        /// Replaces the additional contracts rewards are claimed from during harvest
+        reward_sources: Option<Vec<RewardSource<String>>>,
+        /// Period in seconds over which harvested rewards are released into the exchange rate, 0
+        /// releases them immediately
+        reward_vesting_period: Option<u64>,
+        /// Sets the contract mints and burns of ustake are reported to, which records historical
+        /// balances. It needs to be registered as before-send hook of the denom to track
+        /// transfers, and the hub must never be one of its module accounts. An empty string
+        /// removes it.
+        ustake_snapshot: Option<String>,
     },
 
     /// Submit an unbonding request to the current unbonding queue; automatically invokes `unbond`
@@ -237,6 +381,28 @@ pub enum ExecuteMsg {
     Claim {
         claims: Vec<ClaimType>,
     },
+
+    /// Locks the contract's current balance of the provided assets and distributes it pro-rata to
+    /// the ustake holders of a snapshot. The merkle root commits to `{address}{ustake}` leaves of
+    /// the ustake balances at `snapshot_height`. If an ustake snapshot contract is configured,
+    /// `total_ustake` has to match its supply and claims are capped by its balances. Only
+    /// callable by the owner.
+    CreateAirdrop {
+        snapshot_height: u64,
+        merkle_root: String,
+        total_ustake: Uint128,
+        assets: Vec<AssetInfo>,
+    },
+    /// Claims the sender's share of an airdrop, `ustake` being the sender's balance in the snapshot
+    ClaimAirdrop {
+        id: u64,
+        ustake: Uint128,
+        proof: Vec<String>,
+    },
+    /// Removes an airdrop and releases the unclaimed assets. Only callable by the owner.
+    CloseAirdrop {
+        id: u64,
+    },
 }
 
 #[cw_serde]
@@ -334,6 +500,66 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+
+    /// Compares the internally tracked bonded amount with the amount the DAO reports as staked by
+    /// the hub. Response: `HealthResponse`
+    #[returns(HealthResponse)]
+    Health {},
+
+    /// Token ids of the NFTs that are unbonding or not yet withdrawn in a previous batch. Only used
+    /// when staking NFTs. Response: `Vec<String>`
+    #[returns(Vec<String>)]
+    BatchTokenIds(u64),
+
+    /// Harvested rewards that are not yet released into the exchange rate. Response: `RewardVesting`
+    #[returns(RewardVesting)]
+    RewardVesting {},
+
+    /// The vote the hub cast on a proposal. If `multiple` is set, the proposal is one of the
+    /// multiple choice module. Response: `HubVote`
+    #[returns(HubVote)]
+    Vote {
+        proposal_id: u64,
+        multiple: Option<bool>,
+    },
+    /// Enumerate all votes the hub cast, by proposal id. If `multiple` is set, the votes on
+    /// proposals of the multiple choice module are enumerated. Response: `Vec<HubVote>`
+    #[returns(Vec<HubVote>)]
+    Votes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        multiple: Option<bool>,
+    },
+
+    /// A proposal opened for ustake holders to vote on. Response: `HolderProposal`
+    #[returns(HolderProposal)]
+    HolderProposal {
+        proposal_id: u64,
+    },
+    /// The vote a holder cast on a proposal opened for holders. Response: `HolderBallot`
+    #[returns(HolderBallot)]
+    HolderVote {
+        proposal_id: u64,
+        voter: String,
+    },
+
+    /// An airdrop distributed to ustake holders. Response: `Airdrop`
+    #[returns(Airdrop)]
+    Airdrop {
+        id: u64,
+    },
+    /// Enumerate all open airdrops. Response: `Vec<Airdrop>`
+    #[returns(Vec<Airdrop>)]
+    Airdrops {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Whether the address already claimed its share of the airdrop. Response: `bool`
+    #[returns(bool)]
+    AirdropClaimed {
+        id: u64,
+        address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -369,6 +595,14 @@ pub struct ConfigResponse {
 
     /// address of the DAO
     pub dao_interface: DaoInterface<Addr>,
+
+    /// Additional contracts rewards are claimed from during harvest
+    #[serde(default)]
+    pub reward_sources: Vec<RewardSource<Addr>>,
+
+    /// Contract recording historical ustake balances
+    #[serde(default)]
+    pub ustake_snapshot: Option<String>,
 }
 
 #[cw_serde]
@@ -389,6 +623,31 @@ pub struct StateResponse {
     pub tvl_utoken: Uint128,
 }
 
+#[cw_serde]
+pub struct HealthResponse {
+    /// Amount of utoken bonded according to the internal accounting, including vesting rewards
+    pub total_utoken_bonded: Uint128,
+    /// Amount of utoken staked by the hub according to the DAO
+    pub dao_staked: Uint128,
+    /// Absolute difference between the two amounts
+    pub delta: Uint128,
+    /// Whether the DAO reports less than the internal accounting
+    pub deficit: bool,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RewardVesting {
+    /// Period in seconds over which harvested rewards are released linearly, 0 if disabled
+    pub period: u64,
+    /// Amount of `utoken` staked but not yet released into the exchange rate
+    pub pending: Uint128,
+    /// Time from which the pending amount is released
+    pub start_time: u64,
+    /// Time when the pending amount is fully released
+    pub end_time: u64,
+}
+
 #[cw_serde]
 pub struct PendingBatch {
     /// ID of this batch
@@ -517,6 +776,73 @@ pub enum ClaimType {
         token: AssetInfo,
         recipient: String,
     },
+    /// Claims from a cw20-merkle-airdrop compatible contract
+    Merkle {
+        contract: String,
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
+}
+
+#[cw_serde]
+pub enum HubVoteOption {
+    Single(VoteOption),
+    Multiple(u32),
+}
+
+#[cw_serde]
+pub struct HubVote {
+    pub proposal_id: u64,
+    pub vote: HubVoteOption,
+    /// The hub's total_utoken_bonded when the vote was cast
+    pub voting_power: Uint128,
+    /// Timestamp of the vote in seconds
+    pub timestamp: u64,
+    /// Vote operator that cast the vote
+    pub operator: Addr,
+}
+
+#[cw_serde]
+pub struct HolderProposal {
+    pub proposal_id: u64,
+    /// Height of the ustake balance snapshot
+    pub snapshot_height: u64,
+    /// Hex encoded sha256 merkle root of the snapshot
+    pub merkle_root: String,
+    /// Timestamp in seconds after which voting ends
+    pub end_time: u64,
+    /// Voted ustake per option
+    pub tally: Vec<(VoteOption, Uint128)>,
+    /// Whether the tally was forwarded to the DAO
+    pub forwarded: bool,
+    /// Snapshot contract capping the ustake of the holders
+    pub ustake_snapshot: Addr,
+}
+
+#[cw_serde]
+pub struct HolderBallot {
+    /// The holder's ustake in the snapshot
+    pub ustake: Uint128,
+    pub votes: Vec<(Decimal, VoteOption)>,
+}
+
+#[cw_serde]
+pub struct Airdrop {
+    pub id: u64,
+    /// Height of the ustake balance snapshot
+    pub snapshot_height: u64,
+    /// Hex encoded sha256 merkle root of the snapshot
+    pub merkle_root: String,
+    /// Total ustake of the snapshot
+    pub total_ustake: Uint128,
+    /// Assets locked for the airdrop
+    pub assets: Vec<Asset>,
+    /// Assets already claimed by holders
+    pub claimed: Vec<Asset>,
+    /// Snapshot contract the total was checked against, claims are capped by its balances
+    #[serde(default)]
+    pub ustake_snapshot: Option<Addr>,
 }
 
 #[cw_serde]
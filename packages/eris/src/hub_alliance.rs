@@ -10,7 +10,7 @@ use eris_chain_adapter::types::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::hub::DaoInterface;
+use crate::hub::{DaoInterface, HubVote};
 
 // StageType = DEX
 // DenomType = Chain specific denom
@@ -184,6 +184,18 @@ pub enum QueryMsg {
     /// Returns information about a pair in an object of type [`super::asset::PairInfo`].
     #[returns(PairInfo)]
     Pair {},
+
+    /// The latest vote the hub cast on a proposal. Response: `HubVote`
+    #[returns(HubVote)]
+    Vote {
+        proposal_id: u64,
+    },
+    /// Enumerate all votes the hub cast, by proposal id. Response: `Vec<HubVote>`
+    #[returns(Vec<HubVote>)]
+    Votes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 /// This structure stores the main parameters for an Astroport pair
@@ -9,6 +9,7 @@ pub mod hub;
 pub mod hub_alliance;
 pub mod prop_gauges;
 pub mod querier;
+pub mod ustake_snapshot;
 pub mod voting_escrow;
 
 mod extensions {
@@ -20,6 +20,18 @@ pub struct InstantiateMsg {
     pub hub_addr: String,
     /// Min voting power required
     pub quorum_bps: u16,
+    /// Vote that is cast when a proposal is initialized. If not set, InitProp abstains and SyncProps
+    /// does not vote.
+    pub default_vote: Option<VoteOption>,
+    /// Share of the voted voting power for no with veto that leads to a veto vote
+    pub veto_threshold_bps: Option<u16>,
+    /// Seconds before the end of a proposal in which votes are rejected and the vote can only be
+    /// cast by FinalizeProp (default 0)
+    pub lock_in_s: Option<u64>,
+    /// Tracks the proposals of the multiple choice module of the hub's DAO (`gov_multiple` for
+    /// DaoDao) instead of its main proposal module (default false). As the proposal ids of both
+    /// modules overlap, each module needs its own prop_gauges contract. Can't be changed later.
+    pub gov_multiple: Option<bool>,
 }
 
 /// This structure describes the execute messages available in the contract.
@@ -29,10 +41,23 @@ pub enum ExecuteMsg {
         proposal_id: u64,
     },
 
-    /// Vote allows a vAMP holder to cast votes on which validators should get the delegations
+    /// Initializes all open proposals of the DAO that are not yet known. Can be called by anyone.
+    SyncProps {
+        limit: Option<u32>,
+    },
+
+    /// Casts the final vote of a proposal from the tally frozen at the start of its lock-in window.
+    /// Can be called by anyone.
+    FinalizeProp {
+        proposal_id: u64,
+    },
+
+    /// Vote allows a vAMP holder to cast votes on which validators should get the delegations.
+    /// Multiple choice proposals require the index of the option instead of a vote.
     Vote {
         proposal_id: u64,
-        vote: VoteOption,
+        vote: Option<VoteOption>,
+        option: Option<u32>,
     },
 
     /// Updates the vote for a specified user. Only can be called from the escrow_addr
@@ -44,6 +69,24 @@ pub enum ExecuteMsg {
     UpdateConfig {
         /// ChangeValidatorsLimit changes the max amount of validators that can be voted at once to receive delegations
         quorum_bps: Option<u16>,
+        /// Vote that is cast when a proposal is initialized
+        default_vote: Option<VoteOption>,
+        /// Share of the voted voting power for no with veto that leads to a veto vote
+        veto_threshold_bps: Option<u16>,
+        /// Seconds before the end of a proposal in which votes are rejected and the vote can only be
+        /// cast by FinalizeProp
+        lock_in_s: Option<u64>,
+        /// Unsets the default vote, so that new proposals are not voted on until users vote
+        remove_default_vote: Option<bool>,
+        /// Unsets the veto threshold, so that the hub votes no instead of no with veto
+        remove_veto_threshold: Option<bool>,
+    },
+    /// Overrides the quorum and veto threshold of the config for a single proposal (only owner).
+    /// Removes the override if both are None.
+    SetPropThresholds {
+        proposal_id: u64,
+        quorum_bps: Option<u16>,
+        veto_threshold_bps: Option<u16>,
     },
     // Admin action to remove a user
     RemoveUser {
@@ -129,6 +172,24 @@ pub struct ConfigResponse {
 
     /// Required min quorum (voted voting power / total voting power must be > quorum to allow the contract to vote)
     pub quorum_bps: u16,
+
+    /// Vote that is cast when a proposal is initialized. If not set, InitProp abstains and SyncProps
+    /// does not vote.
+    #[serde(default)]
+    pub default_vote: Option<VoteOption>,
+
+    /// If the share of no with veto of the voted voting power reaches the threshold, the hub votes no with veto.
+    #[serde(default)]
+    pub veto_threshold_bps: Option<u16>,
+
+    /// Seconds before the end of a proposal in which the tally is frozen and votes are rejected.
+    /// The final vote is cast by FinalizeProp.
+    #[serde(default)]
+    pub lock_in_s: u64,
+
+    /// If set, the proposals of the multiple choice module of the hub's DAO are tracked
+    #[serde(default)]
+    pub gov_multiple: bool,
 }
 
 impl ConfigResponse {
@@ -157,15 +218,45 @@ pub struct PropInfo {
     pub abstain_vp: Uint128,
     pub nwv_vp: Uint128,
 
+    /// Voting power per option index, only used by multiple choice proposals
+    #[serde(default)]
+    pub option_vp: Vec<Uint128>,
+
     #[serde(default)]
     pub total_vp: Uint128,
 
     pub current_vote: VoteOption,
+
+    /// Option the hub voted for on a multiple choice proposal
+    #[serde(default)]
+    pub current_option: Option<u32>,
+
+    /// Set by FinalizeProp, afterwards the vote of the hub does not change anymore
+    #[serde(default)]
+    pub finalized: bool,
 }
 
 impl PropInfo {
     pub fn voted_vp(&self) -> Uint128 {
-        self.yes_vp + self.no_vp + self.abstain_vp + self.nwv_vp
+        self.yes_vp
+            + self.no_vp
+            + self.abstain_vp
+            + self.nwv_vp
+            + self.option_vp.iter().copied().sum::<Uint128>()
+    }
+
+    pub fn is_multiple_choice(&self) -> bool {
+        !self.option_vp.is_empty()
+    }
+
+    pub fn reached_veto_threshold(&self, veto_threshold: u16) -> StdResult<bool> {
+        let voted = self.voted_vp();
+        if voted.is_zero() {
+            return Ok(false);
+        }
+
+        let veto = Decimal::from_ratio(self.nwv_vp, voted);
+        Ok(veto >= BasicPoints::try_from(veto_threshold)?.decimal())
     }
 
     pub fn reached_quorum(&self, total_vp: Uint128, quorum: u16) -> StdResult<bool> {
@@ -236,7 +327,12 @@ impl PropInfo {
         votes
     }
 
-    pub fn get_wanted_vote(&self, total_vp: Uint128, quorum: u16) -> StdResult<VoteOption> {
+    pub fn get_wanted_vote(
+        &self,
+        total_vp: Uint128,
+        quorum: u16,
+        veto_threshold: Option<u16>,
+    ) -> StdResult<VoteOption> {
         let current = self.voted_vp();
         let voted = Decimal::from_ratio(current, total_vp);
         let quorum = BasicPoints::try_from(quorum)?.decimal();
@@ -244,6 +340,8 @@ impl PropInfo {
         let result = if voted < quorum {
             // for DAOs automatically abstain
             VoteOption::Abstain
+        } else if veto_threshold.map_or(Ok(false), |veto| self.reached_veto_threshold(veto))? {
+            VoteOption::NoWithVeto
         } else if self.yes_vp >= self.no_vp
             && self.yes_vp >= self.abstain_vp
             && self.yes_vp >= self.nwv_vp
@@ -259,6 +357,23 @@ impl PropInfo {
 
         Ok(result)
     }
+
+    /// Returns the option with the most voting power (first one on equal voting power) or None if the
+    /// quorum is not reached.
+    pub fn get_wanted_option(&self, total_vp: Uint128, quorum: u16) -> StdResult<Option<u32>> {
+        if self.voted_vp().is_zero() || !self.reached_quorum(total_vp, quorum)? {
+            return Ok(None);
+        }
+
+        let mut wanted: Option<(u32, Uint128)> = None;
+        for (index, vp) in self.option_vp.iter().enumerate() {
+            if wanted.map_or(true, |(_, max)| *vp > max) {
+                wanted = Some((index as u32, *vp));
+            }
+        }
+
+        Ok(wanted.map(|(index, _)| index))
+    }
 }
 
 #[cw_serde]
@@ -275,6 +390,8 @@ pub struct UserVotesResponse {
 pub struct UserPropResponseItem {
     pub id: u64,
     pub current_vote: VoteOption,
+    #[serde(default)]
+    pub current_option: Option<u32>,
     pub vp: Uint128,
 }
 
@@ -283,6 +400,15 @@ pub struct UserPropResponseItem {
 pub struct PropDetailResponse {
     pub prop: PropInfo,
     pub user: Option<PropUserInfo>,
+    /// Overrides of the config for the proposal
+    #[serde(default)]
+    pub thresholds: Option<PropThresholds>,
+}
+
+#[cw_serde]
+pub struct PropThresholds {
+    pub quorum_bps: Option<u16>,
+    pub veto_threshold_bps: Option<u16>,
 }
 
 #[cw_serde]
@@ -294,7 +420,10 @@ pub struct PropVotersResponse {
 pub struct PropUserInfo {
     #[serde(default = "default_addr")]
     pub user: Addr,
+    /// Not used when voting for an option of a multiple choice proposal
     pub current_vote: VoteOption,
+    #[serde(default)]
+    pub current_option: Option<u32>,
     pub vp: Uint128,
 }
 
@@ -311,8 +440,11 @@ fn test_weighted_votes_empty() {
         yes_vp: Uint128::zero(),
         no_vp: Uint128::zero(),
         nwv_vp: Uint128::zero(),
+        option_vp: vec![],
         total_vp: Uint128::zero(),
         current_vote: VoteOption::Abstain,
+        current_option: None,
+        finalized: false,
     };
 
     let votes = prop.get_weighted_votes();
@@ -328,8 +460,11 @@ fn test_weighted_votes_some() {
         yes_vp: Uint128::new(10),
         no_vp: Uint128::new(20),
         nwv_vp: Uint128::zero(),
+        option_vp: vec![],
         total_vp: Uint128::zero(),
         current_vote: VoteOption::Abstain,
+        current_option: None,
+        finalized: false,
     };
 
     let votes = prop.get_weighted_votes();
@@ -347,8 +482,11 @@ fn test_weighted_votes_single() {
         yes_vp: Uint128::new(9907854),
         no_vp: Uint128::zero(),
         nwv_vp: Uint128::zero(),
+        option_vp: vec![],
         total_vp: Uint128::zero(),
         current_vote: VoteOption::Abstain,
+        current_option: None,
+        finalized: false,
     };
 
     let votes = prop.get_weighted_votes();
@@ -357,3 +495,54 @@ fn test_weighted_votes_single() {
         vec!["1".to_string()]
     );
 }
+
+#[test]
+fn test_wanted_option() {
+    let mut prop = PropInfo {
+        abstain_vp: Uint128::zero(),
+        period: 100,
+        end_time_s: 100,
+        yes_vp: Uint128::zero(),
+        no_vp: Uint128::zero(),
+        nwv_vp: Uint128::zero(),
+        option_vp: vec![Uint128::new(10), Uint128::new(30), Uint128::new(30)],
+        total_vp: Uint128::zero(),
+        current_vote: VoteOption::Abstain,
+        current_option: None,
+        finalized: false,
+    };
+
+    assert_eq!(prop.get_wanted_option(Uint128::new(100), 5000).unwrap(), Some(1));
+    assert_eq!(prop.get_wanted_option(Uint128::new(200), 5000).unwrap(), None);
+
+    prop.option_vp = vec![Uint128::zero(); 3];
+    assert_eq!(prop.get_wanted_option(Uint128::new(100), 0).unwrap(), None);
+}
+
+#[test]
+fn test_wanted_vote_veto() {
+    let prop = PropInfo {
+        abstain_vp: Uint128::zero(),
+        period: 100,
+        end_time_s: 100,
+        yes_vp: Uint128::new(50),
+        no_vp: Uint128::new(10),
+        nwv_vp: Uint128::new(40),
+        option_vp: vec![],
+        total_vp: Uint128::zero(),
+        current_vote: VoteOption::Abstain,
+        current_option: None,
+        finalized: false,
+    };
+
+    assert_eq!(prop.get_wanted_vote(Uint128::new(100), 5000, None).unwrap(), VoteOption::Yes);
+    assert_eq!(prop.get_wanted_vote(Uint128::new(100), 5000, Some(5000)).unwrap(), VoteOption::Yes);
+    assert_eq!(
+        prop.get_wanted_vote(Uint128::new(100), 5000, Some(3340)).unwrap(),
+        VoteOption::NoWithVeto
+    );
+    assert_eq!(
+        prop.get_wanted_vote(Uint128::new(1000), 5000, Some(3340)).unwrap(),
+        VoteOption::Abstain
+    );
+}
@@ -0,0 +1,90 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use eris_chain_adapter::types::CustomMsgType;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The hub minting and burning the tracked token. Must never be one of the `module_accounts`,
+    /// as the burned ustake is taken from the hub's recorded balance.
+    pub hub: String,
+    /// Full denom of the tracked token
+    pub denom: String,
+    /// Module accounts that are part of mints and burns. Transfers from or to them are ignored by
+    /// the before-send hook, as the hub reports mints and burns itself.
+    pub module_accounts: Vec<String>,
+    /// Balances of the existing holders, required when attaching the contract to a denom that was
+    /// already minted. Transfers of unrecorded balances clamp the sender's balance at zero and emit
+    /// an `erissnapshot/unrecorded_balance` event.
+    #[serde(default)]
+    pub initial_balances: Vec<(String, Uint128)>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Records newly minted tokens of the recipient (only allowed by the hub)
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Records tokens burned from the hub's balance (only allowed by the hub)
+    Burn {
+        amount: Uint128,
+    },
+}
+
+impl ExecuteMsg {
+    pub fn into_msg(&self, contract_addr: &Addr) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&self)?,
+            funds: vec![],
+        }))
+    }
+}
+
+/// Tokenfactory before-send hook, called by the chain for every transfer of the denom. Balances
+/// are recorded by `TrackBeforeSend`, `BlockBeforeSend` never blocks a transfer.
+#[cw_serde]
+pub enum SudoMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+    TrackBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Response: `Config`
+    #[returns(Config)]
+    Config {},
+    /// Balance of the address at the start of the block `height`, the current balance if no
+    /// height is provided. Response: `cw20::BalanceResponse`
+    #[returns(cw20::BalanceResponse)]
+    BalanceAt {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Total supply at the start of the block `height`, the current supply if no height is
+    /// provided. Response: `Uint128`
+    #[returns(Uint128)]
+    TotalSupplyAt {
+        height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct Config {
+    pub hub: Addr,
+    pub denom: String,
+    pub module_accounts: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}